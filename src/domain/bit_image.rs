@@ -2,43 +2,13 @@
 
 #![cfg(feature = "graphics")]
 
+use super::dithering::{self, Dithering};
+use super::image_transform::*;
+use super::raster::{BitImageMode, BitImageSize, RasterBitImage};
 use crate::errors::{PrinterError, Result};
 #[cfg(not(feature = "std"))]
-use alloc::{borrow::ToOwned, string::String, string::ToString, vec, vec::Vec};
-use core::fmt;
-use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
-
-/// BitImage size
-#[derive(Debug, Default, Clone, Copy)]
-pub enum BitImageSize {
-    #[default]
-    Normal,
-    DoubleWidth,
-    DoubleHeight,
-    DoubleWidthAndHeight,
-}
-
-impl fmt::Display for BitImageSize {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            BitImageSize::Normal => write!(f, "Normal"),
-            BitImageSize::DoubleWidth => write!(f, "Double width"),
-            BitImageSize::DoubleHeight => write!(f, "Double height"),
-            BitImageSize::DoubleWidthAndHeight => write!(f, "Double width and height"),
-        }
-    }
-}
-
-impl From<&BitImageSize> for u8 {
-    fn from(size: &BitImageSize) -> Self {
-        match size {
-            BitImageSize::Normal => 0,
-            BitImageSize::DoubleWidth => 1,
-            BitImageSize::DoubleHeight => 2,
-            BitImageSize::DoubleWidthAndHeight => 3,
-        }
-    }
-}
+use alloc::{borrow::ToOwned, format, string::String, string::ToString, vec, vec::Vec};
+use image::{DynamicImage, GenericImageView, GrayImage, Rgba, RgbaImage};
 
 /// Bit image option
 #[derive(Debug)]
@@ -49,6 +19,24 @@ pub struct BitImageOption {
     max_height: Option<u32>,
     /// Image size
     size: BitImageSize,
+    /// Print mode (`None`: the printer options mode)
+    mode: Option<BitImageMode>,
+    /// Band height in lines (`None`: the printer options band height)
+    band_height: Option<u16>,
+    /// Fit mode in the max width x max height box
+    fit: ImageFit,
+    /// Resampling filter
+    filter: ImageFilter,
+    /// Horizontal alignment (the image is padded to the max width)
+    alignment: ImageAlignment,
+    /// Rotation
+    rotation: ImageRotation,
+    /// Dithering algorithm
+    dithering: Dithering,
+    /// Stretch the image contrast before the dithering
+    auto_contrast: bool,
+    /// Gamma correction applied before the dithering
+    gamma: Option<f32>,
 }
 
 impl Default for BitImageOption {
@@ -57,6 +45,15 @@ impl Default for BitImageOption {
             max_width: Some(512),
             max_height: Some(512),
             size: BitImageSize::Normal,
+            mode: None,
+            band_height: None,
+            fit: ImageFit::default(),
+            filter: ImageFilter::default(),
+            alignment: ImageAlignment::default(),
+            rotation: ImageRotation::default(),
+            dithering: Dithering::default(),
+            auto_contrast: false,
+            gamma: None,
         }
     }
 }
@@ -83,8 +80,110 @@ impl BitImageOption {
             max_width,
             max_height,
             size,
+            ..Default::default()
         })
     }
+
+    /// Set the print mode (default: the printer options bit image mode, [`BitImageMode::Raster`] by default)
+    ///
+    /// The column modes (`ESC *`) are supported by older printers which ignore `GS v 0`.
+    /// The image size is not used in these modes.
+    ///
+    /// ```
+    /// use escpos::utils::{BitImageMode, BitImageOption};
+    ///
+    /// let option = BitImageOption::default().mode(BitImageMode::Column24DoubleDensity);
+    /// ```
+    pub fn mode(mut self, mode: BitImageMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Set the print mode if it is not already set
+    pub(crate) fn default_mode(mut self, mode: BitImageMode) -> Self {
+        self.mode.get_or_insert(mode);
+        self
+    }
+
+    /// Set the band height (in lines)
+    ///
+    /// Tall images are split in bands sent as consecutive commands to avoid overflowing
+    /// the printer receive buffer (default: the printer options band height).
+    pub fn band_height(mut self, band_height: u16) -> Result<Self> {
+        if band_height == 0 {
+            return Err(PrinterError::Input(
+                "bit image band height must be greater than 0".to_owned(),
+            ));
+        }
+        self.band_height = Some(band_height);
+        Ok(self)
+    }
+
+    /// Set the band height if it is not already set
+    pub(crate) fn default_band_height(mut self, band_height: Option<u16>) -> Self {
+        self.band_height = self.band_height.or(band_height);
+        self
+    }
+
+    /// Set the fit mode in the max width x max height box (default: [`ImageFit::Shrink`])
+    ///
+    /// ```
+    /// use escpos::utils::{BitImageOption, BitImageSize, ImageAlignment, ImageFilter, ImageFit};
+    ///
+    /// let option = BitImageOption::new(Some(576), Some(512), BitImageSize::Normal)
+    ///     .unwrap()
+    ///     .fit(ImageFit::Width)
+    ///     .filter(ImageFilter::Lanczos3)
+    ///     .alignment(ImageAlignment::Center);
+    /// ```
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Set the resampling filter (default: [`ImageFilter::Nearest`])
+    pub fn filter(mut self, filter: ImageFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set the horizontal alignment
+    ///
+    /// The image is padded with white dots to the max width (usually the paper width in dots).
+    pub fn alignment(mut self, alignment: ImageAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Set the rotation (applied before scaling)
+    pub fn rotation(mut self, rotation: ImageRotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Set the dithering algorithm (default: [`Dithering::Threshold`] with 128)
+    ///
+    /// ```
+    /// use escpos::utils::{BitImageOption, Dithering};
+    ///
+    /// let option = BitImageOption::default().dithering(Dithering::FloydSteinberg).auto_contrast(true);
+    /// ```
+    pub fn dithering(mut self, dithering: Dithering) -> Self {
+        self.dithering = dithering;
+        self
+    }
+
+    /// Stretch the image contrast before the dithering
+    pub fn auto_contrast(mut self, auto_contrast: bool) -> Self {
+        self.auto_contrast = auto_contrast;
+        self
+    }
+
+    /// Set the gamma correction applied before the dithering (a value greater than 1 lightens the image)
+    pub fn gamma(mut self, gamma: f32) -> Result<Self> {
+        self.gamma = Some(dithering::check_gamma(gamma)?);
+        Ok(self)
+    }
 }
 
 #[derive(Debug)]
@@ -98,48 +197,55 @@ impl BitImage {
     /// Create a new image
     pub fn new(path: &str, option: BitImageOption) -> Result<Self> {
         let img = image::open(path)?;
-        Self::from_dynamic_image(img, option, path)
+        Self::from_image(img, option, path)
     }
 
-    /// Create a new image from bytes
+    /// Create a new image from bytes (encoded image file: PNG, JPEG, etc.)
     pub fn from_bytes(bytes: &[u8], option: BitImageOption) -> Result<Self> {
         let img = image::load_from_memory(bytes)?;
-        Self::from_dynamic_image(img, option, "")
+        Self::from_image(img, option, "")
     }
 
     /// Create a new image from `DynamicImage`
-    fn from_dynamic_image(img: DynamicImage, option: BitImageOption, path: &str) -> Result<Self> {
-        // Resize image with max width and max height constraints and convert to grayscale
-        let mut img = match (option.max_width, option.max_height) {
-            (Some(max_width), None) => {
-                if img.width() > max_width {
-                    img.resize(max_width, max_width, image::imageops::Nearest)
-                } else {
-                    img
-                }
-            }
-            (None, Some(max_height)) => {
-                if img.height() > max_height {
-                    img.resize(max_height, max_height, image::imageops::Nearest)
-                } else {
-                    img
-                }
-            }
-            (Some(max_width), Some(max_height)) => {
-                if img.width() > max_width || img.height() > max_height {
-                    img.resize(max_width, max_height, image::imageops::Nearest)
-                } else {
-                    img
-                }
-            }
-            _ => img,
-        };
+    pub fn from_dynamic_image(img: DynamicImage, option: BitImageOption) -> Result<Self> {
+        Self::from_image(img, option, "")
+    }
+
+    /// Create a new image from RGBA pixels (4 bytes per pixel, row by row)
+    pub fn from_rgba(pixels: &[u8], width: u32, height: u32, option: BitImageOption) -> Result<Self> {
+        let img = RgbaImage::from_raw(width, height, pixels.to_vec()).ok_or_else(|| {
+            PrinterError::Input(format!(
+                "invalid RGBA pixels length for a {width} x {height} image: {}",
+                pixels.len()
+            ))
+        })?;
+        Self::from_image(DynamicImage::ImageRgba8(img), option, "")
+    }
 
-        // Remove alpha canal
-        Self::remove_alpha(&mut img);
+    /// Create a new image from luma pixels (1 byte per pixel, row by row, 0 is black)
+    pub fn from_luma(pixels: &[u8], width: u32, height: u32, option: BitImageOption) -> Result<Self> {
+        let img = GrayImage::from_raw(width, height, pixels.to_vec()).ok_or_else(|| {
+            PrinterError::Input(format!(
+                "invalid luma pixels length for a {width} x {height} image: {}",
+                pixels.len()
+            ))
+        })?;
+        Self::from_image(DynamicImage::ImageLuma8(img), option, "")
+    }
+
+    /// Create a new image from `DynamicImage` and its path
+    fn from_image(img: DynamicImage, option: BitImageOption, path: &str) -> Result<Self> {
+        let transform = ImageTransform {
+            max_width: option.max_width,
+            max_height: option.max_height,
+            fit: option.fit,
+            filter: option.filter,
+            alignment: option.alignment,
+            rotation: option.rotation,
+        };
 
-        // Make gray scale
-        img = img.grayscale();
+        // Rotate, resize, align and convert to grayscale
+        let img = transform.apply(img).grayscale();
 
         Ok(Self {
             path: path.to_string(),
@@ -148,32 +254,13 @@ impl BitImage {
         })
     }
 
-    /// Remove alpha canal in image
-    fn remove_alpha(img: &mut DynamicImage) {
-        for y in 0..img.height() {
-            for x in 0..img.width() {
-                let old = img.get_pixel(x, y);
-                let alpha = old.0[3] as u32;
-                let inverse_alpha = 255 - alpha;
-
-                let new = Rgba::from([
-                    ((old.0[0] as u32 * alpha + inverse_alpha * 255) / 255) as u8,
-                    ((old.0[1] as u32 * alpha + inverse_alpha * 255) / 255) as u8,
-                    ((old.0[2] as u32 * alpha + inverse_alpha * 255) / 255) as u8,
-                    255,
-                ]);
-                img.put_pixel(x, y, new);
-            }
-        }
-    }
-
     /// Get image width
-    fn width(&self) -> Result<u16> {
+    pub(crate) fn width(&self) -> Result<u16> {
         Ok(u16::try_from(self.image.width())?)
     }
 
     /// Get image height
-    fn height(&self) -> Result<u16> {
+    pub(crate) fn height(&self) -> Result<u16> {
         Ok(u16::try_from(self.image.height())?)
     }
 
@@ -203,6 +290,16 @@ impl BitImage {
         &self.option.size
     }
 
+    /// Get print mode
+    pub fn mode(&self) -> BitImageMode {
+        self.option.mode.unwrap_or_default()
+    }
+
+    /// Get band height
+    pub fn band_height(&self) -> Option<u16> {
+        self.option.band_height
+    }
+
     /// Get image width in bytes
     pub fn with_bytes_u8(&self) -> Result<Vec<u8>> {
         let width = self.width_bytes()?;
@@ -225,15 +322,23 @@ impl BitImage {
         Ok(vec![u8::try_from(yl)?, u8::try_from(yh)?])
     }
 
-    /// Is the pixel black?
-    fn is_pixel_black(&self, x: u16, y: u16) -> bool {
-        self.pixel(u32::from(x), u32::from(y)).0[0] <= 128
+    /// Get the printed pixels (row by row), after the contrast adjustment and the dithering
+    fn black_pixels(&self) -> Vec<bool> {
+        let mut luma: Vec<u8> = self.image.to_luma8().into_raw();
+        dithering::adjust(&mut luma, self.option.auto_contrast, self.option.gamma);
+
+        let (width, height) = (self.image.width() as usize, self.image.height() as usize);
+        dithering::dither(&luma, width, height, self.option.dithering, 2)
+            .into_iter()
+            .map(|tone| tone == 1)
+            .collect()
     }
 
     /// Get image raster data
     pub fn raster_data(&self) -> Result<Vec<u8>> {
         let width = self.width()?;
-        let height = self.height()?;
+        let height = self.image.height();
+        let black_pixels = self.black_pixels();
         let mut data = Vec::new();
 
         for y in 0..height {
@@ -252,7 +357,8 @@ impl BitImage {
                     }
 
                     // Shift byte to the left, adding the pixel value at the end
-                    byte = (byte << 1) | u8::from(self.is_pixel_black(x_offset, y));
+                    byte =
+                        (byte << 1) | u8::from(black_pixels[y as usize * usize::from(width) + usize::from(x_offset)]);
                 }
 
                 data.push(byte);
@@ -261,4 +367,95 @@ impl BitImage {
 
         Ok(data)
     }
+
+    /// Get the raster bit image
+    pub fn raster(&self) -> Result<RasterBitImage> {
+        RasterBitImage::new(
+            self.width()?,
+            self.image.height(),
+            self.raster_data()?,
+            self.option.size,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, ImageFormat, Luma};
+    use std::io::Cursor;
+
+    /// Encode a synthetic grayscale image in PNG
+    fn gray_image(width: u32, height: u32, luma: impl Fn(u32, u32) -> u8) -> Vec<u8> {
+        let img = GrayImage::from_fn(width, height, |x, y| Luma([luma(x, y)]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png).unwrap();
+        bytes
+    }
+
+    /// Number of printed dots in the columns `columns` of raster data with 8 bytes per line
+    fn dots(data: &[u8], columns: core::ops::Range<usize>) -> usize {
+        data.chunks(8)
+            .map(|line| {
+                columns
+                    .clone()
+                    .filter(|&x| line[x / 8] & (0x80 >> (x % 8)) != 0)
+                    .count()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_raster_data_threshold() {
+        let bytes = gray_image(64, 2, |x, _| (x * 4) as u8);
+
+        let image = BitImage::from_bytes(&bytes, BitImageOption::default()).unwrap();
+        assert_eq!(
+            image.raster_data().unwrap(),
+            [[0xFF, 0xFF, 0xFF, 0xFF, 0x80, 0, 0, 0]; 2].concat()
+        );
+
+        let option = BitImageOption::default().dithering(Dithering::Threshold(63));
+        let image = BitImage::from_bytes(&bytes, option).unwrap();
+        assert_eq!(
+            image.raster_data().unwrap(),
+            [[0xFF, 0xFF, 0, 0, 0, 0, 0, 0]; 2].concat()
+        );
+    }
+
+    #[test]
+    fn test_raster_data_dithering_gradient() {
+        let bytes = gray_image(64, 64, |x, _| (x * 4) as u8);
+
+        for dithering in [Dithering::FloydSteinberg, Dithering::Atkinson, Dithering::Bayer] {
+            let option = BitImageOption::default().dithering(dithering);
+            let data = BitImage::from_bytes(&bytes, option).unwrap().raster_data().unwrap();
+            assert_eq!(data.len(), 8 * 64);
+
+            // The darker the columns, the more dots are printed
+            let bands: Vec<usize> = (0..4).map(|band| dots(&data, band * 16..(band + 1) * 16)).collect();
+            assert!(bands.windows(2).all(|w| w[0] > w[1]), "{dithering}: {bands:?}");
+
+            // No solid black or white areas in the middle tones
+            assert!(bands[1] < 16 * 64 && bands[2] > 0, "{dithering}: {bands:?}");
+        }
+    }
+
+    #[test]
+    fn test_raster_data_auto_contrast_and_gamma() {
+        // Low contrast image: every pixel is printed with the default threshold
+        let bytes = gray_image(16, 1, |x, _| 100 + x as u8);
+        let image = BitImage::from_bytes(&bytes, BitImageOption::default()).unwrap();
+        assert_eq!(image.raster_data().unwrap(), vec![0xFF, 0xFF]);
+
+        let option = BitImageOption::default().auto_contrast(true);
+        let image = BitImage::from_bytes(&bytes, option).unwrap();
+        assert_eq!(image.raster_data().unwrap(), vec![0xFF, 0x00]);
+
+        let option = BitImageOption::default().gamma(2.0).unwrap();
+        let image = BitImage::from_bytes(&bytes, option).unwrap();
+        assert_eq!(image.raster_data().unwrap(), vec![0x00, 0x00]);
+
+        assert!(BitImageOption::default().gamma(-1.0).is_err());
+    }
 }
@@ -1,8 +1,15 @@
 //! Aztec code
 
+use super::capacity::{self, Capacity};
+#[cfg(feature = "graphics")]
+use super::render::{self, CodeRendering};
+#[cfg(feature = "graphics")]
+use crate::domain::RasterBitImage;
 use crate::errors::{PrinterError, Result};
+use crate::io::encoder::Encoder;
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt;
 
 /// Aztec code mode
@@ -58,6 +65,9 @@ pub struct AztecOption {
     mode: AztecMode,
     size: u8,
     correction_level: u8,
+    encoder: Encoder,
+    #[cfg(feature = "graphics")]
+    rendering: Option<CodeRendering>,
 }
 
 impl Default for AztecOption {
@@ -66,6 +76,9 @@ impl Default for AztecOption {
             mode: AztecMode::default(),
             size: 3,
             correction_level: 23,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
         }
     }
 }
@@ -87,9 +100,25 @@ impl AztecOption {
             mode,
             size,
             correction_level,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
         })
     }
 
+    #[cfg(feature = "graphics")]
+    /// Set the [rendering](CodeRendering) of the Aztec code (default: printer options)
+    pub fn rendering(mut self, rendering: CodeRendering) -> Self {
+        self.rendering = Some(rendering);
+        self
+    }
+
+    /// Set the [encoder](Encoder) of the text data (default: UTF-8)
+    pub fn encoding(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
     /// Get mode
     pub fn mode(&self) -> AztecMode {
         self.mode
@@ -104,6 +133,36 @@ impl AztecOption {
     pub fn correction_level(&self) -> u8 {
         self.correction_level
     }
+
+    /// Get the encoder of the text data
+    pub(crate) fn encoder(&self) -> &Encoder {
+        &self.encoder
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the rendering if it is set
+    pub(crate) fn code_rendering(&self) -> Option<CodeRendering> {
+        self.rendering
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the Aztec code of the data as a raster bit image
+    pub(crate) fn raster(&self, data: &[u8]) -> Result<RasterBitImage> {
+        render::aztec::render(data, self)
+    }
+
+    /// Capacity of the largest symbol of the mode (compact: 4 layers, full-range: 32 layers)
+    fn capacity(&self) -> Capacity {
+        match self.mode {
+            AztecMode::Compact(_) => Capacity::new(110, 89, 53),
+            AztecMode::FullRange(_) => Capacity::new(3832, 3067, 1914),
+        }
+    }
+
+    /// Check that the data fits the largest Aztec code symbol of the mode
+    pub(crate) fn check_data(&self, data: &[u8]) -> Result<()> {
+        self.capacity().check("Aztec code", data, capacity::is_upper_case_text)
+    }
 }
 
 /// Aztec code
@@ -121,6 +180,32 @@ impl Aztec {
             option,
         }
     }
+
+    /// Get the data encoded by the option [encoder](AztecOption::encoding) and check its length
+    pub(crate) fn encoded_data(&self) -> Result<Vec<u8>> {
+        let data = self.option.encoder().encode(&self.data)?;
+        self.option.check_data(&data)?;
+        Ok(data)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the Aztec code as a raster bit image
+    ///
+    /// The option size is the size of a module in dots.
+    /// The symbol is the smallest one of the option mode which holds the data and the error correction level.
+    ///
+    /// ```
+    /// use escpos::utils::{Aztec, AztecMode, AztecOption};
+    ///
+    /// let option = AztecOption::new(AztecMode::Compact(0), 4, 23).unwrap();
+    /// let raster = Aztec::new("Hello", option).raster().unwrap();
+    ///
+    /// // 15 modules and 2 quiet zones of 1 module, 4 dots per module
+    /// assert_eq!((raster.width(), raster.height()), (68, 68));
+    /// ```
+    pub fn raster(&self) -> Result<RasterBitImage> {
+        self.option.raster(&self.encoded_data()?)
+    }
 }
 
 #[cfg(test)]
@@ -159,4 +244,21 @@ mod tests {
         assert!(AztecOption::new(AztecMode::default(), 3, 4).is_err());
         assert!(AztecOption::new(AztecMode::default(), 3, 96).is_err());
     }
+
+    #[test]
+    fn test_aztec_check_data() {
+        let option = AztecOption::default();
+        assert!(option.check_data("A".repeat(3067).as_bytes()).is_ok());
+        assert!(option.check_data("A".repeat(3068).as_bytes()).is_err());
+        let option = AztecOption::new(AztecMode::Compact(0), 3, 23).unwrap();
+        assert!(option.check_data("1".repeat(110).as_bytes()).is_ok());
+        assert!(option.check_data("1".repeat(111).as_bytes()).is_err());
+        assert!(option.check_data(&[0xFF; 53]).is_ok());
+        assert!(option.check_data(&[0xFF; 54]).is_err());
+        assert!(
+            Aztec::new(&"A".repeat(3068), AztecOption::default())
+                .encoded_data()
+                .is_err()
+        );
+    }
 }
@@ -3,6 +3,12 @@
 #![cfg(feature = "barcodes")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+use super::check_digit::{self, digits_string};
+#[cfg(feature = "graphics")]
+use super::render::{self, CodeRendering};
+use super::{code128, gs1};
+#[cfg(feature = "graphics")]
+use crate::domain::RasterBitImage;
 use crate::errors::{PrinterError, Result};
 use alloc::format;
 use alloc::string::{String, ToString};
@@ -17,7 +23,10 @@ const CODABAR_VALID_CHARS: [char; 24] = [
     ':',
 ];
 
-/// Barcode system (function A used)
+/// Maximum number of characters of the `GS k` function B data
+const FUNCTION_B_MAX_DATA_SIZE: usize = 255;
+
+/// Barcode system (function A used, function B for CODE93, CODE128, GS1-128 and GS1 DataBar)
 #[derive(Debug, Clone, Copy)]
 pub enum BarcodeSystem {
     UPCA,
@@ -27,6 +36,18 @@ pub enum BarcodeSystem {
     CODE39,
     ITF,
     CODABAR,
+    CODE93,
+    CODE128,
+    /// GS1-128 (data with the AIs in parentheses: `(01)09501101530003(10)AB-123`)
+    GS1_128,
+    /// GS1 DataBar Omnidirectional (13 digits of the GTIN, without the check digit)
+    GS1DataBarOmnidirectional,
+    /// GS1 DataBar Truncated (13 digits of the GTIN, without the check digit)
+    GS1DataBarTruncated,
+    /// GS1 DataBar Limited (13 digits of the GTIN starting with `0` or `1`, without the check digit)
+    GS1DataBarLimited,
+    /// GS1 DataBar Expanded (data with the AIs in parentheses: `(01)09501101530003(10)AB-123`)
+    GS1DataBarExpanded,
 }
 
 impl From<BarcodeSystem> for u8 {
@@ -39,6 +60,13 @@ impl From<BarcodeSystem> for u8 {
             BarcodeSystem::CODE39 => 4,
             BarcodeSystem::ITF => 5,
             BarcodeSystem::CODABAR => 6,
+            BarcodeSystem::CODE93 => 72,
+            BarcodeSystem::CODE128 => 73,
+            BarcodeSystem::GS1_128 => 74,
+            BarcodeSystem::GS1DataBarOmnidirectional => 75,
+            BarcodeSystem::GS1DataBarTruncated => 76,
+            BarcodeSystem::GS1DataBarLimited => 77,
+            BarcodeSystem::GS1DataBarExpanded => 78,
         }
     }
 }
@@ -53,6 +81,13 @@ impl fmt::Display for BarcodeSystem {
             BarcodeSystem::CODE39 => write!(f, "CODE39"),
             BarcodeSystem::ITF => write!(f, "ITF"),
             BarcodeSystem::CODABAR => write!(f, "CODABAR"),
+            BarcodeSystem::CODE93 => write!(f, "CODE93"),
+            BarcodeSystem::CODE128 => write!(f, "CODE128"),
+            BarcodeSystem::GS1_128 => write!(f, "GS1-128"),
+            BarcodeSystem::GS1DataBarOmnidirectional => write!(f, "GS1 DataBar Omnidirectional"),
+            BarcodeSystem::GS1DataBarTruncated => write!(f, "GS1 DataBar Truncated"),
+            BarcodeSystem::GS1DataBarLimited => write!(f, "GS1 DataBar Limited"),
+            BarcodeSystem::GS1DataBarExpanded => write!(f, "GS1 DataBar Expanded"),
         }
     }
 }
@@ -215,6 +250,9 @@ pub struct BarcodeOption {
     height: BarcodeHeight,
     font: BarcodeFont,
     position: BarcodePosition,
+    check_digit: bool,
+    #[cfg(feature = "graphics")]
+    rendering: Option<CodeRendering>,
 }
 
 impl Default for BarcodeOption {
@@ -224,6 +262,9 @@ impl Default for BarcodeOption {
             height: BarcodeHeight::default(),
             font: BarcodeFont::A,
             position: BarcodePosition::Below,
+            check_digit: false,
+            #[cfg(feature = "graphics")]
+            rendering: None,
         }
     }
 }
@@ -236,9 +277,29 @@ impl BarcodeOption {
             height,
             font,
             position,
+            check_digit: false,
+            #[cfg(feature = "graphics")]
+            rendering: None,
         }
     }
 
+    /// Add the optional check digit to CODE39 (modulo 43) and ITF (modulo 10) barcodes (default: `false`)
+    ///
+    /// EAN / UPC check digits are always computed when missing and verified when provided.
+    pub fn check_digit(mut self, check_digit: bool) -> Self {
+        self.check_digit = check_digit;
+        self
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the [rendering](CodeRendering) of the barcode (default: printer options)
+    ///
+    /// With [`CodeRendering::Image`], the HRI characters are printed as text with the current font.
+    pub fn rendering(mut self, rendering: CodeRendering) -> Self {
+        self.rendering = Some(rendering);
+        self
+    }
+
     /// Get width
     pub fn width(&self) -> BarcodeWidth {
         self.width
@@ -258,6 +319,17 @@ impl BarcodeOption {
     pub fn position(&self) -> BarcodePosition {
         self.position
     }
+
+    /// Is the optional check digit added?
+    pub fn has_check_digit(&self) -> bool {
+        self.check_digit
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the rendering if it is set
+    pub(crate) fn code_rendering(&self) -> Option<CodeRendering> {
+        self.rendering
+    }
 }
 
 /// Barcode
@@ -270,14 +342,86 @@ pub struct Barcode {
 
 impl Barcode {
     /// Create a new `Barcode`
+    ///
+    /// The EAN / UPC check digit is added when missing and verified when provided,
+    /// UPC-E data is stored with its number system and check digit (8 digits).
+    ///
+    /// ```
+    /// use escpos::utils::{Barcode, BarcodeOption, BarcodeSystem};
+    ///
+    /// let barcode = Barcode::new(BarcodeSystem::EAN13, "400638133393", BarcodeOption::default()).unwrap();
+    /// assert_eq!(barcode.data, "4006381333931");
+    /// assert!(Barcode::new(BarcodeSystem::EAN13, "4006381333932", BarcodeOption::default()).is_err());
+    ///
+    /// let option = BarcodeOption::default().check_digit(true);
+    /// let barcode = Barcode::new(BarcodeSystem::ITF, "1234567", option).unwrap();
+    /// assert_eq!(barcode.data, "12345670");
+    /// ```
     pub fn new(system: BarcodeSystem, data: &str, option: BarcodeOption) -> Result<Self> {
         Self::validate(system, data)?;
+        let data = Self::check_digit(system, data, &option)?;
+
+        Ok(Self { system, data, option })
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the barcode as a raster bit image
+    ///
+    /// The width of the narrow bars and the height come from the option.
+    ///
+    /// ```
+    /// use escpos::utils::{Barcode, BarcodeOption, BarcodeSystem};
+    ///
+    /// let barcode = Barcode::new(BarcodeSystem::EAN8, "9638507", BarcodeOption::default()).unwrap();
+    /// let raster = barcode.raster().unwrap();
+    ///
+    /// // 67 modules and 2 quiet zones of 11 modules, 3 dots per module
+    /// assert_eq!(raster.width(), 267);
+    /// assert_eq!(raster.height(), 102);
+    /// ```
+    pub fn raster(&self) -> Result<RasterBitImage> {
+        Ok(self.render()?.0)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the barcode as a raster bit image with its human readable interpretation (HRI)
+    pub(crate) fn render(&self) -> Result<(RasterBitImage, String)> {
+        render::barcode::render(self)
+    }
+
+    /// Add or verify the check digit
+    fn check_digit(system: BarcodeSystem, data: &str, option: &BarcodeOption) -> Result<String> {
+        match system {
+            BarcodeSystem::EAN13 => Ok(digits_string(&check_digit::gtin_digits(data, 13, "EAN13")?)),
+            BarcodeSystem::EAN8 => Ok(digits_string(&check_digit::gtin_digits(data, 8, "EAN8")?)),
+            BarcodeSystem::UPCA => Ok(digits_string(&check_digit::gtin_digits(data, 12, "UPC-A")?)),
+            BarcodeSystem::UPCE => Ok(digits_string(&check_digit::upce_digits(data)?)),
+            BarcodeSystem::CODE39 if option.check_digit => {
+                // The check character is placed before the stop character
+                let (start, stop) = (data.starts_with('*'), data.len() > 1 && data.ends_with('*'));
+                let content = &data[usize::from(start)..data.len() - usize::from(stop)];
+                let check = check_digit::code39_check_character(content)
+                    .ok_or_else(|| PrinterError::Input(format!("invalid CODE39 data: {data}")))?;
+
+                Ok(format!(
+                    "{}{content}{check}{}",
+                    &data[..usize::from(start)],
+                    &data[data.len() - usize::from(stop)..]
+                ))
+            }
+            BarcodeSystem::ITF if option.check_digit => {
+                let mut digits = check_digit::parse_digits(data, "ITF")?;
+                if digits.len() % 2 == 0 {
+                    return Err(PrinterError::Input(format!(
+                        "ITF data with a check digit must have an odd number of digits: {data}"
+                    )));
+                }
+                digits.push(gs1::gtin_check_digit(&digits));
 
-        Ok(Self {
-            system,
-            data: data.to_string(),
-            option,
-        })
+                Ok(digits_string(&digits))
+            }
+            _ => Ok(data.to_string()),
+        }
     }
 
     /// Validate data
@@ -338,6 +482,37 @@ impl Barcode {
                     Err(PrinterError::Input(format!("invalid CODABAR data: {data}")))
                 }
             }
+            BarcodeSystem::CODE93 => {
+                if (1..=FUNCTION_B_MAX_DATA_SIZE).contains(&data_len) && data.is_ascii() {
+                    Ok(())
+                } else {
+                    Err(PrinterError::Input(format!("invalid CODE93 data: {data}")))
+                }
+            }
+            BarcodeSystem::CODE128 => code128::validate(data),
+            BarcodeSystem::GS1_128 | BarcodeSystem::GS1DataBarExpanded => {
+                let size = gs1::escpos_data(&gs1::parse(data)?).len();
+                if size > FUNCTION_B_MAX_DATA_SIZE {
+                    return Err(PrinterError::Input(format!(
+                        "{system} data is too long ({size} bytes, the maximum is {FUNCTION_B_MAX_DATA_SIZE})"
+                    )));
+                }
+                Ok(())
+            }
+            BarcodeSystem::GS1DataBarOmnidirectional | BarcodeSystem::GS1DataBarTruncated => {
+                if is_data_all_digits && data_len == 13 {
+                    Ok(())
+                } else {
+                    Err(PrinterError::Input(format!("invalid {system} data: {data}")))
+                }
+            }
+            BarcodeSystem::GS1DataBarLimited => {
+                if is_data_all_digits && data_len == 13 && (data.starts_with('0') || data.starts_with('1')) {
+                    Ok(())
+                } else {
+                    Err(PrinterError::Input(format!("invalid {system} data: {data}")))
+                }
+            }
         }
     }
 }
@@ -441,4 +616,96 @@ mod tests {
         assert!(Barcode::validate(BarcodeSystem::CODABAR, "9805f8740701009").is_err());
         assert!(Barcode::validate(BarcodeSystem::CODABAR, "98f874d0d70s09").is_err());
     }
+
+    #[test]
+    fn test_barcode_validate_code128() {
+        assert!(Barcode::validate(BarcodeSystem::CODE128, "1").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::CODE128, "Order #1234-ab {x}").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::CODE128, "\tTab").is_ok());
+
+        assert!(Barcode::validate(BarcodeSystem::CODE128, "").is_err());
+        assert!(Barcode::validate(BarcodeSystem::CODE128, "Café").is_err());
+    }
+
+    #[test]
+    fn test_barcode_validate_code93() {
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "Code 93").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "\tTab").is_ok());
+
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "").is_err());
+        assert!(Barcode::validate(BarcodeSystem::CODE93, "Café").is_err());
+        assert!(Barcode::validate(BarcodeSystem::CODE93, &"9".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn test_barcode_validate_gs1() {
+        for system in [BarcodeSystem::GS1_128, BarcodeSystem::GS1DataBarExpanded] {
+            assert!(Barcode::validate(system, "(01)09501101530003(17)250101(10)AB-123").is_ok());
+
+            assert!(Barcode::validate(system, "0109501101530003").is_err());
+            assert!(Barcode::validate(system, "(01)09501101530004").is_err());
+            assert!(Barcode::validate(system, "(17)251301").is_err());
+        }
+
+        assert!(Barcode::validate(BarcodeSystem::GS1DataBarOmnidirectional, "0950110153000").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::GS1DataBarTruncated, "0950110153000").is_ok());
+        assert!(Barcode::validate(BarcodeSystem::GS1DataBarLimited, "1950110153000").is_ok());
+
+        assert!(Barcode::validate(BarcodeSystem::GS1DataBarOmnidirectional, "09501101530003").is_err());
+        assert!(Barcode::validate(BarcodeSystem::GS1DataBarTruncated, "095011015300a").is_err());
+        assert!(Barcode::validate(BarcodeSystem::GS1DataBarLimited, "2950110153000").is_err());
+    }
+
+    #[test]
+    fn test_barcode_check_digit() {
+        let option = BarcodeOption::default();
+        let barcode = |system, data, option| Barcode::new(system, data, option).map(|barcode| barcode.data);
+
+        assert_eq!(
+            barcode(BarcodeSystem::EAN13, "400638133393", option.clone()).unwrap(),
+            "4006381333931"
+        );
+        assert_eq!(
+            barcode(BarcodeSystem::EAN8, "9638507", option.clone()).unwrap(),
+            "96385074"
+        );
+        assert_eq!(
+            barcode(BarcodeSystem::UPCA, "03600029145", option.clone()).unwrap(),
+            "036000291452"
+        );
+        assert_eq!(
+            barcode(BarcodeSystem::UPCE, "123456", option.clone()).unwrap(),
+            "01234565"
+        );
+        assert_eq!(
+            barcode(BarcodeSystem::UPCE, "012345000065", option.clone()).unwrap(),
+            "01234565"
+        );
+        assert!(barcode(BarcodeSystem::EAN13, "4006381333932", option.clone()).is_err());
+        assert!(barcode(BarcodeSystem::EAN8, "96385075", option.clone()).is_err());
+        assert!(barcode(BarcodeSystem::UPCA, "036000291453", option.clone()).is_err());
+        assert!(barcode(BarcodeSystem::UPCE, "01234564", option.clone()).is_err());
+
+        // Optional check digits
+        assert_eq!(
+            barcode(BarcodeSystem::CODE39, "123ABC", option.clone()).unwrap(),
+            "123ABC"
+        );
+        assert_eq!(barcode(BarcodeSystem::ITF, "1234", option.clone()).unwrap(), "1234");
+
+        let option = option.check_digit(true);
+        assert_eq!(
+            barcode(BarcodeSystem::CODE39, "123ABC", option.clone()).unwrap(),
+            "123ABC$"
+        );
+        assert_eq!(
+            barcode(BarcodeSystem::CODE39, "*123ABC*", option.clone()).unwrap(),
+            "*123ABC$*"
+        );
+        assert_eq!(
+            barcode(BarcodeSystem::ITF, "1234567", option.clone()).unwrap(),
+            "12345670"
+        );
+        assert!(barcode(BarcodeSystem::ITF, "1234", option).is_err());
+    }
 }
@@ -0,0 +1,68 @@
+//! Data capacity of the 2D codes
+//!
+//! The capacity of a symbol depends on the most compact encodation which can hold the whole data:
+//! digits only, the text characters of the symbology or any byte.
+
+#![cfg(feature = "codes_2d")]
+
+use crate::errors::{PrinterError, Result};
+use alloc::format;
+
+/// Maximum data size of a symbol by encodation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Capacity {
+    /// Number of digits
+    numeric: usize,
+    /// Number of text characters
+    text: usize,
+    /// Number of bytes
+    binary: usize,
+}
+
+impl Capacity {
+    /// Create a new `Capacity`
+    pub(crate) const fn new(numeric: usize, text: usize, binary: usize) -> Self {
+        Self { numeric, text, binary }
+    }
+
+    /// Check that the data fits the symbol (`is_text` returns the text characters of the symbology)
+    pub(crate) fn check(&self, name: &str, data: &[u8], is_text: impl Fn(u8) -> bool) -> Result<()> {
+        let (max, unit) = if data.iter().all(u8::is_ascii_digit) {
+            (self.numeric, "digits")
+        } else if data.iter().all(|&c| is_text(c)) {
+            (self.text, "characters")
+        } else {
+            (self.binary, "bytes")
+        };
+
+        if data.len() > max {
+            return Err(PrinterError::Input(format!(
+                "{name} data is too long ({} {unit}, the maximum is {max})",
+                data.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Upper case letters, digits and space (C40 encodation of DataMatrix, upper mode of Aztec code)
+pub(crate) fn is_upper_case_text(c: u8) -> bool {
+    c.is_ascii_uppercase() || c.is_ascii_digit() || c == b' '
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_check() {
+        let capacity = Capacity::new(10, 6, 4);
+        assert!(capacity.check("Test", b"0123456789", is_upper_case_text).is_ok());
+        assert!(capacity.check("Test", b"01234567890", is_upper_case_text).is_err());
+        assert!(capacity.check("Test", b"ABC 12", is_upper_case_text).is_ok());
+        assert!(capacity.check("Test", b"ABC 123", is_upper_case_text).is_err());
+        assert!(capacity.check("Test", b"abcd", is_upper_case_text).is_ok());
+        assert!(capacity.check("Test", &[0xFF, 0, 1, 2, 3], is_upper_case_text).is_err());
+        assert!(capacity.check("Test", b"", is_upper_case_text).is_ok());
+    }
+}
@@ -0,0 +1,198 @@
+//! Barcode check digits
+//!
+//! EAN / UPC check digits are computed when missing and verified when provided.
+//! CODE39 (modulo 43) and ITF (modulo 10) check digits are optional.
+
+#![cfg(feature = "barcodes")]
+
+use super::gs1::gtin_check_digit;
+use crate::errors::{PrinterError, Result};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// CODE39 characters ordered by modulo 43 value
+const CODE39_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+/// Convert digits to a string
+pub(crate) fn digits_string(digits: &[u8]) -> String {
+    digits.iter().map(|digit| char::from(b'0' + digit)).collect()
+}
+
+/// Parse a string of digits
+pub(crate) fn parse_digits(data: &str, name: &str) -> Result<Vec<u8>> {
+    if data.is_empty() || !data.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(PrinterError::Input(format!("invalid {name} data: {data}")));
+    }
+
+    Ok(data.bytes().map(|c| c - b'0').collect())
+}
+
+/// Get the digits of a GTIN (EAN / UPC) and add or verify the check digit
+pub(crate) fn gtin_digits(data: &str, length: usize, name: &str) -> Result<Vec<u8>> {
+    let mut digits = parse_digits(data, name)?;
+    if digits.len() == length - 1 {
+        digits.push(gtin_check_digit(&digits));
+    } else if digits.len() != length {
+        return Err(PrinterError::Input(format!("invalid {name} data: {data}")));
+    } else if gtin_check_digit(&digits[..length - 1]) != digits[length - 1] {
+        return Err(PrinterError::Input(format!("invalid {name} check digit: {data}")));
+    }
+
+    Ok(digits)
+}
+
+/// Get the UPC-E digits (number system, 6 digits and check digit)
+///
+/// The data can be 6 digits (number system 0), 7 digits (number system + 6 digits),
+/// 8 digits (number system + 6 digits + check digit) or a UPC-A code (11 or 12 digits) which can be compressed.
+pub(crate) fn upce_digits(data: &str) -> Result<Vec<u8>> {
+    let digits = parse_digits(data, "UPC-E")?;
+    let (number_system, upce, check) = match digits.len() {
+        6 => (0, digits.clone(), None),
+        7 | 8 => (digits[0], digits[1..7].to_vec(), digits.get(7).copied()),
+        11 | 12 => (digits[0], upce_compress(&digits[1..11], data)?, digits.get(11).copied()),
+        _ => return Err(PrinterError::Input(format!("invalid UPC-E data: {data}"))),
+    };
+    if number_system > 1 {
+        return Err(PrinterError::Input(format!("invalid UPC-E number system: {data}")));
+    }
+
+    let mut upca = vec![number_system];
+    upca.extend(upce_expand(&upce));
+    let check_digit = gtin_check_digit(&upca);
+    if check.is_some_and(|check| check != check_digit) {
+        return Err(PrinterError::Input(format!("invalid UPC-E check digit: {data}")));
+    }
+
+    let mut result = vec![number_system];
+    result.extend(upce);
+    result.push(check_digit);
+    Ok(result)
+}
+
+/// Compress the manufacturer and product digits of a UPC-A code (10 digits) to UPC-E (6 digits)
+fn upce_compress(digits: &[u8], data: &str) -> Result<Vec<u8>> {
+    let (manufacturer, product) = digits.split_at(5);
+    let compressed = match (manufacturer, product) {
+        ([m1, m2, m3 @ 0..=2, 0, 0], [0, 0, p3, p4, p5]) => vec![*m1, *m2, *p3, *p4, *p5, *m3],
+        ([m1, m2, m3, 0, 0], [0, 0, 0, p4, p5]) => vec![*m1, *m2, *m3, *p4, *p5, 3],
+        ([m1, m2, m3, m4, 0], [0, 0, 0, 0, p5]) => vec![*m1, *m2, *m3, *m4, *p5, 4],
+        ([m1, m2, m3, m4, m5], [0, 0, 0, 0, p5 @ 5..=9]) if *m5 != 0 => vec![*m1, *m2, *m3, *m4, *m5, *p5],
+        _ => {
+            return Err(PrinterError::Input(format!(
+                "UPC-A data can not be compressed to UPC-E: {data}"
+            )));
+        }
+    };
+
+    Ok(compressed)
+}
+
+/// Expand UPC-E digits (6 digits) to the UPC-A manufacturer and product digits (10 digits)
+fn upce_expand(digits: &[u8]) -> Vec<u8> {
+    let [d1, d2, d3, d4, d5, d6] = [digits[0], digits[1], digits[2], digits[3], digits[4], digits[5]];
+    match d6 {
+        0..=2 => vec![d1, d2, d6, 0, 0, 0, 0, d3, d4, d5],
+        3 => vec![d1, d2, d3, 0, 0, 0, 0, 0, d4, d5],
+        4 => vec![d1, d2, d3, d4, 0, 0, 0, 0, 0, d5],
+        _ => vec![d1, d2, d3, d4, d5, 0, 0, 0, 0, d6],
+    }
+}
+
+/// Expand a UPC-E code (6, 7 or 8 digits) to UPC-A (12 digits with the check digit)
+///
+/// ```
+/// use escpos::utils::upce_to_upca;
+///
+/// assert_eq!(upce_to_upca("01234565").unwrap(), "012345000065");
+/// assert_eq!(upce_to_upca("123456").unwrap(), "012345000065");
+/// ```
+pub fn upce_to_upca(data: &str) -> Result<String> {
+    if !matches!(data.len(), 6..=8) {
+        return Err(PrinterError::Input(format!("invalid UPC-E data: {data}")));
+    }
+    let digits = upce_digits(data)?;
+
+    let mut upca = vec![digits[0]];
+    upca.extend(upce_expand(&digits[1..7]));
+    upca.push(digits[7]);
+    Ok(digits_string(&upca))
+}
+
+/// Compress a UPC-A code (11 or 12 digits) to UPC-E (8 digits with the number system and the check digit)
+///
+/// Only the UPC-A codes with enough zeros in the manufacturer and product codes can be compressed.
+///
+/// ```
+/// use escpos::utils::upca_to_upce;
+///
+/// assert_eq!(upca_to_upce("01234500006").unwrap(), "01234565");
+/// assert!(upca_to_upce("012345678905").is_err());
+/// ```
+pub fn upca_to_upce(data: &str) -> Result<String> {
+    if !matches!(data.len(), 11 | 12) {
+        return Err(PrinterError::Input(format!("invalid UPC-A data: {data}")));
+    }
+
+    Ok(digits_string(&upce_digits(data)?))
+}
+
+/// Compute the CODE39 modulo 43 check character (`None` if the data contains invalid characters)
+pub(crate) fn code39_check_character(data: &str) -> Option<char> {
+    let mut sum = 0;
+    for c in data.chars() {
+        sum += CODE39_CHARS.find(c)?;
+    }
+    CODE39_CHARS.chars().nth(sum % 43)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gtin_digits() {
+        assert_eq!(gtin_digits("400638133393", 13, "EAN13").unwrap().last(), Some(&1));
+        assert!(gtin_digits("4006381333931", 13, "EAN13").is_ok());
+        assert!(gtin_digits("4006381333932", 13, "EAN13").is_err());
+        assert!(gtin_digits("40063813339", 13, "EAN13").is_err());
+        assert!(gtin_digits("40063813339a", 13, "EAN13").is_err());
+    }
+
+    #[test]
+    fn test_upce_digits() {
+        assert_eq!(upce_digits("0123456").unwrap(), [0, 1, 2, 3, 4, 5, 6, 5]);
+        assert_eq!(upce_digits("01234500006").unwrap(), [0, 1, 2, 3, 4, 5, 6, 5]);
+        assert!(upce_digits("01234564").is_err());
+        assert!(upce_digits("01234567890").is_err());
+        assert!(upce_digits("2123456").is_err());
+    }
+
+    #[test]
+    fn test_upce_upca() {
+        // All the UPC-E last digits
+        for (upce, upca) in [
+            ("01234505", "012000003455"),
+            ("04252614", "042100005264"),
+            ("06541228", "065200004128"),
+            ("01234133", "012300000413"),
+            ("01234446", "012340000046"),
+            ("01234565", "012345000065"),
+        ] {
+            assert_eq!(upce_to_upca(upce).unwrap(), upca);
+            assert_eq!(upca_to_upce(upca).unwrap(), upce);
+        }
+        assert!(upce_to_upca("01234500006").is_err());
+        assert!(upca_to_upce("0123456").is_err());
+    }
+
+    #[test]
+    fn test_code39_check_character() {
+        // 1 + 2 + 3 + 10 + 11 + 12 = 39
+        assert_eq!(code39_check_character("123ABC"), Some('$'));
+        assert_eq!(code39_check_character("CODE 39"), Some('R'));
+        assert_eq!(code39_check_character("a"), None);
+    }
+}
@@ -0,0 +1,313 @@
+//! CODE128 code sets
+//!
+//! The data is split in code sets A, B and C in order to get the shortest barcode.
+//! The data can contain the [`FNC1`] function character (GS1-128).
+
+#![cfg(feature = "barcodes")]
+
+use crate::errors::{PrinterError, Result};
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Maximum number of bytes of the `GS k` CODE128 data
+pub(crate) const CODE128_MAX_DATA_SIZE: usize = 255;
+
+/// FNC1 function character in the data (not an ASCII character)
+pub(crate) const FNC1: u8 = 0xF1;
+
+#[cfg(feature = "graphics")]
+/// Code set start symbol values (A, B, C)
+const START_VALUES: [u8; 3] = [103, 104, 105];
+#[cfg(feature = "graphics")]
+/// Shift symbol value (next character in the other code set of A and B)
+const SHIFT_VALUE: u8 = 98;
+#[cfg(feature = "graphics")]
+/// FNC1 symbol value (all code sets)
+const FNC1_VALUE: u8 = 102;
+
+/// CODE128 code set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeSet {
+    /// Control characters, digits, upper case letters and punctuation (ASCII 0 - 95)
+    A,
+    /// Digits, upper and lower case letters and punctuation (ASCII 32 - 127)
+    B,
+    /// Pairs of digits
+    C,
+}
+
+impl CodeSet {
+    const ALL: [CodeSet; 3] = [CodeSet::A, CodeSet::B, CodeSet::C];
+
+    /// Code set index
+    fn index(self) -> usize {
+        match self {
+            CodeSet::A => 0,
+            CodeSet::B => 1,
+            CodeSet::C => 2,
+        }
+    }
+
+    /// ESC/POS code set selection character (`{A`, `{B` or `{C`)
+    fn escpos(self) -> u8 {
+        match self {
+            CodeSet::A => b'A',
+            CodeSet::B => b'B',
+            CodeSet::C => b'C',
+        }
+    }
+
+    /// Can the character be encoded in the code set (A or B)?
+    fn contains(self, c: u8) -> bool {
+        match self {
+            CodeSet::A => c < 96,
+            CodeSet::B => (32..128).contains(&c),
+            CodeSet::C => false,
+        }
+    }
+
+    /// Other code set for a shift (A <-> B)
+    fn shifted(self) -> Option<CodeSet> {
+        match self {
+            CodeSet::A => Some(CodeSet::B),
+            CodeSet::B => Some(CodeSet::A),
+            CodeSet::C => None,
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Symbol value of a character in the code set (A or B)
+    fn value(self, c: u8) -> u8 {
+        match self {
+            CodeSet::A if c < 32 => c + 64,
+            _ => c - 32,
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Symbol value of the code set selection
+    fn switch_value(self) -> u8 {
+        match self {
+            CodeSet::A => 101,
+            CodeSet::B => 100,
+            CodeSet::C => 99,
+        }
+    }
+}
+
+/// CODE128 symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Symbol {
+    /// Start in a code set
+    Start(CodeSet),
+    /// Code set selection
+    Switch(CodeSet),
+    /// Next character in the other code set (A <-> B)
+    Shift,
+    /// FNC1 function character
+    Fnc1,
+    /// Character in a code set (A or B)
+    Char(CodeSet, u8),
+    /// Pair of digits (code set C)
+    Digits(u8),
+}
+
+/// Encoding step found by the optimization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// Character or pair of digits in the current code set
+    Current,
+    /// Shifted character
+    Shift,
+}
+
+/// Validate CODE128 data (ASCII characters only)
+pub(crate) fn validate(data: &str) -> Result<()> {
+    if data.is_empty() || !data.is_ascii() {
+        return Err(PrinterError::Input(format!("invalid CODE128 data: {data}")));
+    }
+
+    let size = escpos_data(data.as_bytes()).len();
+    if size > CODE128_MAX_DATA_SIZE {
+        return Err(PrinterError::Input(format!(
+            "CODE128 data is too long ({size} bytes with the code sets, the maximum is {CODE128_MAX_DATA_SIZE})"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Split ASCII data (and [`FNC1`]) in the code sets which give the fewest symbols
+pub(crate) fn encode(data: &[u8]) -> Vec<Symbol> {
+    let length = data.len();
+    let is_digits = |i: usize| i + 1 < length && data[i].is_ascii_digit() && data[i + 1].is_ascii_digit();
+
+    // costs[i][set]: fewest symbols to encode data[i..] when the current code set is `set`
+    // (without code set selection first, with an optional code set selection first)
+    let mut costs = vec![[(usize::MAX, usize::MAX); 3]; length + 1];
+    // steps[i][set]: best step in the code set and best code set selection (if any)
+    let mut steps = vec![[(Step::Current, None); 3]; length + 1];
+    costs[length] = [(0, 0); 3];
+
+    for i in (0..length).rev() {
+        for set in CodeSet::ALL {
+            let mut best = (usize::MAX, Step::Current);
+            if data[i] == FNC1 {
+                best = (costs[i + 1][set.index()].1.saturating_add(1), Step::Current);
+            } else if set == CodeSet::C {
+                if is_digits(i) {
+                    best = (costs[i + 2][set.index()].1.saturating_add(1), Step::Current);
+                }
+            } else if set.contains(data[i]) {
+                best = (costs[i + 1][set.index()].1.saturating_add(1), Step::Current);
+            } else if set.shifted().is_some_and(|other| other.contains(data[i])) {
+                best = (costs[i + 1][set.index()].1.saturating_add(2), Step::Shift);
+            }
+            costs[i][set.index()].0 = best.0;
+            steps[i][set.index()].0 = best.1;
+        }
+
+        for set in CodeSet::ALL {
+            let mut best = (costs[i][set.index()].0, None);
+            for other in CodeSet::ALL.into_iter().filter(|other| *other != set) {
+                let cost = costs[i][other.index()].0.saturating_add(1);
+                if cost < best.0 {
+                    best = (cost, Some(other));
+                }
+            }
+            costs[i][set.index()].1 = best.0;
+            steps[i][set.index()].1 = best.1;
+        }
+    }
+
+    // Start code set: the fewest symbols, code set B first on equality
+    let mut set = [CodeSet::B, CodeSet::C, CodeSet::A]
+        .into_iter()
+        .min_by_key(|set| costs[0][set.index()].0)
+        .unwrap_or(CodeSet::B);
+    let mut symbols = vec![Symbol::Start(set)];
+    let mut i = 0;
+    let mut can_switch = false;
+
+    while i < length {
+        if can_switch && let Some(other) = steps[i][set.index()].1 {
+            symbols.push(Symbol::Switch(other));
+            set = other;
+        }
+
+        match steps[i][set.index()].0 {
+            Step::Current if data[i] == FNC1 => {
+                symbols.push(Symbol::Fnc1);
+                i += 1;
+            }
+            Step::Current if set == CodeSet::C => {
+                symbols.push(Symbol::Digits((data[i] - b'0') * 10 + data[i + 1] - b'0'));
+                i += 2;
+            }
+            Step::Current => {
+                symbols.push(Symbol::Char(set, data[i]));
+                i += 1;
+            }
+            Step::Shift => {
+                let other = set.shifted().unwrap_or(set);
+                symbols.extend([Symbol::Shift, Symbol::Char(other, data[i])]);
+                i += 1;
+            }
+        }
+        can_switch = true;
+    }
+
+    symbols
+}
+
+/// Get the `GS k` CODE128 data (`{A`, `{B`, `{C` code set selections, `{S` shift, `{1` FNC1 and `{{` for `{`)
+pub(crate) fn escpos_data(data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.len() + 2);
+    for symbol in encode(data) {
+        match symbol {
+            Symbol::Start(set) | Symbol::Switch(set) => bytes.extend([b'{', set.escpos()]),
+            Symbol::Shift => bytes.extend([b'{', b'S']),
+            Symbol::Fnc1 => bytes.extend([b'{', b'1']),
+            Symbol::Char(_, b'{') => bytes.extend([b'{', b'{']),
+            Symbol::Char(_, c) | Symbol::Digits(c) => bytes.push(c),
+        }
+    }
+    bytes
+}
+
+#[cfg(feature = "graphics")]
+/// Get the symbol values of the symbols, with the check symbol (the stop symbol is not included)
+pub(crate) fn values(symbols: &[Symbol]) -> Vec<u8> {
+    let mut values = Vec::with_capacity(symbols.len() + 1);
+    for symbol in symbols {
+        values.push(match *symbol {
+            Symbol::Start(set) => START_VALUES[set.index()],
+            Symbol::Switch(set) => set.switch_value(),
+            Symbol::Shift => SHIFT_VALUE,
+            Symbol::Fnc1 => FNC1_VALUE,
+            Symbol::Char(set, c) => set.value(c),
+            Symbol::Digits(value) => value,
+        });
+    }
+
+    let check = values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| i.max(1) * usize::from(value))
+        .sum::<usize>()
+        % 103;
+    values.push(check as u8);
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(escpos_data(b"ABC-abc"), b"{BABC-abc");
+        assert_eq!(escpos_data(b"123456"), [b'{', b'C', 12, 34, 56]);
+        // Odd number of digits
+        assert_eq!(escpos_data(b"12345"), [b'{', b'B', b'1', b'{', b'C', 23, 45]);
+        // Digits in the middle
+        assert_eq!(
+            escpos_data(b"AB123456cd"),
+            [b'{', b'B', b'A', b'B', b'{', b'C', 12, 34, 56, b'{', b'B', b'c', b'd']
+        );
+        // Not enough digits to switch
+        assert_eq!(escpos_data(b"A1234b"), b"{BA1234b");
+        // Control characters
+        assert_eq!(escpos_data(b"\tAB"), b"{A\tAB");
+        assert_eq!(escpos_data(b"a\tb"), b"{Ba{S\tb");
+        assert_eq!(escpos_data(b"{x"), b"{B{{x");
+        // FNC1 does not break the pairs of digits
+        assert_eq!(
+            escpos_data(&[FNC1, b'0', b'1', FNC1, b'2', b'3']),
+            [b'{', b'C', b'{', b'1', 1, b'{', b'1', 23]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_values() {
+        // Start B, "A", "B", check digit: (104 + 1 * 33 + 2 * 34) % 103 = 102
+        assert_eq!(values(&encode(b"AB")), vec![104, 33, 34, 102]);
+        assert_eq!(values(&encode(b"1234")), vec![105, 12, 34, 82]);
+        // Start B, shift, "\t" (code set A), "b"
+        assert_eq!(values(&encode(b"\tb")), vec![104, 98, 73, 66, 31]);
+        // Start C, FNC1, "01", check digit: (105 + 1 * 102 + 2 * 1) % 103 = 3
+        assert_eq!(values(&encode(&[FNC1, b'0', b'1'])), vec![105, 102, 1, 3]);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("Hello 128").is_ok());
+        assert!(validate("").is_err());
+        assert!(validate("é").is_err());
+        assert!(validate(&"a".repeat(253)).is_ok());
+        assert!(validate(&"a".repeat(254)).is_err());
+        assert!(validate(&"1".repeat(500)).is_ok());
+    }
+}
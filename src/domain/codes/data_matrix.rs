@@ -1,10 +1,20 @@
 //! DataMatrix
 
+use super::capacity::{self, Capacity};
+#[cfg(feature = "graphics")]
+use super::render::{self, CodeRendering};
+#[cfg(feature = "graphics")]
+use crate::domain::RasterBitImage;
 use crate::errors::{PrinterError, Result};
+use crate::io::encoder::Encoder;
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt;
 
+/// Capacity of the largest DataMatrix symbol (144 x 144)
+const DATA_MATRIX_CAPACITY: Capacity = Capacity::new(3116, 2335, 1556);
+
 /// DataMatrix type
 #[derive(Debug, Clone, Copy)]
 pub enum DataMatrixType {
@@ -72,6 +82,10 @@ impl TryFrom<DataMatrixType> for (u8, u8, u8) {
 pub struct DataMatrixOption {
     code_type: DataMatrixType,
     size: u8,
+    gs1: bool,
+    encoder: Encoder,
+    #[cfg(feature = "graphics")]
+    rendering: Option<CodeRendering>,
 }
 
 impl Default for DataMatrixOption {
@@ -79,6 +93,10 @@ impl Default for DataMatrixOption {
         Self {
             code_type: DataMatrixType::default(),
             size: 3,
+            gs1: false,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
         }
     }
 }
@@ -90,7 +108,39 @@ impl DataMatrixOption {
             return Err(PrinterError::Input(format!("DataMatrix size must in 2 - 16: {size}")));
         }
 
-        Ok(Self { code_type, size })
+        Ok(Self {
+            code_type,
+            size,
+            gs1: false,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
+        })
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the [rendering](CodeRendering) of the DataMatrix (default: printer options)
+    pub fn rendering(mut self, rendering: CodeRendering) -> Self {
+        self.rendering = Some(rendering);
+        self
+    }
+
+    /// Set the GS1 DataMatrix mode (default: `false`)
+    ///
+    /// The data is a GS1 element string with GS separators (see [`GS1ElementString::to_gs_separated`]).
+    /// The FNC1 character is encoded in first position. The `GS ( k` commands can not set this mode:
+    /// GS1 DataMatrix must be rendered as images.
+    ///
+    /// [`GS1ElementString::to_gs_separated`]: crate::utils::GS1ElementString::to_gs_separated
+    pub fn gs1(mut self, gs1: bool) -> Self {
+        self.gs1 = gs1;
+        self
+    }
+
+    /// Set the [encoder](Encoder) of the text data (default: UTF-8)
+    pub fn encoding(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
     }
 
     /// Get type, number of rows and columns
@@ -102,6 +152,33 @@ impl DataMatrixOption {
     pub fn size(&self) -> u8 {
         self.size
     }
+
+    /// Is the GS1 DataMatrix mode enabled?
+    pub fn is_gs1(&self) -> bool {
+        self.gs1
+    }
+
+    /// Get the encoder of the text data
+    pub(crate) fn encoder(&self) -> &Encoder {
+        &self.encoder
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the rendering if it is set
+    pub(crate) fn code_rendering(&self) -> Option<CodeRendering> {
+        self.rendering
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the DataMatrix of the data as a raster bit image
+    pub(crate) fn raster(&self, data: &[u8]) -> Result<RasterBitImage> {
+        render::data_matrix::render(data, self)
+    }
+
+    /// Check that the data fits the largest DataMatrix symbol
+    pub(crate) fn check_data(&self, data: &[u8]) -> Result<()> {
+        DATA_MATRIX_CAPACITY.check("DataMatrix", data, capacity::is_upper_case_text)
+    }
 }
 
 /// DataMatrix
@@ -119,6 +196,32 @@ impl DataMatrix {
             option,
         }
     }
+
+    /// Get the data encoded by the option [encoder](DataMatrixOption::encoding) and check its length
+    pub(crate) fn encoded_data(&self) -> Result<Vec<u8>> {
+        let data = self.option.encoder().encode(&self.data)?;
+        self.option.check_data(&data)?;
+        Ok(data)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the DataMatrix (ECC 200) as a raster bit image
+    ///
+    /// The option size is the size of a module in dots.
+    /// The data is encoded with the ASCII encodation in the smallest symbol of the option type.
+    ///
+    /// ```
+    /// use escpos::utils::{DataMatrix, DataMatrixOption, DataMatrixType};
+    ///
+    /// let option = DataMatrixOption::new(DataMatrixType::Square(0), 4).unwrap();
+    /// let raster = DataMatrix::new("123456", option).raster().unwrap();
+    ///
+    /// // 10 modules and 2 quiet zones of 1 module, 4 dots per module
+    /// assert_eq!((raster.width(), raster.height()), (48, 48));
+    /// ```
+    pub fn raster(&self) -> Result<RasterBitImage> {
+        self.option.raster(&self.encoded_data()?)
+    }
 }
 
 #[cfg(test)]
@@ -153,4 +256,16 @@ mod tests {
         assert!(DataMatrixOption::new(DataMatrixType::default(), 1).is_err());
         assert!(DataMatrixOption::new(DataMatrixType::default(), 17).is_err());
     }
+
+    #[test]
+    fn test_data_matrix_check_data() {
+        let option = DataMatrixOption::default();
+        assert!(option.check_data("1".repeat(3116).as_bytes()).is_ok());
+        assert!(option.check_data("1".repeat(3117).as_bytes()).is_err());
+        assert!(option.check_data("AB".repeat(1167).as_bytes()).is_ok());
+        assert!(option.check_data("AB".repeat(1168).as_bytes()).is_err());
+        assert!(option.check_data(&[0xFF; 1556]).is_ok());
+        assert!(option.check_data(&[0xFF; 1557]).is_err());
+        assert!(DataMatrix::new(&"1".repeat(3117), option).encoded_data().is_err());
+    }
 }
@@ -0,0 +1,475 @@
+//! GS1 application identifiers (AI)
+//!
+//! GS1 element strings are written with the AIs in parentheses: `(01)09501101530003(17)250101(10)AB-123`.
+
+#![cfg(any(feature = "barcodes", feature = "codes_2d"))]
+
+#[cfg(feature = "codes_2d")]
+use super::GS1DataBar2DType;
+#[cfg(all(feature = "barcodes", feature = "graphics"))]
+use super::code128::FNC1;
+use crate::errors::{PrinterError, Result};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Group separator (GS), used as FNC1 separator in the 2D codes data
+const GROUP_SEPARATOR: u8 = 0x1D;
+
+/// First two digits of the AIs with a predefined length (no FNC1 separator is needed after them)
+const PREDEFINED_LENGTH_AIS: [&str; 22] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "31", "32", "33", "34",
+    "35", "36", "41",
+];
+
+/// GS1 value format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// Digits
+    Numeric,
+    /// Digits, the last one is a GS1 check digit
+    NumericCheckDigit,
+    /// Date (`YYMMDD`, the day can be `00`)
+    Date,
+    /// GS1 character set 82, starting with a number of digits
+    Alphanumeric(usize),
+}
+
+/// Application identifier definition
+#[derive(Debug)]
+struct Definition {
+    /// AI or first digits of the AI
+    prefix: &'static str,
+    /// Number of digits of the AI
+    ai_length: usize,
+    /// Minimum length of the value
+    min: usize,
+    /// Maximum length of the value
+    max: usize,
+    /// Value format
+    format: Format,
+}
+
+impl Definition {
+    const fn new(prefix: &'static str, ai_length: usize, min: usize, max: usize, format: Format) -> Self {
+        Self {
+            prefix,
+            ai_length,
+            min,
+            max,
+            format,
+        }
+    }
+}
+
+/// Supported application identifiers
+const DEFINITIONS: [Definition; 56] = [
+    Definition::new("00", 2, 18, 18, Format::NumericCheckDigit),
+    Definition::new("01", 2, 14, 14, Format::NumericCheckDigit),
+    Definition::new("02", 2, 14, 14, Format::NumericCheckDigit),
+    Definition::new("10", 2, 1, 20, Format::Alphanumeric(0)),
+    Definition::new("11", 2, 6, 6, Format::Date),
+    Definition::new("12", 2, 6, 6, Format::Date),
+    Definition::new("13", 2, 6, 6, Format::Date),
+    Definition::new("15", 2, 6, 6, Format::Date),
+    Definition::new("16", 2, 6, 6, Format::Date),
+    Definition::new("17", 2, 6, 6, Format::Date),
+    Definition::new("20", 2, 2, 2, Format::Numeric),
+    Definition::new("21", 2, 1, 20, Format::Alphanumeric(0)),
+    Definition::new("22", 2, 1, 20, Format::Alphanumeric(0)),
+    Definition::new("235", 3, 1, 28, Format::Alphanumeric(0)),
+    Definition::new("240", 3, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("241", 3, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("242", 3, 1, 6, Format::Numeric),
+    Definition::new("243", 3, 1, 20, Format::Alphanumeric(0)),
+    Definition::new("250", 3, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("251", 3, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("253", 3, 14, 30, Format::Alphanumeric(13)),
+    Definition::new("254", 3, 1, 20, Format::Alphanumeric(0)),
+    Definition::new("255", 3, 13, 25, Format::Numeric),
+    Definition::new("30", 2, 1, 8, Format::Numeric),
+    // Trade measures (the last digit of the AI is the position of the decimal point)
+    Definition::new("31", 4, 6, 6, Format::Numeric),
+    Definition::new("32", 4, 6, 6, Format::Numeric),
+    Definition::new("33", 4, 6, 6, Format::Numeric),
+    Definition::new("34", 4, 6, 6, Format::Numeric),
+    Definition::new("35", 4, 6, 6, Format::Numeric),
+    Definition::new("36", 4, 6, 6, Format::Numeric),
+    Definition::new("37", 2, 1, 8, Format::Numeric),
+    // Amounts (the last digit of the AI is the position of the decimal point)
+    Definition::new("390", 4, 1, 15, Format::Numeric),
+    Definition::new("391", 4, 4, 18, Format::Numeric),
+    Definition::new("392", 4, 1, 15, Format::Numeric),
+    Definition::new("393", 4, 4, 18, Format::Numeric),
+    Definition::new("400", 3, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("401", 3, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("402", 3, 17, 17, Format::NumericCheckDigit),
+    Definition::new("403", 3, 1, 30, Format::Alphanumeric(0)),
+    // Global Location Numbers (410 - 417)
+    Definition::new("41", 3, 13, 13, Format::NumericCheckDigit),
+    Definition::new("420", 3, 1, 20, Format::Alphanumeric(0)),
+    Definition::new("421", 3, 4, 12, Format::Alphanumeric(3)),
+    Definition::new("422", 3, 3, 3, Format::Numeric),
+    Definition::new("7003", 4, 10, 10, Format::Numeric),
+    Definition::new("8003", 4, 15, 30, Format::Alphanumeric(14)),
+    Definition::new("8004", 4, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("8005", 4, 6, 6, Format::Numeric),
+    Definition::new("8006", 4, 18, 18, Format::Numeric),
+    Definition::new("8007", 4, 1, 34, Format::Alphanumeric(0)),
+    Definition::new("8008", 4, 8, 12, Format::Numeric),
+    Definition::new("8017", 4, 18, 18, Format::NumericCheckDigit),
+    Definition::new("8018", 4, 18, 18, Format::NumericCheckDigit),
+    Definition::new("8020", 4, 1, 25, Format::Alphanumeric(0)),
+    Definition::new("8200", 4, 1, 70, Format::Alphanumeric(0)),
+    // Internal use (90 - 99)
+    Definition::new("90", 2, 1, 30, Format::Alphanumeric(0)),
+    Definition::new("9", 2, 1, 90, Format::Alphanumeric(0)),
+];
+
+/// Is the character in the GS1 character set 82 (parentheses excluded)?
+fn is_cset82(c: u8) -> bool {
+    matches!(c, b'!' | b'"' | b'%'..=b'\'' | b'*'..=b'?' | b'A'..=b'Z' | b'_' | b'a'..=b'z')
+}
+
+/// Compute the GS1 check digit (GTIN, SSCC, GLN) of digits without check digit
+pub(crate) fn gtin_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| u32::from(digit) * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// GS1 element (application identifier and value)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Element {
+    ai: String,
+    value: String,
+}
+
+impl Element {
+    /// Create a new element and validate the value
+    pub(crate) fn new(ai: &str, value: &str) -> Result<Self> {
+        let definition = DEFINITIONS
+            .iter()
+            .find(|definition| ai.len() == definition.ai_length && ai.starts_with(definition.prefix))
+            .filter(|_| ai.bytes().all(|c| c.is_ascii_digit()))
+            .ok_or_else(|| PrinterError::Input(format!("unsupported GS1 application identifier: ({ai})")))?;
+
+        let invalid = || PrinterError::Input(format!("invalid GS1 value: ({ai}){value}"));
+        let bytes = value.as_bytes();
+        if bytes.len() < definition.min || bytes.len() > definition.max {
+            return Err(invalid());
+        }
+
+        let is_numeric = bytes.iter().all(u8::is_ascii_digit);
+        match definition.format {
+            Format::Numeric if !is_numeric => return Err(invalid()),
+            Format::NumericCheckDigit => {
+                let digits: Vec<u8> = bytes.iter().map(|c| c.wrapping_sub(b'0')).collect();
+                let (check, digits) = digits.split_last().ok_or_else(invalid)?;
+                if !is_numeric || gtin_check_digit(digits) != *check {
+                    return Err(PrinterError::Input(format!("invalid GS1 check digit: ({ai}){value}")));
+                }
+            }
+            Format::Date => {
+                let month = value.get(2..4).and_then(|month| month.parse::<u8>().ok());
+                let day = value.get(4..6).and_then(|day| day.parse::<u8>().ok());
+                if !is_numeric || !matches!((month, day), (Some(1..=12), Some(0..=31))) {
+                    return Err(PrinterError::Input(format!("invalid GS1 date (YYMMDD): ({ai}){value}")));
+                }
+            }
+            Format::Alphanumeric(digits) => {
+                let (head, tail) = bytes.split_at(digits);
+                if !head.iter().all(u8::is_ascii_digit) || !tail.iter().all(|&c| is_cset82(c)) {
+                    return Err(invalid());
+                }
+            }
+            Format::Numeric => {}
+        }
+
+        Ok(Self {
+            ai: ai.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Does the element have a predefined length (no FNC1 separator needed after it)?
+    fn is_predefined_length(&self) -> bool {
+        PREDEFINED_LENGTH_AIS.contains(&&self.ai[..2])
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}){}", self.ai, self.value)
+    }
+}
+
+/// Parse a GS1 element string written with the AIs in parentheses (`(01)09501101530003(10)AB-123`)
+pub(crate) fn parse(data: &str) -> Result<Vec<Element>> {
+    let invalid = || PrinterError::Input(format!("invalid GS1 element string: {data}"));
+
+    let mut elements = Vec::new();
+    let mut rest = data.strip_prefix('(').ok_or_else(invalid)?;
+    while !rest.is_empty() {
+        let (ai, tail) = rest.split_once(')').ok_or_else(invalid)?;
+        let (value, tail) = match tail.split_once('(') {
+            Some((_, "")) => return Err(invalid()),
+            Some((value, tail)) => (value, tail),
+            None => (tail, ""),
+        };
+        elements.push(Element::new(ai, value)?);
+        rest = tail;
+    }
+
+    Ok(elements)
+}
+
+/// Join the elements, with FNC1 (`separator`) after the elements without a predefined length but the last one
+fn join(elements: &[Element], separator: &[u8], parentheses: bool) -> Vec<u8> {
+    let mut data = Vec::new();
+    for (i, element) in elements.iter().enumerate() {
+        if parentheses {
+            data.extend(element.to_string().bytes());
+        } else {
+            data.extend(element.ai.bytes().chain(element.value.bytes()));
+        }
+        if i + 1 < elements.len() && !element.is_predefined_length() {
+            data.extend_from_slice(separator);
+        }
+    }
+    data
+}
+
+/// Get the `GS k` data of the GS1-128 and GS1 DataBar Expanded barcodes
+///
+/// The parentheses are only printed in the HRI characters and `{1` is the FNC1 separator.
+pub(crate) fn escpos_data(elements: &[Element]) -> Vec<u8> {
+    join(elements, b"{1", true)
+}
+
+#[cfg(all(feature = "barcodes", feature = "graphics"))]
+/// Get the CODE128 data of a GS1-128 barcode (starting with FNC1, FNC1 as separator)
+pub(crate) fn code128_data(elements: &[Element]) -> Vec<u8> {
+    let mut data = Vec::from([FNC1]);
+    data.extend(join(elements, &[FNC1], false));
+    data
+}
+
+/// GS1 element string builder
+///
+/// The application identifiers (AI) are validated when they are added: fixed and variable lengths,
+/// character set, dates (`YYMMDD`) and check digits (GTIN, SSCC, GLN).
+/// The element string is written with the AIs in parentheses by [`Display`](fmt::Display),
+/// which is the format expected by the GS1-128 and GS1 DataBar Expanded barcodes.
+///
+/// ```
+/// use escpos::utils::GS1ElementString;
+///
+/// let elements = GS1ElementString::new()
+///     .gtin("09501101530003")?
+///     .expiry_date("270131")?
+///     .batch("AB-123")?
+///     .serial("1234")?;
+///
+/// assert_eq!(elements.to_string(), "(01)09501101530003(17)270131(10)AB-123(21)1234");
+/// assert_eq!(elements.to_gs_separated(), "01095011015300031727013110AB-123\u{1d}211234");
+///
+/// assert!(GS1ElementString::new().gtin("09501101530004").is_err());
+/// # Ok::<(), escpos::errors::PrinterError>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GS1ElementString {
+    elements: Vec<Element>,
+}
+
+impl GS1ElementString {
+    /// Create an empty `GS1ElementString`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse an element string written with the AIs in parentheses (`(01)09501101530003(10)AB-123`)
+    pub fn parse(data: &str) -> Result<Self> {
+        Ok(Self { elements: parse(data)? })
+    }
+
+    /// Add an element
+    pub fn add(mut self, ai: &str, value: &str) -> Result<Self> {
+        self.elements.push(Element::new(ai, value)?);
+        Ok(self)
+    }
+
+    /// Add the GTIN (AI 01)
+    ///
+    /// GTIN-8, GTIN-12 and GTIN-13 (with their check digit) are padded with zeros to 14 digits.
+    pub fn gtin(self, gtin: &str) -> Result<Self> {
+        if ![8, 12, 13, 14].contains(&gtin.len()) {
+            return Err(PrinterError::Input(format!("invalid GTIN: {gtin}")));
+        }
+        self.add("01", &format!("{gtin:0>14}"))
+    }
+
+    /// Add the batch or lot number (AI 10)
+    pub fn batch(self, batch: &str) -> Result<Self> {
+        self.add("10", batch)
+    }
+
+    /// Add the expiration date (AI 17, `YYMMDD`, the day can be `00`)
+    pub fn expiry_date(self, date: &str) -> Result<Self> {
+        self.add("17", date)
+    }
+
+    /// Add the serial number (AI 21)
+    pub fn serial(self, serial: &str) -> Result<Self> {
+        self.add("21", serial)
+    }
+
+    /// Is the element string empty?
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Get the element string without parentheses, with a GS character (`0x1D`) after the variable length elements
+    ///
+    /// This is the data of the GS1 DataMatrix (with `DataMatrixOption::gs1`) and of the GS1 QR codes
+    /// (with `QRCodeOption::gs1`). The `GS ( k` commands can not set their FNC1 mode: they are rendered as images.
+    pub fn to_gs_separated(&self) -> String {
+        join(&self.elements, &[GROUP_SEPARATOR], false)
+            .into_iter()
+            .map(char::from)
+            .collect()
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the data of a 2D GS1 DataBar
+    ///
+    /// GS1 DataBar Stacked and Stacked Omnidirectional only contain a GTIN (13 digits without the check digit),
+    /// GS1 DataBar Expanded Stacked contains the element string with the AIs in parentheses and `{1` FNC1 separators.
+    pub fn gs1_databar_2d_data(&self, code_type: GS1DataBar2DType) -> Result<String> {
+        match code_type {
+            GS1DataBar2DType::Stacked | GS1DataBar2DType::StackedOmnidirectional => match self.elements.as_slice() {
+                [element] if element.ai == "01" => Ok(element.value[..13].to_string()),
+                _ => Err(PrinterError::Input(format!("{code_type} only contains a GTIN: {self}"))),
+            },
+            GS1DataBar2DType::ExpandedStacked => Ok(escpos_data(&self.elements).into_iter().map(char::from).collect()),
+        }
+    }
+}
+
+impl fmt::Display for GS1ElementString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for element in &self.elements {
+            write!(f, "{element}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gtin_check_digit() {
+        assert_eq!(gtin_check_digit(&[4, 0, 0, 6, 3, 8, 1, 3, 3, 3, 9, 3]), 1);
+        assert_eq!(gtin_check_digit(&[0, 3, 6, 0, 0, 0, 2, 9, 1, 4, 5]), 2);
+        assert_eq!(gtin_check_digit(&[9, 6, 3, 8, 5, 0, 7]), 4);
+    }
+
+    #[test]
+    fn test_element_new() {
+        assert!(Element::new("01", "09501101530003").is_ok());
+        assert!(Element::new("01", "09501101530004").is_err());
+        assert!(Element::new("01", "0950110153000").is_err());
+        assert!(Element::new("10", "AB-123/x").is_ok());
+        assert!(Element::new("10", "AB 123").is_err());
+        assert!(Element::new("10", &"A".repeat(21)).is_err());
+        assert!(Element::new("17", "250100").is_ok());
+        assert!(Element::new("17", "251301").is_err());
+        assert!(Element::new("3103", "001250").is_ok());
+        assert!(Element::new("310", "001250").is_err());
+        assert!(Element::new("421", "250ABC").is_ok());
+        assert!(Element::new("421", "25ABC").is_err());
+        assert!(Element::new("97", "Internal").is_ok());
+        assert!(Element::new("05", "1").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let elements = parse("(01)09501101530003(17)250101(10)AB-123(21)42").unwrap();
+        assert_eq!(elements.len(), 4);
+        assert_eq!(elements[2], Element::new("10", "AB-123").unwrap());
+
+        assert!(parse("").is_err());
+        assert!(parse("0109501101530003").is_err());
+        assert!(parse("(01)09501101530003(").is_err());
+        assert!(parse("(01").is_err());
+    }
+
+    #[test]
+    fn test_escpos_data() {
+        let elements = parse("(01)09501101530003(10)AB-123(21)42").unwrap();
+        assert_eq!(escpos_data(&elements), b"(01)09501101530003(10)AB-123{1(21)42");
+    }
+
+    #[cfg(all(feature = "barcodes", feature = "graphics"))]
+    #[test]
+    fn test_code128_data() {
+        let elements = parse("(10)AB(01)09501101530003").unwrap();
+        let mut expected = Vec::from([FNC1]);
+        expected.extend(b"10AB");
+        expected.push(FNC1);
+        expected.extend(b"0109501101530003");
+        assert_eq!(code128_data(&elements), expected);
+    }
+
+    #[test]
+    fn test_element_string() {
+        let elements = GS1ElementString::new()
+            .gtin("9501101530003")
+            .unwrap()
+            .add("3103", "000125")
+            .unwrap()
+            .batch("ABC")
+            .unwrap();
+        assert_eq!(elements.to_string(), "(01)09501101530003(3103)000125(10)ABC");
+        assert_eq!(elements.to_gs_separated(), "0109501101530003310300012510ABC");
+        assert_eq!(GS1ElementString::parse(&elements.to_string()).unwrap(), elements);
+
+        let elements = GS1ElementString::new()
+            .serial("42")
+            .unwrap()
+            .expiry_date("270100")
+            .unwrap();
+        assert_eq!(elements.to_gs_separated(), "2142\u{1d}17270100");
+
+        assert!(GS1ElementString::new().gtin("950110153000").is_err());
+        assert!(GS1ElementString::new().expiry_date("271301").is_err());
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_gs1_databar_2d_data() {
+        let elements = GS1ElementString::new().gtin("09501101530003").unwrap();
+        assert_eq!(
+            elements.gs1_databar_2d_data(GS1DataBar2DType::Stacked).unwrap(),
+            "0950110153000"
+        );
+
+        let elements = elements.batch("AB").unwrap().serial("1").unwrap();
+        assert!(
+            elements
+                .gs1_databar_2d_data(GS1DataBar2DType::StackedOmnidirectional)
+                .is_err()
+        );
+        assert_eq!(
+            elements.gs1_databar_2d_data(GS1DataBar2DType::ExpandedStacked).unwrap(),
+            "(01)09501101530003(10)AB{1(21)1"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! MaxiCode
 
+use super::capacity::Capacity;
+use crate::errors::Result;
 use alloc::string::{String, ToString};
 use core::fmt;
 
@@ -38,6 +40,21 @@ impl From<MaxiCodeMode> for u8 {
     }
 }
 
+impl MaxiCodeMode {
+    /// Capacity of the mode (the bytes out of the ASCII characters are shifted)
+    fn capacity(self) -> Capacity {
+        match self {
+            MaxiCodeMode::Mode5 => Capacity::new(113, 77, 38),
+            _ => Capacity::new(138, 93, 46),
+        }
+    }
+
+    /// Check that the data fits a MaxiCode symbol of the mode
+    pub(crate) fn check_data(self, data: &[u8]) -> Result<()> {
+        self.capacity().check("MaxiCode", data, |c| c.is_ascii())
+    }
+}
+
 #[derive(Debug)]
 pub struct MaxiCode {
     pub data: String,
@@ -53,3 +70,18 @@ impl MaxiCode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maxi_code_check_data() {
+        assert!(MaxiCodeMode::Mode4.check_data("1".repeat(138).as_bytes()).is_ok());
+        assert!(MaxiCodeMode::Mode4.check_data("1".repeat(139).as_bytes()).is_err());
+        assert!(MaxiCodeMode::Mode2.check_data("a".repeat(93).as_bytes()).is_ok());
+        assert!(MaxiCodeMode::Mode5.check_data("a".repeat(78).as_bytes()).is_err());
+        assert!(MaxiCodeMode::Mode6.check_data(&[0xFF; 46]).is_ok());
+        assert!(MaxiCodeMode::Mode6.check_data(&[0xFF; 47]).is_err());
+    }
+}
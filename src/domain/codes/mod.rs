@@ -5,7 +5,15 @@ mod aztec;
 #[cfg(feature = "barcodes")]
 mod barcodes;
 #[cfg(feature = "codes_2d")]
+mod capacity;
+#[cfg(feature = "barcodes")]
+pub(crate) mod check_digit;
+#[cfg(feature = "barcodes")]
+pub(crate) mod code128;
+#[cfg(feature = "codes_2d")]
 mod data_matrix;
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+pub(crate) mod gs1;
 #[cfg(feature = "codes_2d")]
 mod gs1_databar_2d;
 #[cfg(feature = "codes_2d")]
@@ -14,16 +22,26 @@ mod maxi_code;
 mod pdf417;
 #[cfg(feature = "codes_2d")]
 mod qrcode;
+#[cfg(feature = "graphics")]
+mod render;
+#[cfg(feature = "codes_2d")]
+mod symbol_size;
 
 #[cfg(feature = "barcodes")]
 pub use barcodes::*;
 
+#[cfg(feature = "barcodes")]
+pub use check_digit::{upca_to_upce, upce_to_upca};
+
 #[cfg(feature = "codes_2d")]
 pub use aztec::*;
 
 #[cfg(feature = "codes_2d")]
 pub use data_matrix::*;
 
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+pub use gs1::GS1ElementString;
+
 #[cfg(feature = "codes_2d")]
 pub use gs1_databar_2d::*;
 
@@ -35,3 +53,9 @@ pub use pdf417::*;
 
 #[cfg(feature = "codes_2d")]
 pub use qrcode::*;
+
+#[cfg(feature = "graphics")]
+pub use render::CodeRendering;
+
+#[cfg(feature = "codes_2d")]
+pub use symbol_size::CodeSymbolSize;
@@ -1,10 +1,25 @@
 //! PDF417
 
+use super::capacity::Capacity;
+#[cfg(feature = "graphics")]
+use super::render::{self, CodeRendering};
+#[cfg(feature = "graphics")]
+use crate::domain::RasterBitImage;
 use crate::errors::{PrinterError, Result};
+use crate::io::encoder::Encoder;
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use core::fmt;
 
+/// Capacity of the largest PDF417 symbol (numeric, text and byte compaction)
+const PDF417_CAPACITY: Capacity = Capacity::new(2710, 1850, 1108);
+
+/// Characters of the PDF417 text compaction
+fn is_text(c: u8) -> bool {
+    matches!(c, b' '..=b'~' | b'\t' | b'\n' | b'\r')
+}
+
 /// PDF417 correction level
 #[derive(Debug, Clone, Copy)]
 pub enum Pdf417CorrectionLevel {
@@ -100,6 +115,9 @@ pub struct Pdf417Option {
     row_height: u8, // Default: ?
     code_type: Pdf417Type,
     correction_level: Pdf417CorrectionLevel,
+    encoder: Encoder,
+    #[cfg(feature = "graphics")]
+    rendering: Option<CodeRendering>,
 }
 
 impl Pdf417Option {
@@ -131,9 +149,25 @@ impl Pdf417Option {
             row_height,
             code_type,
             correction_level,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
         })
     }
 
+    #[cfg(feature = "graphics")]
+    /// Set the [rendering](CodeRendering) of the PDF417 (default: printer options)
+    pub fn rendering(mut self, rendering: CodeRendering) -> Self {
+        self.rendering = Some(rendering);
+        self
+    }
+
+    /// Set the [encoder](Encoder) of the text data (default: UTF-8)
+    pub fn encoding(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
     /// Get number of columns
     pub fn columns(&self) -> u8 {
         self.columns
@@ -163,6 +197,28 @@ impl Pdf417Option {
     pub fn correction_level(&self) -> Pdf417CorrectionLevel {
         self.correction_level
     }
+
+    /// Get the encoder of the text data
+    pub(crate) fn encoder(&self) -> &Encoder {
+        &self.encoder
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the rendering if it is set
+    pub(crate) fn code_rendering(&self) -> Option<CodeRendering> {
+        self.rendering
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the PDF417 of the data as a raster bit image
+    pub(crate) fn raster(&self, data: &[u8]) -> Result<RasterBitImage> {
+        render::pdf417::render(data, self)
+    }
+
+    /// Check that the data fits the largest PDF417 symbol
+    pub(crate) fn check_data(&self, data: &[u8]) -> Result<()> {
+        PDF417_CAPACITY.check("PDF417", data, is_text)
+    }
 }
 
 /// PDF417
@@ -180,6 +236,34 @@ impl Pdf417 {
             option,
         }
     }
+
+    /// Get the data encoded by the option [encoder](Pdf417Option::encoding) and check its length
+    pub(crate) fn encoded_data(&self) -> Result<Vec<u8>> {
+        let data = self.option.encoder().encode(&self.data)?;
+        self.option.check_data(&data)?;
+        Ok(data)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the PDF417 as a raster bit image
+    ///
+    /// The option width is the width of a module in dots and the row height is in module widths
+    /// (the printer defaults, 3 and 3, when they are `0`).
+    /// The number of columns and rows are computed when they are `0`.
+    ///
+    /// ```
+    /// use escpos::utils::{Pdf417, Pdf417CorrectionLevel, Pdf417Option, Pdf417Type};
+    ///
+    /// let option = Pdf417Option::new(3, 3, 2, 4, Pdf417Type::Truncated, Pdf417CorrectionLevel::Level0).unwrap();
+    /// let raster = Pdf417::new("PDF417", option).raster().unwrap();
+    ///
+    /// // Start, row indicator and 3 columns of 17 modules, stop bar and 2 quiet zones of 2 modules: 2 dots per module
+    /// // 3 rows and 2 quiet zones of 1 row: 8 dots per row
+    /// assert_eq!((raster.width(), raster.height()), (180, 40));
+    /// ```
+    pub fn raster(&self) -> Result<RasterBitImage> {
+        self.option.raster(&self.encoded_data()?)
+    }
 }
 
 #[cfg(test)]
@@ -193,4 +277,26 @@ mod tests {
         assert!(Pdf417Option::new(0, 100, 8, 8, Pdf417Type::Standard, Pdf417CorrectionLevel::Level0).is_err());
         assert!(Pdf417Option::new(0, 0, 8, 8, Pdf417Type::Standard, Pdf417CorrectionLevel::Level0).is_ok());
     }
+
+    #[test]
+    fn test_pdf417_check_data() {
+        let option = Pdf417Option::default();
+        assert!(option.check_data("1".repeat(2710).as_bytes()).is_ok());
+        assert!(option.check_data("1".repeat(2711).as_bytes()).is_err());
+        assert!(option.check_data("a\n".repeat(925).as_bytes()).is_ok());
+        assert!(option.check_data("a\n".repeat(926).as_bytes()).is_err());
+        assert!(option.check_data(&[0xFF; 1108]).is_ok());
+        assert!(option.check_data(&[0xFF; 1109]).is_err());
+    }
+
+    #[test]
+    fn test_pdf417_encoded_data() {
+        let option = Pdf417Option::default().encoding(Encoder::new(encoding_rs::WINDOWS_1252));
+        assert_eq!(Pdf417::new("café", option).encoded_data().unwrap(), b"caf\xE9");
+        assert!(
+            Pdf417::new(&"1".repeat(2711), Pdf417Option::default())
+                .encoded_data()
+                .is_err()
+        );
+    }
 }
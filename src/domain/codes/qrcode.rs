@@ -2,13 +2,50 @@
 
 #![cfg(feature = "codes_2d")]
 
-use crate::errors::Result;
+use super::capacity::Capacity;
+#[cfg(feature = "graphics")]
+use super::render::{self, CodeRendering};
+#[cfg(feature = "graphics")]
+use crate::domain::RasterBitImage;
+use crate::errors::{PrinterError, Result};
+use crate::io::encoder::Encoder;
+use alloc::borrow::ToOwned;
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use core::fmt;
 
 const QRCODE_MAX_DATA_SIZE: usize = 7089;
 
+/// Capacity of the largest model 1 QR code (version 14) by error correction level (L, M, Q, H)
+const MODEL1_CAPACITIES: [Capacity; 4] = [
+    Capacity::new(1167, 707, 486),
+    Capacity::new(919, 557, 382),
+    Capacity::new(707, 428, 293),
+    Capacity::new(509, 309, 210),
+];
+
+/// Capacity of the largest model 2 QR code (version 40) by error correction level (L, M, Q, H)
+const MODEL2_CAPACITIES: [Capacity; 4] = [
+    Capacity::new(7089, 4296, 2953),
+    Capacity::new(5596, 3391, 2331),
+    Capacity::new(3993, 2420, 1663),
+    Capacity::new(3057, 1852, 1273),
+];
+
+/// Capacity of the largest micro QR code (M4) by error correction level (L, M, Q)
+const MICRO_CAPACITIES: [Capacity; 3] = [
+    Capacity::new(35, 21, 15),
+    Capacity::new(30, 18, 13),
+    Capacity::new(21, 13, 9),
+];
+
+/// Characters of the QR code alphanumeric mode
+fn is_alphanumeric(c: u8) -> bool {
+    c.is_ascii_digit() || c.is_ascii_uppercase() || b" $%*+-./:".contains(&c)
+}
+
 /// QR Code model
 #[derive(Debug, Clone, Copy)]
 pub enum QRCodeModel {
@@ -74,6 +111,10 @@ pub struct QRCodeOption {
     model: QRCodeModel,
     size: u8,
     correction_level: QRCodeCorrectionLevel,
+    gs1: bool,
+    encoder: Encoder,
+    #[cfg(feature = "graphics")]
+    rendering: Option<CodeRendering>,
 }
 
 impl Default for QRCodeOption {
@@ -82,6 +123,10 @@ impl Default for QRCodeOption {
             model: QRCodeModel::Model1,
             size: 4,
             correction_level: QRCodeCorrectionLevel::H,
+            gs1: false,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
         }
     }
 }
@@ -93,9 +138,40 @@ impl QRCodeOption {
             model,
             size,
             correction_level,
+            gs1: false,
+            encoder: Encoder::default(),
+            #[cfg(feature = "graphics")]
+            rendering: None,
         }
     }
 
+    /// Set the [encoder](Encoder) of the text data (default: UTF-8)
+    ///
+    /// For example, Shift_JIS text is printed with the Kanji mode.
+    pub fn encoding(mut self, encoder: Encoder) -> Self {
+        self.encoder = encoder;
+        self
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the [rendering](CodeRendering) of the QR code (default: printer options)
+    pub fn rendering(mut self, rendering: CodeRendering) -> Self {
+        self.rendering = Some(rendering);
+        self
+    }
+
+    /// Set the GS1 QR code mode (default: `false`)
+    ///
+    /// The data is a GS1 element string with GS separators (see [`GS1ElementString::to_gs_separated`]),
+    /// encoded after the FNC1 in first position mode indicator.
+    /// The `GS ( k` commands can not set this mode: GS1 QR codes must be rendered as images.
+    ///
+    /// [`GS1ElementString::to_gs_separated`]: crate::utils::GS1ElementString::to_gs_separated
+    pub fn gs1(mut self, gs1: bool) -> Self {
+        self.gs1 = gs1;
+        self
+    }
+
     /// Get model
     pub fn model(&self) -> QRCodeModel {
         self.model
@@ -110,6 +186,50 @@ impl QRCodeOption {
     pub fn correction_level(&self) -> QRCodeCorrectionLevel {
         self.correction_level
     }
+
+    /// Is the GS1 QR code mode enabled?
+    pub fn is_gs1(&self) -> bool {
+        self.gs1
+    }
+
+    /// Get the encoder of the text data
+    pub(crate) fn encoder(&self) -> &Encoder {
+        &self.encoder
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the rendering if it is set
+    pub(crate) fn code_rendering(&self) -> Option<CodeRendering> {
+        self.rendering
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the QR code of the data as a raster bit image
+    pub(crate) fn raster(&self, data: &[u8]) -> Result<RasterBitImage> {
+        render::qrcode::render(data, self)
+    }
+
+    /// Capacity of the largest QR code of the model and the error correction level
+    fn capacity(&self) -> Result<Capacity> {
+        let level = match self.correction_level {
+            QRCodeCorrectionLevel::L => 0,
+            QRCodeCorrectionLevel::M => 1,
+            QRCodeCorrectionLevel::Q => 2,
+            QRCodeCorrectionLevel::H => 3,
+        };
+        match self.model {
+            QRCodeModel::Model1 => Ok(MODEL1_CAPACITIES[level]),
+            QRCodeModel::Model2 => Ok(MODEL2_CAPACITIES[level]),
+            QRCodeModel::Micro => MICRO_CAPACITIES.get(level).copied().ok_or_else(|| {
+                PrinterError::Input("Micro QR codes do not support the error correction level H".to_owned())
+            }),
+        }
+    }
+
+    /// Check that the data fits the largest QR code of the model and the error correction level
+    pub(crate) fn check_data(&self, data: &[u8]) -> Result<()> {
+        self.capacity()?.check("QR code", data, is_alphanumeric)
+    }
 }
 
 /// QR code
@@ -135,12 +255,579 @@ impl QRCode {
         let bytes = data.as_bytes();
         let data_len = bytes.len();
         if data_len > QRCODE_MAX_DATA_SIZE {
-            return Err(crate::errors::PrinterError::Input(format!(
+            return Err(PrinterError::Input(format!(
                 "QR code data is too long ({data_len}), its length should be smaller than 7090"
             )));
         }
         Ok(())
     }
+
+    /// Get the data encoded by the option [encoder](QRCodeOption::encoding) and check its length
+    pub(crate) fn encoded_data(&self) -> Result<Vec<u8>> {
+        let data = self.option.encoder().encode(&self.data)?;
+        self.option.check_data(&data)?;
+        Ok(data)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Render the QR code as a raster bit image
+    ///
+    /// The option size is the size of a module in dots.
+    /// Only model 2 QR codes are generated: model 1 (the default option model) is rendered as model 2
+    /// and micro QR codes return an error.
+    ///
+    /// ```
+    /// use escpos::utils::{QRCode, QRCodeCorrectionLevel, QRCodeModel, QRCodeOption};
+    ///
+    /// let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M);
+    /// let raster = QRCode::new("Hello", Some(option)).unwrap().raster().unwrap();
+    ///
+    /// // 21 modules and 2 quiet zones of 4 modules, 4 dots per module
+    /// assert_eq!((raster.width(), raster.height()), (116, 116));
+    /// ```
+    pub fn raster(&self) -> Result<RasterBitImage> {
+        self.option.raster(&self.encoded_data()?)
+    }
+}
+
+/// Maximum number of bytes of an EPC QR code
+const EPC_MAX_DATA_SIZE: usize = 331;
+
+impl QRCodeOption {
+    /// Create a `QRCodeOption` which fits the data (model 2)
+    ///
+    /// The highest error correction level which can hold the data is chosen
+    /// and the size of a module is reduced for long data so that the QR code fits an 80 mm paper.
+    ///
+    /// ```
+    /// use escpos::utils::QRCodeOption;
+    ///
+    /// let option = QRCodeOption::for_data("https://example.com").unwrap();
+    /// assert_eq!(option.size(), 4);
+    /// assert!(QRCodeOption::for_data(&"a".repeat(3000)).is_err());
+    /// ```
+    pub fn for_data(data: &str) -> Result<Self> {
+        let size = match data.len() {
+            0..=500 => 4,
+            501..=1273 => 3,
+            _ => 2,
+        };
+
+        for correction_level in [
+            QRCodeCorrectionLevel::H,
+            QRCodeCorrectionLevel::Q,
+            QRCodeCorrectionLevel::M,
+        ] {
+            let option = Self::new(QRCodeModel::Model2, size, correction_level);
+            if option.check_data(data.as_bytes()).is_ok() {
+                return Ok(option);
+            }
+        }
+
+        let option = Self::new(QRCodeModel::Model2, size, QRCodeCorrectionLevel::L);
+        option.check_data(data.as_bytes())?;
+        Ok(option)
+    }
+}
+
+impl QRCode {
+    /// Create a new `QRCode` from a [payload](QRCodePayload) with the option which fits its data
+    ///
+    /// ```
+    /// use escpos::utils::{QRCode, QRCodeWifi, WifiSecurity};
+    ///
+    /// let wifi = QRCodeWifi::new("Home").password(WifiSecurity::WPA, "secret;123");
+    /// let qrcode = QRCode::from_payload(&wifi).unwrap();
+    /// assert_eq!(qrcode.data, r"WIFI:T:WPA;S:Home;P:secret\;123;;");
+    /// ```
+    pub fn from_payload(payload: &impl QRCodePayload) -> Result<Self> {
+        let data = payload.data()?;
+        let option = payload.option(&data)?;
+        Self::new(&data, Some(option))
+    }
+}
+
+/// QR code payload (structured data read by smartphones)
+pub trait QRCodePayload {
+    /// Get the escaped QR code data
+    fn data(&self) -> Result<String>;
+
+    /// Get the QR code option which fits the data
+    fn option(&self, data: &str) -> Result<QRCodeOption> {
+        QRCodeOption::for_data(data)
+    }
+}
+
+/// Escape the special characters of a value with a backslash
+fn escape(value: &str, special_chars: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if special_chars.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Wi-Fi network security
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WifiSecurity {
+    /// No password
+    Open,
+    WEP,
+    /// WPA / WPA2
+    WPA,
+    /// WPA3 (SAE)
+    WPA3,
+}
+
+impl fmt::Display for WifiSecurity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WifiSecurity::Open => write!(f, "nopass"),
+            WifiSecurity::WEP => write!(f, "WEP"),
+            WifiSecurity::WPA => write!(f, "WPA"),
+            WifiSecurity::WPA3 => write!(f, "SAE"),
+        }
+    }
+}
+
+/// Wi-Fi network payload (`WIFI:T:WPA;S:<SSID>;P:<password>;;`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QRCodeWifi {
+    ssid: String,
+    security: WifiSecurity,
+    password: String,
+    hidden: bool,
+}
+
+impl QRCodeWifi {
+    /// Create a new `QRCodeWifi` for an open network
+    pub fn new(ssid: &str) -> Self {
+        Self {
+            ssid: ssid.to_string(),
+            security: WifiSecurity::Open,
+            password: String::new(),
+            hidden: false,
+        }
+    }
+
+    /// Set the security and the password of the network
+    pub fn password(mut self, security: WifiSecurity, password: &str) -> Self {
+        self.security = security;
+        self.password = password.to_string();
+        self
+    }
+
+    /// Set if the network is hidden (default: `false`)
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+}
+
+impl QRCodePayload for QRCodeWifi {
+    fn data(&self) -> Result<String> {
+        if self.ssid.is_empty() {
+            return Err(PrinterError::Input("Wi-Fi SSID can not be empty".to_owned()));
+        }
+        if self.security != WifiSecurity::Open && self.password.is_empty() {
+            return Err(PrinterError::Input(format!(
+                "Wi-Fi password is required with {} security",
+                self.security
+            )));
+        }
+
+        let special_chars = ['\\', ';', ',', ':', '"'];
+        let mut data = format!("WIFI:T:{};S:{};", self.security, escape(&self.ssid, &special_chars));
+        if self.security != WifiSecurity::Open {
+            data.push_str(&format!("P:{};", escape(&self.password, &special_chars)));
+        }
+        if self.hidden {
+            data.push_str("H:true;");
+        }
+        data.push(';');
+        Ok(data)
+    }
+}
+
+/// Contact payload (vCard 3.0)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QRCodeVCard {
+    first_name: String,
+    last_name: String,
+    organization: Option<String>,
+    title: Option<String>,
+    phones: Vec<String>,
+    emails: Vec<String>,
+    url: Option<String>,
+    address: Option<[String; 4]>,
+    note: Option<String>,
+}
+
+impl QRCodeVCard {
+    /// Create a new `QRCodeVCard`
+    pub fn new(first_name: &str, last_name: &str) -> Self {
+        Self {
+            first_name: first_name.to_string(),
+            last_name: last_name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the organization
+    pub fn organization(mut self, organization: &str) -> Self {
+        self.organization = Some(organization.to_string());
+        self
+    }
+
+    /// Set the job title
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Add a phone number
+    pub fn phone(mut self, phone: &str) -> Self {
+        self.phones.push(phone.to_string());
+        self
+    }
+
+    /// Add an email address
+    pub fn email(mut self, email: &str) -> Self {
+        self.emails.push(email.to_string());
+        self
+    }
+
+    /// Set the website
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Set the postal address
+    pub fn address(mut self, street: &str, city: &str, postal_code: &str, country: &str) -> Self {
+        self.address = Some([street, city, postal_code, country].map(|value| value.to_string()));
+        self
+    }
+
+    /// Set the note
+    pub fn note(mut self, note: &str) -> Self {
+        self.note = Some(note.to_string());
+        self
+    }
+}
+
+impl QRCodePayload for QRCodeVCard {
+    fn data(&self) -> Result<String> {
+        if self.first_name.is_empty() && self.last_name.is_empty() {
+            return Err(PrinterError::Input("vCard name can not be empty".to_owned()));
+        }
+
+        let text = |value: &str| {
+            escape(value, &['\\', ',', ';'])
+                .replace("\r\n", "\\n")
+                .replace('\n', "\\n")
+        };
+        let full_name = [self.first_name.as_str(), self.last_name.as_str()]
+            .into_iter()
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            "VERSION:3.0".to_string(),
+            format!("N:{};{};;;", text(&self.last_name), text(&self.first_name)),
+            format!("FN:{}", text(&full_name)),
+        ];
+        if let Some(organization) = &self.organization {
+            lines.push(format!("ORG:{}", text(organization)));
+        }
+        if let Some(title) = &self.title {
+            lines.push(format!("TITLE:{}", text(title)));
+        }
+        lines.extend(self.phones.iter().map(|phone| format!("TEL:{}", text(phone))));
+        lines.extend(self.emails.iter().map(|email| format!("EMAIL:{}", text(email))));
+        if let Some(url) = &self.url {
+            lines.push(format!("URL:{}", text(url)));
+        }
+        if let Some([street, city, postal_code, country]) = &self.address {
+            lines.push(format!(
+                "ADR:;;{};{};;{};{}",
+                text(street),
+                text(city),
+                text(postal_code),
+                text(country)
+            ));
+        }
+        if let Some(note) = &self.note {
+            lines.push(format!("NOTE:{}", text(note)));
+        }
+        lines.push("END:VCARD".to_string());
+
+        Ok(lines.join("\r\n"))
+    }
+}
+
+/// SEPA credit transfer payload (EPC QR code, version 002)
+///
+/// The error correction level of an EPC QR code is always M.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QRCodeEpcPayment {
+    name: String,
+    iban: String,
+    bic: Option<String>,
+    amount: Option<u64>,
+    purpose: Option<String>,
+    reference: Option<String>,
+    text: Option<String>,
+    information: Option<String>,
+}
+
+impl QRCodeEpcPayment {
+    /// Create a new `QRCodeEpcPayment` with the beneficiary name and IBAN
+    pub fn new(name: &str, iban: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            iban: iban
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_uppercase(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the BIC of the beneficiary bank
+    pub fn bic(mut self, bic: &str) -> Self {
+        self.bic = Some(bic.to_uppercase());
+        self
+    }
+
+    /// Set the amount in euro cents (0.01 to 999999999.99 euros)
+    pub fn amount(mut self, cents: u64) -> Self {
+        self.amount = Some(cents);
+        self
+    }
+
+    /// Set the purpose code (4 characters)
+    pub fn purpose(mut self, purpose: &str) -> Self {
+        self.purpose = Some(purpose.to_uppercase());
+        self
+    }
+
+    /// Set the structured creditor reference (35 characters maximum, exclusive with the text)
+    pub fn reference(mut self, reference: &str) -> Self {
+        self.reference = Some(reference.to_string());
+        self
+    }
+
+    /// Set the unstructured remittance text (140 characters maximum, exclusive with the reference)
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Set the information to the payer (70 characters maximum)
+    pub fn information(mut self, information: &str) -> Self {
+        self.information = Some(information.to_string());
+        self
+    }
+
+    /// Check the IBAN (length, characters and modulo 97 checksum)
+    fn check_iban(&self) -> Result<()> {
+        let iban = &self.iban;
+        let is_valid = (15..=34).contains(&iban.len())
+            && iban.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            && iban[..2].chars().all(|c| c.is_ascii_uppercase())
+            && iban[2..4].chars().all(|c| c.is_ascii_digit())
+            && iban[4..]
+                .chars()
+                .chain(iban[..4].chars())
+                .filter_map(|c| c.to_digit(36))
+                .fold(0, |remainder, value| {
+                    let factor = if value < 10 { 10 } else { 100 };
+                    (remainder * factor + value) % 97
+                })
+                == 1;
+        if !is_valid {
+            return Err(PrinterError::Input(format!("invalid EPC IBAN: {iban}")));
+        }
+        Ok(())
+    }
+}
+
+impl QRCodePayload for QRCodeEpcPayment {
+    fn data(&self) -> Result<String> {
+        let check_length = |name: &str, value: &str, max: usize| {
+            let length = value.chars().count();
+            if length > max || value.contains(['\r', '\n']) {
+                return Err(PrinterError::Input(format!(
+                    "invalid EPC {name} ({length} characters, the maximum is {max}): {value}"
+                )));
+            }
+            Ok(())
+        };
+
+        if self.name.is_empty() {
+            return Err(PrinterError::Input("EPC beneficiary name can not be empty".to_owned()));
+        }
+        check_length("beneficiary name", &self.name, 70)?;
+        self.check_iban()?;
+        if let Some(bic) = &self.bic
+            && (!matches!(bic.len(), 8 | 11) || !bic.chars().all(|c| c.is_ascii_alphanumeric()))
+        {
+            return Err(PrinterError::Input(format!("invalid EPC BIC: {bic}")));
+        }
+        if let Some(amount) = self.amount
+            && !(1..=99_999_999_999).contains(&amount)
+        {
+            return Err(PrinterError::Input(format!("invalid EPC amount: {amount} cents")));
+        }
+        if let Some(purpose) = &self.purpose
+            && (purpose.len() != 4 || !purpose.chars().all(|c| c.is_ascii_alphanumeric()))
+        {
+            return Err(PrinterError::Input(format!("invalid EPC purpose code: {purpose}")));
+        }
+        if self.reference.is_some() && self.text.is_some() {
+            return Err(PrinterError::Input(
+                "EPC reference and text can not be both set".to_owned(),
+            ));
+        }
+        check_length("reference", self.reference.as_deref().unwrap_or_default(), 35)?;
+        check_length("text", self.text.as_deref().unwrap_or_default(), 140)?;
+        check_length("information", self.information.as_deref().unwrap_or_default(), 70)?;
+
+        let amount = self
+            .amount
+            .map(|amount| format!("EUR{}.{:02}", amount / 100, amount % 100))
+            .unwrap_or_default();
+        let mut lines = vec![
+            "BCD",
+            "002",
+            "1",
+            "SCT",
+            self.bic.as_deref().unwrap_or_default(),
+            &self.name,
+            &self.iban,
+            &amount,
+            self.purpose.as_deref().unwrap_or_default(),
+            self.reference.as_deref().unwrap_or_default(),
+            self.text.as_deref().unwrap_or_default(),
+            self.information.as_deref().unwrap_or_default(),
+        ];
+        // The last empty fields can be omitted
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let data = lines.join("\n");
+        if data.len() > EPC_MAX_DATA_SIZE {
+            return Err(PrinterError::Input(format!(
+                "EPC data is too long ({} bytes), the maximum is {EPC_MAX_DATA_SIZE}",
+                data.len()
+            )));
+        }
+        Ok(data)
+    }
+
+    fn option(&self, _data: &str) -> Result<QRCodeOption> {
+        Ok(QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M))
+    }
+}
+
+/// URL payload with optional UTM (campaign tracking) parameters
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QRCodeUrl {
+    url: String,
+    parameters: Vec<(String, String)>,
+}
+
+impl QRCodeUrl {
+    /// Create a new `QRCodeUrl` (`http` or `https` URL)
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Add a query parameter (the name and the value are percent-encoded)
+    pub fn parameter(mut self, name: &str, value: &str) -> Self {
+        self.parameters.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set the campaign source (`utm_source`)
+    pub fn utm_source(self, source: &str) -> Self {
+        self.parameter("utm_source", source)
+    }
+
+    /// Set the campaign medium (`utm_medium`)
+    pub fn utm_medium(self, medium: &str) -> Self {
+        self.parameter("utm_medium", medium)
+    }
+
+    /// Set the campaign name (`utm_campaign`)
+    pub fn utm_campaign(self, campaign: &str) -> Self {
+        self.parameter("utm_campaign", campaign)
+    }
+
+    /// Set the campaign term (`utm_term`)
+    pub fn utm_term(self, term: &str) -> Self {
+        self.parameter("utm_term", term)
+    }
+
+    /// Set the campaign content (`utm_content`)
+    pub fn utm_content(self, content: &str) -> Self {
+        self.parameter("utm_content", content)
+    }
+}
+
+/// Percent-encode a query component (only the unreserved characters are kept)
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+impl QRCodePayload for QRCodeUrl {
+    fn data(&self) -> Result<String> {
+        let scheme_len = self.url.find("://").unwrap_or_default();
+        let scheme = self.url[..scheme_len].to_ascii_lowercase();
+        if !matches!(scheme.as_str(), "http" | "https")
+            || self.url.len() == scheme_len + 3
+            || self.url.contains(char::is_whitespace)
+        {
+            return Err(PrinterError::Input(format!("invalid URL: {}", self.url)));
+        }
+
+        let (url, fragment) = self
+            .url
+            .split_once('#')
+            .map_or((self.url.as_str(), None), |(url, fragment)| (url, Some(fragment)));
+        let mut data = url.to_string();
+        for (name, value) in &self.parameters {
+            if !data.contains('?') {
+                data.push('?');
+            } else if !data.ends_with(['?', '&']) {
+                data.push('&');
+            }
+            data.push_str(&format!("{}={}", percent_encode(name), percent_encode(value)));
+        }
+        if let Some(fragment) = fragment {
+            data.push_str(&format!("#{fragment}"));
+        }
+        Ok(data)
+    }
 }
 
 #[cfg(test)]
@@ -168,4 +855,152 @@ mod tests {
         let data = "azerty123456789QTG,{".repeat(400);
         assert!(QRCode::check_data(&data).is_err());
     }
+
+    #[test]
+    fn test_qrcode_option_check_data() {
+        let option = |model, correction_level| QRCodeOption::new(model, 4, correction_level);
+        let model2 = option(QRCodeModel::Model2, QRCodeCorrectionLevel::L);
+        assert!(model2.check_data("1".repeat(7089).as_bytes()).is_ok());
+        assert!(model2.check_data("1".repeat(7090).as_bytes()).is_err());
+        assert!(model2.check_data("A".repeat(4296).as_bytes()).is_ok());
+        assert!(model2.check_data("A".repeat(4297).as_bytes()).is_err());
+        assert!(model2.check_data(&[0xFF; 2953]).is_ok());
+        assert!(model2.check_data(&[0xFF; 2954]).is_err());
+
+        let model1 = option(QRCodeModel::Model1, QRCodeCorrectionLevel::M);
+        assert!(model1.check_data(&[b'a'; 382]).is_ok());
+        assert!(model1.check_data(&[b'a'; 383]).is_err());
+
+        let micro = option(QRCodeModel::Micro, QRCodeCorrectionLevel::L);
+        assert!(micro.check_data(b"0123456789").is_ok());
+        assert!(micro.check_data(&[b'a'; 16]).is_err());
+        let micro = option(QRCodeModel::Micro, QRCodeCorrectionLevel::H);
+        assert!(micro.check_data(b"1").is_err());
+    }
+
+    #[test]
+    fn test_qrcode_encoding() {
+        let option = QRCodeOption::default().encoding(Encoder::new(encoding_rs::SHIFT_JIS));
+        assert_eq!(
+            QRCode::new("漢字", Some(option)).unwrap().encoded_data().unwrap(),
+            [0x8A, 0xBF, 0x8E, 0x9A]
+        );
+        assert_eq!(QRCode::new("é", None).unwrap().encoded_data().unwrap(), [0xC3, 0xA9]);
+
+        let option = QRCodeOption::default().encoding(Encoder::new(encoding_rs::WINDOWS_1252));
+        assert!(QRCode::new("漢字", Some(option)).unwrap().encoded_data().is_err());
+    }
+
+    #[test]
+    fn test_qrcode_option_for_data() {
+        let option = QRCodeOption::for_data("Hello").unwrap();
+        assert_eq!((option.size(), u8::from(option.correction_level())), (4, 51));
+        let option = QRCodeOption::for_data(&"a".repeat(1000)).unwrap();
+        assert_eq!((option.size(), u8::from(option.correction_level())), (3, 51));
+        let option = QRCodeOption::for_data(&"a".repeat(2000)).unwrap();
+        assert_eq!((option.size(), u8::from(option.correction_level())), (2, 49));
+        assert!(QRCodeOption::for_data(&"a".repeat(2953)).is_ok());
+        assert!(QRCodeOption::for_data(&"a".repeat(2954)).is_err());
+    }
+
+    #[test]
+    fn test_qrcode_wifi() {
+        assert_eq!(QRCodeWifi::new("Guest").data().unwrap(), "WIFI:T:nopass;S:Guest;;");
+        assert_eq!(
+            QRCodeWifi::new(r#"My "Home""#)
+                .password(WifiSecurity::WPA, r"a;b,c:d\e")
+                .hidden(true)
+                .data()
+                .unwrap(),
+            r#"WIFI:T:WPA;S:My \"Home\";P:a\;b\,c\:d\\e;H:true;;"#
+        );
+        assert!(QRCodeWifi::new("").data().is_err());
+        assert!(QRCodeWifi::new("Home").password(WifiSecurity::WPA3, "").data().is_err());
+    }
+
+    #[test]
+    fn test_qrcode_vcard() {
+        let vcard = QRCodeVCard::new("John", "Doe")
+            .organization("Doe, Inc.")
+            .phone("+33 1 23 45 67 89")
+            .email("john@example.com")
+            .address("1 rue de la Paix", "Paris", "75002", "France")
+            .note("Line 1\nLine 2; end");
+        assert_eq!(
+            vcard.data().unwrap(),
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;John;;;\r\nFN:John Doe\r\nORG:Doe\\, Inc.\r\n\
+             TEL:+33 1 23 45 67 89\r\nEMAIL:john@example.com\r\nADR:;;1 rue de la Paix;Paris;;75002;France\r\n\
+             NOTE:Line 1\\nLine 2\\; end\r\nEND:VCARD"
+        );
+        assert!(QRCodeVCard::new("", "").data().is_err());
+    }
+
+    #[test]
+    fn test_qrcode_epc_payment() {
+        let payment = QRCodeEpcPayment::new("Wikimedia Foerdergesellschaft", "DE33 1002 0500 0001 1947 00")
+            .bic("BFSWDE33BER")
+            .amount(12345)
+            .text("Spende fuer Wikipedia");
+        assert_eq!(
+            payment.data().unwrap(),
+            "BCD\n002\n1\nSCT\nBFSWDE33BER\nWikimedia Foerdergesellschaft\nDE33100205000001194700\nEUR123.45\n\n\n\
+             Spende fuer Wikipedia"
+        );
+        assert_eq!(u8::from(payment.option("").unwrap().correction_level()), 49);
+
+        // Minimal payment
+        assert_eq!(
+            QRCodeEpcPayment::new("Doe", "DE89370400440532013000").data().unwrap(),
+            "BCD\n002\n1\nSCT\n\nDoe\nDE89370400440532013000"
+        );
+
+        // Wrong IBAN checksum
+        assert!(QRCodeEpcPayment::new("Doe", "DE89370400440532013001").data().is_err());
+        assert!(QRCodeEpcPayment::new("", "DE89370400440532013000").data().is_err());
+        let payment = QRCodeEpcPayment::new("Doe", "DE89370400440532013000");
+        assert!(payment.clone().bic("BFSW").data().is_err());
+        assert!(payment.clone().amount(0).data().is_err());
+        assert!(payment.clone().amount(100_000_000_000).data().is_err());
+        assert!(payment.clone().purpose("CHARITY").data().is_err());
+        assert!(
+            payment
+                .clone()
+                .reference("RF18539007547034")
+                .text("Invoice")
+                .data()
+                .is_err()
+        );
+        assert!(payment.clone().text(&"a".repeat(141)).data().is_err());
+        assert!(
+            QRCodeEpcPayment::new(&"a".repeat(71), "DE89370400440532013000")
+                .data()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_qrcode_url() {
+        assert_eq!(
+            QRCodeUrl::new("https://example.com/shop#top")
+                .utm_source("receipt")
+                .utm_campaign("summer sale & more")
+                .data()
+                .unwrap(),
+            "https://example.com/shop?utm_source=receipt&utm_campaign=summer%20sale%20%26%20more#top"
+        );
+        assert_eq!(
+            QRCodeUrl::new("http://example.com/?id=1")
+                .utm_medium("print")
+                .data()
+                .unwrap(),
+            "http://example.com/?id=1&utm_medium=print"
+        );
+        assert_eq!(
+            QRCodeUrl::new("https://example.com").data().unwrap(),
+            "https://example.com"
+        );
+        assert!(QRCodeUrl::new("example.com").data().is_err());
+        assert!(QRCodeUrl::new("ftp://example.com").data().is_err());
+        assert!(QRCodeUrl::new("https://").data().is_err());
+    }
 }
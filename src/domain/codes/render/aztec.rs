@@ -0,0 +1,552 @@
+//! Aztec code rendering
+
+use super::Modules;
+use crate::domain::{AztecMode, AztecOption, RasterBitImage};
+use crate::errors::{PrinterError, Result};
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec, vec::Vec};
+
+/// Aztec code quiet zone (in modules)
+const QUIET_ZONE: usize = 1;
+
+/// Maximum number of bytes of a binary shift
+const MAX_BINARY_SHIFT: usize = 2047 + 31;
+
+/// Size of the codewords by number of layers
+const WORD_SIZES: [usize; 33] = [
+    4, 6, 6, 8, 8, 8, 8, 8, 8, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 12, 12, 12, 12, 12, 12, 12, 12,
+    12, 12,
+];
+
+/// Text encoding mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Upper,
+    Lower,
+    Mixed,
+    Digit,
+}
+
+impl Mode {
+    /// Number of bits of a code
+    fn bits(self) -> usize {
+        match self {
+            Mode::Digit => 4,
+            _ => 5,
+        }
+    }
+
+    /// Code of a character in the mode
+    fn code(self, c: u8) -> Option<u32> {
+        let code = match (self, c) {
+            (_, b' ') => 1,
+            (Mode::Upper, b'A'..=b'Z') => c - b'A' + 2,
+            (Mode::Lower, b'a'..=b'z') => c - b'a' + 2,
+            (Mode::Mixed, 1..=13) => c + 1,
+            (Mode::Mixed, 27..=31) => c - 12,
+            (Mode::Mixed, b'@') => 20,
+            (Mode::Mixed, b'\\') => 21,
+            (Mode::Mixed, b'^') => 22,
+            (Mode::Mixed, b'_') => 23,
+            (Mode::Mixed, b'`') => 24,
+            (Mode::Mixed, b'|') => 25,
+            (Mode::Mixed, b'~') => 26,
+            (Mode::Mixed, 127) => 27,
+            (Mode::Digit, b'0'..=b'9') => c - b'0' + 2,
+            (Mode::Digit, b',') => 12,
+            (Mode::Digit, b'.') => 13,
+            _ => return None,
+        };
+        Some(u32::from(code))
+    }
+
+    /// Codes to latch to another mode (value, number of bits)
+    fn latch(self, to: Mode) -> &'static [(u32, usize)] {
+        match (self, to) {
+            (Mode::Upper, Mode::Lower) | (Mode::Mixed, Mode::Lower) => &[(28, 5)],
+            (Mode::Upper, Mode::Mixed) | (Mode::Lower, Mode::Mixed) | (Mode::Mixed, Mode::Upper) => &[(29, 5)],
+            (Mode::Upper, Mode::Digit) | (Mode::Lower, Mode::Digit) => &[(30, 5)],
+            (Mode::Lower, Mode::Upper) => &[(30, 5), (14, 4)],
+            (Mode::Mixed, Mode::Digit) => &[(29, 5), (30, 5)],
+            (Mode::Digit, Mode::Upper) => &[(14, 4)],
+            (Mode::Digit, Mode::Lower) => &[(14, 4), (28, 5)],
+            (Mode::Digit, Mode::Mixed) => &[(14, 4), (29, 5)],
+            _ => &[],
+        }
+    }
+
+    /// Code to shift to the upper mode for one character
+    fn upper_shift(self) -> Option<u32> {
+        match self {
+            Mode::Lower => Some(28),
+            Mode::Digit => Some(15),
+            _ => None,
+        }
+    }
+}
+
+/// Code of a character of the punctuation mode
+fn punct_code(c: u8) -> Option<u32> {
+    let code = match c {
+        b'\r' => 1,
+        b'!'..=b'/' => c - b'!' + 6,
+        b':'..=b'?' => c - b':' + 21,
+        b'[' => 27,
+        b']' => 28,
+        b'{' => 29,
+        b'}' => 30,
+        _ => return None,
+    };
+    Some(u32::from(code))
+}
+
+/// Sequence of bits
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Bits(Vec<bool>);
+
+impl Bits {
+    /// Append the `count` lowest bits of a value (most significant first)
+    fn push(&mut self, value: u32, count: usize) {
+        self.0.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.0.get(index).copied().unwrap_or(false)
+    }
+
+    /// Split the bits in words of `size` bits (the missing bits of the last word are `fill`)
+    fn word(&self, index: usize, size: usize, fill: bool) -> u32 {
+        (0..size).fold(0, |word, i| {
+            let bit = self.0.get(index * size + i).copied().unwrap_or(fill);
+            word << 1 | u32::from(bit)
+        })
+    }
+}
+
+/// Render an Aztec code
+pub(crate) fn render(data: &[u8], option: &AztecOption) -> Result<RasterBitImage> {
+    let modules = encode(data, option.mode(), option.correction_level())?;
+    let size = u16::from(option.size());
+    modules.raster((QUIET_ZONE, QUIET_ZONE), (size, size))
+}
+
+/// Encode data into an Aztec code symbol
+///
+/// The number of layers of the mode is the smallest one which can hold the data and the error correction
+/// (`0` means automatic). `correction_level` is the minimum percentage of error correction codewords.
+pub(crate) fn encode(data: &[u8], mode: AztecMode, correction_level: u8) -> Result<Modules> {
+    let bits = high_level_encode(data);
+    let ecc_bits = bits.len() * usize::from(correction_level) / 100 + 11;
+
+    let (compact, layers) = match mode {
+        AztecMode::Compact(0) => (true, 1..=4),
+        AztecMode::Compact(n) => (true, n..=n),
+        AztecMode::FullRange(0) => (false, 4..=32),
+        AztecMode::FullRange(n) => (false, n..=n),
+    };
+    let (layers, word_size, stuffed) = layers
+        .map(usize::from)
+        .find_map(|layers| {
+            let word_size = WORD_SIZES[layers];
+            let total_bits = layer_bits(layers, compact);
+            let stuffed = stuff_bits(&bits, word_size);
+            let words = stuffed.len() / word_size;
+            let fits = stuffed.len() + ecc_bits <= total_bits - total_bits % word_size
+                && words <= if compact { 64 } else { 2048 };
+            fits.then_some((layers, word_size, stuffed))
+        })
+        .ok_or_else(|| {
+            PrinterError::Input(format!(
+                "Aztec code data is too long for the symbol ({mode}): {} bits",
+                bits.len()
+            ))
+        })?;
+
+    let message = add_check_words(&stuffed, layer_bits(layers, compact), word_size);
+    let words = stuffed.len() / word_size;
+    let mut mode_message = Bits::default();
+    if compact {
+        mode_message.push(layers as u32 - 1, 2);
+        mode_message.push(words as u32 - 1, 6);
+    } else {
+        mode_message.push(layers as u32 - 1, 5);
+        mode_message.push(words as u32 - 1, 11);
+    }
+    let mode_message = add_check_words(&mode_message, if compact { 28 } else { 40 }, 4);
+
+    Ok(symbol(&message, &mode_message, layers, compact))
+}
+
+/// Encode the data with the text modes, the punctuation shift and the binary shift
+fn high_level_encode(data: &[u8]) -> Bits {
+    let mut bits = Bits::default();
+    let mut mode = Mode::Upper;
+    let mut i = 0;
+
+    while i < data.len() {
+        let c = data[i];
+        if let Some(code) = mode.code(c) {
+            bits.push(code, mode.bits());
+            i += 1;
+            continue;
+        }
+
+        let target = if c.is_ascii_digit() {
+            Some(Mode::Digit)
+        } else {
+            [Mode::Upper, Mode::Lower, Mode::Mixed]
+                .into_iter()
+                .find(|target| target.code(c).is_some())
+        };
+        let next_in_target = |target: Mode| data.get(i + 1).is_some_and(|&next| target.code(next).is_some());
+
+        match (target, punct_code(c)) {
+            // A single upper case letter is shifted
+            (Some(Mode::Upper), _) if !next_in_target(Mode::Upper) && mode.upper_shift().is_some() => {
+                bits.push(mode.upper_shift().unwrap_or_default(), mode.bits());
+                bits.push(Mode::Upper.code(c).unwrap_or_default(), 5);
+                i += 1;
+            }
+            (Some(target), punct) if next_in_target(target) || punct.is_none() => {
+                for &(code, count) in mode.latch(target) {
+                    bits.push(code, count);
+                }
+                mode = target;
+            }
+            (_, Some(code)) => {
+                bits.push(0, mode.bits());
+                bits.push(code, 5);
+                i += 1;
+            }
+            (_, None) => {
+                let count = data[i..]
+                    .iter()
+                    .take(MAX_BINARY_SHIFT)
+                    .take_while(|&&c| {
+                        punct_code(c).is_none()
+                            && [Mode::Upper, Mode::Lower, Mode::Mixed, Mode::Digit]
+                                .iter()
+                                .all(|mode| mode.code(c).is_none())
+                    })
+                    .count();
+                if mode == Mode::Digit {
+                    for &(code, count) in mode.latch(Mode::Upper) {
+                        bits.push(code, count);
+                    }
+                    mode = Mode::Upper;
+                }
+                bits.push(31, 5);
+                if count <= 31 {
+                    bits.push(count as u32, 5);
+                } else {
+                    bits.push(0, 5);
+                    bits.push((count - 31) as u32, 11);
+                }
+                for &byte in &data[i..i + count] {
+                    bits.push(u32::from(byte), 8);
+                }
+                i += count;
+            }
+        }
+    }
+
+    bits
+}
+
+/// Number of bits of the data layers
+fn layer_bits(layers: usize, compact: bool) -> usize {
+    (if compact { 88 } else { 112 } + 16 * layers) * layers
+}
+
+/// Split the bits in words, the words made of `0` or `1` only get a complementary stuffed bit
+fn stuff_bits(bits: &Bits, word_size: usize) -> Bits {
+    let mut stuffed = Bits::default();
+    let mask = (1 << word_size) - 2;
+    let mut i = 0;
+    while i < bits.len() {
+        let word = (0..word_size).fold(0, |word, j| {
+            let bit = i + j >= bits.len() || bits.get(i + j);
+            word << 1 | u32::from(bit)
+        });
+        if word & mask == mask {
+            stuffed.push(word & mask, word_size);
+            i += word_size - 1;
+        } else if word & mask == 0 {
+            stuffed.push(word | 1, word_size);
+            i += word_size - 1;
+        } else {
+            stuffed.push(word, word_size);
+            i += word_size;
+        }
+    }
+    stuffed
+}
+
+/// Add the Reed-Solomon check words, the message is aligned on the end of the `total_bits` bits
+fn add_check_words(bits: &Bits, total_bits: usize, word_size: usize) -> Bits {
+    let data: Vec<u32> = (0..bits.len() / word_size)
+        .map(|i| bits.word(i, word_size, false))
+        .collect();
+    let ecc = reed_solomon(&data, total_bits / word_size - data.len(), word_size);
+
+    let mut message = Bits::default();
+    message.push(0, total_bits % word_size);
+    for &word in data.iter().chain(&ecc) {
+        message.push(word, word_size);
+    }
+    message
+}
+
+/// Galois field primitive polynomial by word size
+fn gf_polynomial(word_size: usize) -> u32 {
+    match word_size {
+        4 => 0x13,
+        6 => 0x43,
+        8 => 0x12D,
+        10 => 0x409,
+        _ => 0x1069,
+    }
+}
+
+/// Compute the Reed-Solomon check words in GF(2^word_size)
+fn reed_solomon(data: &[u32], ecc_length: usize, word_size: usize) -> Vec<u32> {
+    let size = 1 << word_size;
+    let (mut log, mut exp) = (vec![0; size], vec![0; size - 1]);
+    let mut value = 1;
+    for (i, exp) in exp.iter_mut().enumerate() {
+        *exp = value;
+        log[value as usize] = i;
+        value <<= 1;
+        if value >= size as u32 {
+            value ^= gf_polynomial(word_size);
+        }
+    }
+    let multiply = |a: u32, b: u32| {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            exp[(log[a as usize] + log[b as usize]) % (size - 1)]
+        }
+    };
+
+    // Generator polynomial (x - 2^1)(x - 2^2)...(x - 2^n), the highest degree first
+    let mut generator = vec![1];
+    for i in 1..=ecc_length {
+        let root = exp[i % (size - 1)];
+        let mut next = vec![0; generator.len() + 1];
+        for (j, &coefficient) in generator.iter().enumerate() {
+            next[j] ^= coefficient;
+            next[j + 1] ^= multiply(coefficient, root);
+        }
+        generator = next;
+    }
+
+    let mut ecc = vec![0; ecc_length];
+    for &word in data {
+        let factor = word ^ ecc.first().copied().unwrap_or_default();
+        if ecc_length > 0 {
+            ecc.rotate_left(1);
+            ecc[ecc_length - 1] = 0;
+        }
+        for (value, &coefficient) in ecc.iter_mut().zip(&generator[1..]) {
+            *value ^= multiply(factor, coefficient);
+        }
+    }
+    ecc
+}
+
+/// Build the symbol: data layers, mode message, finder pattern and reference grid
+fn symbol(message: &Bits, mode_message: &Bits, layers: usize, compact: bool) -> Modules {
+    let base_size = if compact { 11 } else { 14 } + layers * 4;
+    let (size, alignment) = if compact {
+        (base_size, (0..base_size).collect::<Vec<_>>())
+    } else {
+        // The reference grid lines are inserted every 16 modules from the center
+        let size = base_size + 1 + 2 * ((base_size / 2 - 1) / 15);
+        let (base_center, center) = (base_size / 2, size / 2);
+        let mut alignment = vec![0; base_size];
+        for i in 0..base_center {
+            let offset = i + i / 15;
+            alignment[base_center - i - 1] = center - offset - 1;
+            alignment[base_center + i] = center + offset + 1;
+        }
+        (size, alignment)
+    };
+    let mut modules = Modules::new(size, size);
+
+    // Data layers, from the outermost one, 2 modules wide and counterclockwise from the top left corner
+    let mut offset = 0;
+    for layer in 0..layers {
+        let row_size = (layers - layer) * 4 + if compact { 9 } else { 12 };
+        let (low, high) = (layer * 2, base_size - 1 - layer * 2);
+        for j in 0..row_size {
+            for k in 0..2 {
+                let bit = |side: usize| message.get(offset + row_size * 2 * side + j * 2 + k);
+                if bit(0) {
+                    modules.set(alignment[low + k], alignment[low + j], true);
+                }
+                if bit(1) {
+                    modules.set(alignment[low + j], alignment[high - k], true);
+                }
+                if bit(2) {
+                    modules.set(alignment[high - k], alignment[high - j], true);
+                }
+                if bit(3) {
+                    modules.set(alignment[high - j], alignment[low + k], true);
+                }
+            }
+        }
+        offset += row_size * 8;
+    }
+
+    // Mode message around the finder pattern
+    let center = size / 2;
+    if compact {
+        for i in 0..7 {
+            let offset = center - 3 + i;
+            modules.set(offset, center - 5, mode_message.get(i));
+            modules.set(center + 5, offset, mode_message.get(i + 7));
+            modules.set(offset, center + 5, mode_message.get(20 - i));
+            modules.set(center - 5, offset, mode_message.get(27 - i));
+        }
+    } else {
+        for i in 0..10 {
+            let offset = center - 5 + i + i / 5;
+            modules.set(offset, center - 7, mode_message.get(i));
+            modules.set(center + 7, offset, mode_message.get(i + 10));
+            modules.set(offset, center + 7, mode_message.get(29 - i));
+            modules.set(center - 7, offset, mode_message.get(39 - i));
+        }
+    }
+
+    // Finder pattern (bull's-eye) and orientation patterns
+    let rings = if compact { 5 } else { 7 };
+    for ring in 0..rings {
+        for i in center - ring..=center + ring {
+            for (x, y) in [
+                (i, center - ring),
+                (i, center + ring),
+                (center - ring, i),
+                (center + ring, i),
+            ] {
+                modules.set(x, y, ring % 2 == 0);
+            }
+        }
+    }
+    for (x, y) in [
+        (center - rings, center - rings),
+        (center - rings + 1, center - rings),
+        (center - rings, center - rings + 1),
+        (center + rings, center - rings),
+        (center + rings, center - rings + 1),
+        (center + rings, center + rings - 1),
+    ] {
+        modules.set(x, y, true);
+    }
+
+    // Reference grid
+    if !compact {
+        for line in (0..base_size / 2 - 1).step_by(15).map(|i| i / 15 * 16) {
+            for k in (center & 1..size).step_by(2) {
+                modules.set(center - line, k, true);
+                modules.set(center + line, k, true);
+                modules.set(k, center - line, true);
+                modules.set(k, center + line, true);
+            }
+        }
+    }
+
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evaluate a polynomial (the highest degree first) in GF(2^word_size)
+    fn syndrome(words: &[u32], power: usize, word_size: usize) -> u32 {
+        let size = 1 << word_size;
+        let multiply = |a: u32, b: u32| {
+            (0..word_size).fold((0, a), |(product, a), i| {
+                let product = if b >> i & 1 == 1 { product ^ a } else { product };
+                let a = a << 1;
+                (product, if a >= size { a ^ gf_polynomial(word_size) } else { a })
+            })
+        };
+        let root = (0..power).fold(1, |value, _| multiply(value, 2).0);
+        words.iter().fold(0, |value, &word| multiply(value, root).0 ^ word)
+    }
+
+    #[test]
+    fn test_high_level_encode() {
+        // Upper mode: "AB"
+        let bits = high_level_encode(b"AB");
+        assert_eq!(bits, {
+            let mut expected = Bits::default();
+            expected.push(2, 5);
+            expected.push(3, 5);
+            expected
+        });
+
+        // Lower latch, digit latch, punctuation shift and binary shift
+        let bits = high_level_encode(b"a1!\xFF");
+        let mut expected = Bits::default();
+        for (value, count) in [
+            (28, 5),
+            (2, 5),
+            (30, 5),
+            (3, 4),
+            (0, 4),
+            (6, 5),
+            (14, 4),
+            (31, 5),
+            (1, 5),
+            (0xFF, 8),
+        ] {
+            expected.push(value, count);
+        }
+        assert_eq!(bits, expected);
+    }
+
+    #[test]
+    fn test_stuff_bits() {
+        let mut bits = Bits::default();
+        bits.push(0b000000_111111, 12);
+        let stuffed = stuff_bits(&bits, 6);
+        let words: Vec<u32> = (0..stuffed.len() / 6).map(|i| stuffed.word(i, 6, false)).collect();
+        assert_eq!(words, [0b000001, 0b011111, 0b111110]);
+    }
+
+    #[test]
+    fn test_reed_solomon() {
+        for word_size in [4, 6, 8, 10, 12] {
+            let data = [1, 2, 3, 4, 5];
+            let ecc = reed_solomon(&data, 6, word_size);
+            let words: Vec<u32> = data.iter().chain(&ecc).copied().collect();
+            assert!((1..=6).all(|power| syndrome(&words, power, word_size) == 0));
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        // Compact, 1 layer: 15 x 15 modules
+        let modules = encode(b"Hello", AztecMode::Compact(0), 23).unwrap();
+        assert_eq!((modules.width(), modules.height()), (15, 15));
+        // Bull's-eye
+        assert!(modules.get(7, 7) && !modules.get(8, 7) && modules.get(9, 7) && !modules.get(10, 7));
+        assert!(modules.get(11, 11) && modules.get(3, 3) && !modules.get(4, 4));
+
+        // Full-range, 4 layers: 31 x 31 modules with the reference grid
+        let modules = encode(b"Hello", AztecMode::FullRange(0), 23).unwrap();
+        assert_eq!((modules.width(), modules.height()), (31, 31));
+        assert!(modules.get(15, 1) && !modules.get(15, 0) && modules.get(1, 15));
+
+        assert_eq!(encode(b"Hello", AztecMode::Compact(3), 23).unwrap().width(), 23);
+        assert!(encode(&[b'A'; 100], AztecMode::Compact(1), 23).is_err());
+        assert!(encode(&[0xFF; 2000], AztecMode::FullRange(0), 50).is_err());
+    }
+}
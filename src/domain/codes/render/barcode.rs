@@ -0,0 +1,699 @@
+//! Barcodes rendering
+
+use super::Modules;
+use crate::domain::codes::check_digit::{digits_string, gtin_digits, parse_digits, upce_digits};
+use crate::domain::codes::code128::{self, Symbol};
+use crate::domain::codes::gs1;
+use crate::domain::{Barcode, BarcodeSystem, RasterBitImage};
+use crate::errors::{PrinterError, Result};
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Number of modules of a wide element (narrow element: 1 module)
+const WIDE_ELEMENT_MODULES: usize = 3;
+
+/// EAN / UPC quiet zone (in modules)
+const EAN_QUIET_ZONE: usize = 11;
+/// CODE39, ITF, CODABAR, CODE93 and CODE128 quiet zone (in modules)
+const QUIET_ZONE: usize = 10;
+
+/// EAN / UPC odd parity (L) digit patterns (7 modules, the most significant bit is the leftmost module)
+const EAN_L_PATTERNS: [u8; 10] = [
+    0b0001101, 0b0011001, 0b0010011, 0b0111101, 0b0100011, 0b0110001, 0b0101111, 0b0111011, 0b0110111, 0b0001011,
+];
+
+/// EAN13 parity of the left half digits, by first digit (bit set for an even parity (G) digit)
+const EAN13_PARITIES: [u8; 10] = [
+    0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110, 0b011010,
+];
+
+/// UPC-E parity of the digits for the number system 0, by check digit (bit set for an even parity (G) digit)
+const UPCE_PARITIES: [u8; 10] = [
+    0b111000, 0b110100, 0b110010, 0b110001, 0b101100, 0b100110, 0b100011, 0b101010, 0b101001, 0b100101,
+];
+
+/// CODE39 characters
+const CODE39_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%*";
+
+/// CODE39 character patterns (9 elements, bar first, bit set for a wide element)
+const CODE39_PATTERNS: [u16; 44] = [
+    0x034, 0x121, 0x061, 0x160, 0x031, 0x130, 0x070, 0x025, 0x124, 0x064, 0x109, 0x049, 0x148, 0x019, 0x118, 0x058,
+    0x00D, 0x10C, 0x04C, 0x01C, 0x103, 0x043, 0x142, 0x013, 0x112, 0x052, 0x007, 0x106, 0x046, 0x016, 0x181, 0x0C1,
+    0x1C0, 0x091, 0x190, 0x0D0, 0x085, 0x184, 0x0C4, 0x0A8, 0x0A2, 0x08A, 0x02A, 0x094,
+];
+
+/// ITF digit patterns (5 elements, bit set for a wide element)
+const ITF_PATTERNS: [u8; 10] = [
+    0b00110, 0b10001, 0b01001, 0b11000, 0b00101, 0b10100, 0b01100, 0b00011, 0b10010, 0b01010,
+];
+
+/// CODABAR characters
+const CODABAR_CHARS: &str = "0123456789-$:/.+ABCD";
+
+/// CODABAR character patterns (7 elements, bar first, bit set for a wide element)
+const CODABAR_PATTERNS: [u8; 20] = [
+    0x03, 0x06, 0x09, 0x60, 0x12, 0x42, 0x21, 0x24, 0x30, 0x48, 0x0C, 0x18, 0x45, 0x51, 0x54, 0x15, 0x1A, 0x29, 0x0B,
+    0x0E,
+];
+
+/// CODE93 characters (the values 43 - 46 are the shift characters ($), (%), (/) and (+))
+const CODE93_CHARS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+/// CODE93 character patterns by value, then the start / stop pattern (widths of the 3 bars and 3 spaces, bar first)
+const CODE93_PATTERNS: [[u8; 6]; 48] = [
+    [1, 3, 1, 1, 1, 2],
+    [1, 1, 1, 2, 1, 3],
+    [1, 1, 1, 3, 1, 2],
+    [1, 1, 1, 4, 1, 1],
+    [1, 2, 1, 1, 1, 3],
+    [1, 2, 1, 2, 1, 2],
+    [1, 2, 1, 3, 1, 1],
+    [1, 1, 1, 1, 1, 4],
+    [1, 3, 1, 2, 1, 1],
+    [1, 4, 1, 1, 1, 1],
+    [2, 1, 1, 1, 1, 3],
+    [2, 1, 1, 2, 1, 2],
+    [2, 1, 1, 3, 1, 1],
+    [2, 2, 1, 1, 1, 2],
+    [2, 2, 1, 2, 1, 1],
+    [2, 3, 1, 1, 1, 1],
+    [1, 1, 2, 1, 1, 3],
+    [1, 1, 2, 2, 1, 2],
+    [1, 1, 2, 3, 1, 1],
+    [1, 2, 2, 1, 1, 2],
+    [1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 3],
+    [1, 1, 1, 2, 2, 2],
+    [1, 1, 1, 3, 2, 1],
+    [1, 2, 1, 1, 2, 2],
+    [1, 3, 1, 1, 2, 1],
+    [2, 1, 2, 1, 1, 2],
+    [2, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 2, 2],
+    [2, 1, 1, 2, 2, 1],
+    [2, 2, 1, 1, 2, 1],
+    [2, 2, 2, 1, 1, 1],
+    [1, 1, 2, 1, 2, 2],
+    [1, 1, 2, 2, 2, 1],
+    [1, 2, 2, 1, 2, 1],
+    [1, 2, 3, 1, 1, 1],
+    [1, 2, 1, 1, 3, 1],
+    [3, 1, 1, 1, 1, 2],
+    [3, 1, 1, 2, 1, 1],
+    [3, 2, 1, 1, 1, 1],
+    [1, 1, 2, 1, 3, 1],
+    [1, 1, 3, 1, 2, 1],
+    [2, 1, 1, 1, 3, 1],
+    [1, 2, 1, 2, 2, 1],
+    [3, 1, 2, 1, 1, 1],
+    [3, 1, 1, 1, 2, 1],
+    [1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 4, 1],
+];
+
+/// CODE93 start / stop pattern index
+const CODE93_START_STOP: usize = 47;
+
+/// CODE128 symbol patterns by value (widths of the 3 bars and 3 spaces, bar first)
+const CODE128_PATTERNS: [[u8; 6]; 106] = [
+    [2, 1, 2, 2, 2, 2],
+    [2, 2, 2, 1, 2, 2],
+    [2, 2, 2, 2, 2, 1],
+    [1, 2, 1, 2, 2, 3],
+    [1, 2, 1, 3, 2, 2],
+    [1, 3, 1, 2, 2, 2],
+    [1, 2, 2, 2, 1, 3],
+    [1, 2, 2, 3, 1, 2],
+    [1, 3, 2, 2, 1, 2],
+    [2, 2, 1, 2, 1, 3],
+    [2, 2, 1, 3, 1, 2],
+    [2, 3, 1, 2, 1, 2],
+    [1, 1, 2, 2, 3, 2],
+    [1, 2, 2, 1, 3, 2],
+    [1, 2, 2, 2, 3, 1],
+    [1, 1, 3, 2, 2, 2],
+    [1, 2, 3, 1, 2, 2],
+    [1, 2, 3, 2, 2, 1],
+    [2, 2, 3, 2, 1, 1],
+    [2, 2, 1, 1, 3, 2],
+    [2, 2, 1, 2, 3, 1],
+    [2, 1, 3, 2, 1, 2],
+    [2, 2, 3, 1, 1, 2],
+    [3, 1, 2, 1, 3, 1],
+    [3, 1, 1, 2, 2, 2],
+    [3, 2, 1, 1, 2, 2],
+    [3, 2, 1, 2, 2, 1],
+    [3, 1, 2, 2, 1, 2],
+    [3, 2, 2, 1, 1, 2],
+    [3, 2, 2, 2, 1, 1],
+    [2, 1, 2, 1, 2, 3],
+    [2, 1, 2, 3, 2, 1],
+    [2, 3, 2, 1, 2, 1],
+    [1, 1, 1, 3, 2, 3],
+    [1, 3, 1, 1, 2, 3],
+    [1, 3, 1, 3, 2, 1],
+    [1, 1, 2, 3, 1, 3],
+    [1, 3, 2, 1, 1, 3],
+    [1, 3, 2, 3, 1, 1],
+    [2, 1, 1, 3, 1, 3],
+    [2, 3, 1, 1, 1, 3],
+    [2, 3, 1, 3, 1, 1],
+    [1, 1, 2, 1, 3, 3],
+    [1, 1, 2, 3, 3, 1],
+    [1, 3, 2, 1, 3, 1],
+    [1, 1, 3, 1, 2, 3],
+    [1, 1, 3, 3, 2, 1],
+    [1, 3, 3, 1, 2, 1],
+    [3, 1, 3, 1, 2, 1],
+    [2, 1, 1, 3, 3, 1],
+    [2, 3, 1, 1, 3, 1],
+    [2, 1, 3, 1, 1, 3],
+    [2, 1, 3, 3, 1, 1],
+    [2, 1, 3, 1, 3, 1],
+    [3, 1, 1, 1, 2, 3],
+    [3, 1, 1, 3, 2, 1],
+    [3, 3, 1, 1, 2, 1],
+    [3, 1, 2, 1, 1, 3],
+    [3, 1, 2, 3, 1, 1],
+    [3, 3, 2, 1, 1, 1],
+    [3, 1, 4, 1, 1, 1],
+    [2, 2, 1, 4, 1, 1],
+    [4, 3, 1, 1, 1, 1],
+    [1, 1, 1, 2, 2, 4],
+    [1, 1, 1, 4, 2, 2],
+    [1, 2, 1, 1, 2, 4],
+    [1, 2, 1, 4, 2, 1],
+    [1, 4, 1, 1, 2, 2],
+    [1, 4, 1, 2, 2, 1],
+    [1, 1, 2, 2, 1, 4],
+    [1, 1, 2, 4, 1, 2],
+    [1, 2, 2, 1, 1, 4],
+    [1, 2, 2, 4, 1, 1],
+    [1, 4, 2, 1, 1, 2],
+    [1, 4, 2, 2, 1, 1],
+    [2, 4, 1, 2, 1, 1],
+    [2, 2, 1, 1, 1, 4],
+    [4, 1, 3, 1, 1, 1],
+    [2, 4, 1, 1, 1, 2],
+    [1, 3, 4, 1, 1, 1],
+    [1, 1, 1, 2, 4, 2],
+    [1, 2, 1, 1, 4, 2],
+    [1, 2, 1, 2, 4, 1],
+    [1, 1, 4, 2, 1, 2],
+    [1, 2, 4, 1, 1, 2],
+    [1, 2, 4, 2, 1, 1],
+    [4, 1, 1, 2, 1, 2],
+    [4, 2, 1, 1, 1, 2],
+    [4, 2, 1, 2, 1, 1],
+    [2, 1, 2, 1, 4, 1],
+    [2, 1, 4, 1, 2, 1],
+    [4, 1, 2, 1, 2, 1],
+    [1, 1, 1, 1, 4, 3],
+    [1, 1, 1, 3, 4, 1],
+    [1, 3, 1, 1, 4, 1],
+    [1, 1, 4, 1, 1, 3],
+    [1, 1, 4, 3, 1, 1],
+    [4, 1, 1, 1, 1, 3],
+    [4, 1, 1, 3, 1, 1],
+    [1, 1, 3, 1, 4, 1],
+    [1, 1, 4, 1, 3, 1],
+    [3, 1, 1, 1, 4, 1],
+    [4, 1, 1, 1, 3, 1],
+    [2, 1, 1, 4, 1, 2],
+    [2, 1, 1, 2, 1, 4],
+    [2, 1, 1, 2, 3, 2],
+];
+
+/// CODE128 stop pattern (widths of the 4 bars and 3 spaces, bar first)
+const CODE128_STOP_PATTERN: [u8; 7] = [2, 3, 3, 1, 1, 1, 2];
+
+/// Modules of a barcode
+#[derive(Debug, Default)]
+struct Bars(Vec<bool>);
+
+impl Bars {
+    /// Add modules from a pattern (`count` modules, the most significant bit first)
+    fn pattern(&mut self, pattern: u32, count: u8) {
+        for i in (0..count).rev() {
+            self.0.push(pattern & (1 << i) != 0);
+        }
+    }
+
+    /// Add bars and spaces from wide / narrow elements (`count` elements starting with a bar,
+    /// the most significant bit first)
+    fn elements(&mut self, wide: u32, count: u8) {
+        for i in 0..count {
+            let width = if wide & (1 << (count - 1 - i)) != 0 {
+                WIDE_ELEMENT_MODULES
+            } else {
+                1
+            };
+            self.0.extend(core::iter::repeat_n(i % 2 == 0, width));
+        }
+    }
+
+    /// Add bars and spaces from their widths (in modules, starting with a bar)
+    fn widths(&mut self, widths: &[u8]) {
+        for (i, &width) in widths.iter().enumerate() {
+            self.0.extend(core::iter::repeat_n(i % 2 == 0, usize::from(width)));
+        }
+    }
+
+    /// Add a narrow space
+    fn gap(&mut self) {
+        self.0.push(false);
+    }
+}
+
+/// Render a barcode
+///
+/// Returns the image and the human readable interpretation (HRI) of the data.
+pub(crate) fn render(barcode: &Barcode) -> Result<(RasterBitImage, String)> {
+    let (bars, hri) = encode(barcode.system, &barcode.data)?;
+    let quiet_zone = match barcode.system {
+        BarcodeSystem::UPCA | BarcodeSystem::UPCE | BarcodeSystem::EAN13 | BarcodeSystem::EAN8 => EAN_QUIET_ZONE,
+        BarcodeSystem::CODE39
+        | BarcodeSystem::ITF
+        | BarcodeSystem::CODABAR
+        | BarcodeSystem::CODE93
+        | BarcodeSystem::CODE128
+        | BarcodeSystem::GS1_128 => QUIET_ZONE,
+        BarcodeSystem::GS1DataBarOmnidirectional
+        | BarcodeSystem::GS1DataBarTruncated
+        | BarcodeSystem::GS1DataBarLimited
+        | BarcodeSystem::GS1DataBarExpanded => 0,
+    };
+
+    let option = &barcode.option;
+    let raster = Modules::from_row(bars).raster(
+        (quiet_zone, 0),
+        (
+            u16::from(u8::from(option.width())),
+            u16::from(u8::from(option.height())),
+        ),
+    )?;
+
+    Ok((raster, hri))
+}
+
+/// Encode barcode data into modules (`true` for a bar)
+///
+/// Returns the modules and the human readable interpretation (HRI) of the data.
+pub(crate) fn encode(system: BarcodeSystem, data: &str) -> Result<(Vec<bool>, String)> {
+    match system {
+        BarcodeSystem::EAN13 => {
+            let digits = gtin_digits(data, 13, "EAN13")?;
+            Ok((ean13(&digits), digits_string(&digits)))
+        }
+        BarcodeSystem::UPCA => {
+            let digits = gtin_digits(data, 12, "UPC-A")?;
+            let mut ean = vec![0];
+            ean.extend_from_slice(&digits);
+            Ok((ean13(&ean), digits_string(&digits)))
+        }
+        BarcodeSystem::EAN8 => {
+            let digits = gtin_digits(data, 8, "EAN8")?;
+            Ok((ean8(&digits), digits_string(&digits)))
+        }
+        BarcodeSystem::UPCE => {
+            let digits = upce_digits(data)?;
+            Ok((upce(&digits), digits_string(&digits)))
+        }
+        BarcodeSystem::CODE39 => code39(data),
+        BarcodeSystem::ITF => itf(data),
+        BarcodeSystem::CODABAR => codabar(data),
+        BarcodeSystem::CODE93 => code93(data),
+        BarcodeSystem::CODE128 => code128(data),
+        BarcodeSystem::GS1_128 => gs1_128(data),
+        BarcodeSystem::GS1DataBarOmnidirectional
+        | BarcodeSystem::GS1DataBarTruncated
+        | BarcodeSystem::GS1DataBarLimited
+        | BarcodeSystem::GS1DataBarExpanded => {
+            Err(PrinterError::Input(format!("{system} can not be rendered as an image")))
+        }
+    }
+}
+
+/// Encode EAN13 digits (with check digit)
+fn ean13(digits: &[u8]) -> Vec<bool> {
+    let parities = EAN13_PARITIES[usize::from(digits[0])];
+    let mut bars = Bars::default();
+
+    bars.pattern(0b101, 3);
+    for (i, &digit) in digits[1..7].iter().enumerate() {
+        let even = parities & (0b100000 >> i) != 0;
+        bars.pattern(ean_left_pattern(digit, even), 7);
+    }
+    bars.pattern(0b01010, 5);
+    for &digit in &digits[7..] {
+        bars.pattern(ean_right_pattern(digit), 7);
+    }
+    bars.pattern(0b101, 3);
+
+    bars.0
+}
+
+/// Encode EAN8 digits (with check digit)
+fn ean8(digits: &[u8]) -> Vec<bool> {
+    let mut bars = Bars::default();
+
+    bars.pattern(0b101, 3);
+    for &digit in &digits[..4] {
+        bars.pattern(ean_left_pattern(digit, false), 7);
+    }
+    bars.pattern(0b01010, 5);
+    for &digit in &digits[4..] {
+        bars.pattern(ean_right_pattern(digit), 7);
+    }
+    bars.pattern(0b101, 3);
+
+    bars.0
+}
+
+/// Get the pattern of a left half digit (odd parity (L) or even parity (G))
+fn ean_left_pattern(digit: u8, even: bool) -> u32 {
+    if even {
+        // G pattern: R pattern reversed
+        (ean_right_pattern(digit).reverse_bits() >> 25) & 0x7F
+    } else {
+        u32::from(EAN_L_PATTERNS[usize::from(digit)])
+    }
+}
+
+/// Get the pattern of a right half digit (R pattern: L pattern complement)
+fn ean_right_pattern(digit: u8) -> u32 {
+    u32::from(!EAN_L_PATTERNS[usize::from(digit)] & 0x7F)
+}
+
+/// Encode UPC-E digits (number system, 6 digits and check digit)
+fn upce(digits: &[u8]) -> Vec<bool> {
+    let mut parities = UPCE_PARITIES[usize::from(digits[7])];
+    if digits[0] == 1 {
+        parities = !parities & 0b111111;
+    }
+    let mut bars = Bars::default();
+
+    bars.pattern(0b101, 3);
+    for (i, &digit) in digits[1..7].iter().enumerate() {
+        let even = parities & (0b100000 >> i) != 0;
+        bars.pattern(ean_left_pattern(digit, even), 7);
+    }
+    bars.pattern(0b010101, 6);
+
+    bars.0
+}
+
+/// Encode CODE39 data (the start / stop characters are added if missing)
+fn code39(data: &str) -> Result<(Vec<bool>, String)> {
+    let content = data.strip_prefix('*').unwrap_or(data);
+    let content = content.strip_suffix('*').unwrap_or(content);
+    if content.is_empty() || content.contains('*') {
+        return Err(PrinterError::Input(format!("invalid CODE39 data: {data}")));
+    }
+
+    let hri = format!("*{content}*");
+    let mut bars = Bars::default();
+    for (i, c) in hri.chars().enumerate() {
+        let index = CODE39_CHARS
+            .find(c)
+            .ok_or_else(|| PrinterError::Input(format!("invalid CODE39 data: {data}")))?;
+        if i > 0 {
+            bars.gap();
+        }
+        bars.elements(u32::from(CODE39_PATTERNS[index]), 9);
+    }
+
+    Ok((bars.0, hri))
+}
+
+/// Encode ITF data (even number of digits)
+fn itf(data: &str) -> Result<(Vec<bool>, String)> {
+    let digits = parse_digits(data, "ITF")?;
+    if digits.len() % 2 != 0 {
+        return Err(PrinterError::Input(format!(
+            "invalid ITF data (odd number of digits): {data}"
+        )));
+    }
+
+    let mut bars = Bars::default();
+    bars.elements(0b0000, 4);
+    for pair in digits.chunks(2) {
+        let (bar, space) = (ITF_PATTERNS[usize::from(pair[0])], ITF_PATTERNS[usize::from(pair[1])]);
+        // Interleave the bars of the first digit with the spaces of the second one
+        let mut wide = 0;
+        for i in (0..5).rev() {
+            wide = (wide << 2) | (u32::from((bar >> i) & 1) << 1) | u32::from((space >> i) & 1);
+        }
+        bars.elements(wide, 10);
+    }
+    bars.elements(0b100, 3);
+
+    Ok((bars.0, data.into()))
+}
+
+/// Encode CODABAR data (with start and stop characters)
+fn codabar(data: &str) -> Result<(Vec<bool>, String)> {
+    let chars: Vec<char> = data.chars().map(|c| c.to_ascii_uppercase()).collect();
+    let is_start_stop = |c: Option<&char>| c.is_some_and(|c| ('A'..='D').contains(c));
+    if chars.len() < 2
+        || !is_start_stop(chars.first())
+        || !is_start_stop(chars.last())
+        || chars[1..chars.len() - 1].iter().any(|c| ('A'..='D').contains(c))
+    {
+        return Err(PrinterError::Input(format!("invalid CODABAR data: {data}")));
+    }
+
+    let mut bars = Bars::default();
+    for (i, &c) in chars.iter().enumerate() {
+        let index = CODABAR_CHARS
+            .find(c)
+            .ok_or_else(|| PrinterError::Input(format!("invalid CODABAR data: {data}")))?;
+        if i > 0 {
+            bars.gap();
+        }
+        bars.elements(u32::from(CODABAR_PATTERNS[index]), 7);
+    }
+
+    Ok((bars.0, data.into()))
+}
+
+/// Get the CODE93 full ASCII shift character value and letter of a character out of the CODE93 characters
+fn code93_full_ascii(c: u8) -> (u8, u8) {
+    match c {
+        0 => (44, b'U'),
+        1..=26 => (43, c - 1 + b'A'),
+        27..=31 => (44, c - 27 + b'A'),
+        33..=44 => (45, c - 33 + b'A'),
+        b':' => (45, b'Z'),
+        b';'..=b'?' => (44, c - b';' + b'F'),
+        b'@' => (44, b'V'),
+        b'['..=b'_' => (44, c - b'[' + b'K'),
+        b'`' => (44, b'W'),
+        b'a'..=b'z' => (46, c - b'a' + b'A'),
+        _ => (44, c.saturating_sub(b'{') + b'P'),
+    }
+}
+
+/// Encode CODE93 data (full ASCII, the two check characters are added)
+fn code93(data: &str) -> Result<(Vec<bool>, String)> {
+    if data.is_empty() || !data.is_ascii() {
+        return Err(PrinterError::Input(format!("invalid CODE93 data: {data}")));
+    }
+
+    let mut values = Vec::with_capacity(data.len() + 2);
+    for c in data.bytes() {
+        match CODE93_CHARS.find(char::from(c)) {
+            Some(index) => values.push(index as u8),
+            None => {
+                let (shift, letter) = code93_full_ascii(c);
+                values.extend([shift, letter - b'A' + 10]);
+            }
+        }
+    }
+    // Check characters C and K (weights from 1 to 20, then from 1 to 15, starting from the right)
+    for max_weight in [20, 15] {
+        let sum: usize = values
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &value)| (i % max_weight + 1) * usize::from(value))
+            .sum();
+        values.push((sum % 47) as u8);
+    }
+
+    let mut bars = Bars::default();
+    bars.widths(&CODE93_PATTERNS[CODE93_START_STOP]);
+    for value in values {
+        bars.widths(&CODE93_PATTERNS[usize::from(value)]);
+    }
+    bars.widths(&CODE93_PATTERNS[CODE93_START_STOP]);
+    // Termination bar
+    bars.widths(&[1]);
+
+    Ok((bars.0, data.into()))
+}
+
+/// Encode CODE128 symbols (with the check symbol and the stop pattern)
+fn code128_bars(symbols: &[Symbol]) -> Vec<bool> {
+    let mut bars = Bars::default();
+    for value in code128::values(symbols) {
+        bars.widths(&CODE128_PATTERNS[usize::from(value)]);
+    }
+    bars.widths(&CODE128_STOP_PATTERN);
+
+    bars.0
+}
+
+/// Encode CODE128 data (the code sets are selected automatically)
+fn code128(data: &str) -> Result<(Vec<bool>, String)> {
+    code128::validate(data)?;
+
+    Ok((code128_bars(&code128::encode(data.as_bytes())), data.into()))
+}
+
+/// Encode GS1-128 data (AIs in parentheses)
+fn gs1_128(data: &str) -> Result<(Vec<bool>, String)> {
+    let elements = gs1::parse(data)?;
+    let hri = elements.iter().map(ToString::to_string).collect();
+
+    Ok((code128_bars(&code128::encode(&gs1::code128_data(&elements))), hri))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Convert modules to a string of `1` (bar) and `0` (space)
+    fn modules(bars: &[bool]) -> String {
+        bars.iter().map(|&bar| if bar { '1' } else { '0' }).collect()
+    }
+
+    #[test]
+    fn test_encode_ean13() {
+        let (bars, hri) = encode(BarcodeSystem::EAN13, "400638133393").unwrap();
+        assert_eq!(hri, "4006381333931");
+        assert_eq!(bars.len(), 95);
+
+        let bars = modules(&bars);
+        // Start guard, 0 (L), 0 (G), 6 (L)
+        assert!(bars.starts_with("101000110101001110101111"));
+        // 3 (R), 1 (R) and end guard
+        assert!(bars.ends_with("10000101100110101"));
+        assert_eq!(&bars[45..50], "01010");
+
+        assert!(encode(BarcodeSystem::EAN13, "4006381333932").is_err());
+    }
+
+    #[test]
+    fn test_encode_ean8_upca() {
+        let (bars, hri) = encode(BarcodeSystem::EAN8, "9638507").unwrap();
+        assert_eq!((bars.len(), hri.as_str()), (67, "96385074"));
+
+        let (bars, hri) = encode(BarcodeSystem::UPCA, "03600029145").unwrap();
+        assert_eq!((bars.len(), hri.as_str()), (95, "036000291452"));
+        // Leading 0: all left digits with odd parity
+        assert_eq!(&modules(&bars)[3..10], "0001101");
+    }
+
+    #[test]
+    fn test_encode_upce() {
+        let (bars, hri) = encode(BarcodeSystem::UPCE, "0123456").unwrap();
+        assert_eq!((bars.len(), hri.as_str()), (51, "01234565"));
+        // Check digit 5: parities EOOEEO
+        assert_eq!(&modules(&bars)[3..10], "0110011");
+        assert!(modules(&bars).ends_with("010101"));
+
+        assert_eq!(encode(BarcodeSystem::UPCE, "01234500006").unwrap().1, "01234565");
+        assert_eq!(encode(BarcodeSystem::UPCE, "012345000065").unwrap().1, "01234565");
+        assert!(encode(BarcodeSystem::UPCE, "01234565").is_ok());
+        assert!(encode(BarcodeSystem::UPCE, "01234564").is_err());
+        assert!(encode(BarcodeSystem::UPCE, "01234567890").is_err());
+        assert!(encode(BarcodeSystem::UPCE, "2123456").is_err());
+    }
+
+    #[test]
+    fn test_encode_code39() {
+        let (bars, hri) = encode(BarcodeSystem::CODE39, "A1").unwrap();
+        assert_eq!(hri, "*A1*");
+        // 4 characters of 15 modules and 3 gaps
+        assert_eq!(bars.len(), 63);
+        assert!(modules(&bars).starts_with("1000101110111010"));
+
+        assert_eq!(encode(BarcodeSystem::CODE39, "*A1*").unwrap().0, bars);
+        assert!(encode(BarcodeSystem::CODE39, "A*1").is_err());
+    }
+
+    #[test]
+    fn test_encode_itf() {
+        let (bars, _) = encode(BarcodeSystem::ITF, "12").unwrap();
+        // Start, 1 (bars) interleaved with 2 (spaces), stop
+        assert_eq!(modules(&bars), "101011101000101011100011101");
+        assert!(encode(BarcodeSystem::ITF, "123").is_err());
+    }
+
+    #[test]
+    fn test_encode_codabar() {
+        let (bars, hri) = encode(BarcodeSystem::CODABAR, "A0A").unwrap();
+        assert_eq!(hri, "A0A");
+        // A, 0, A separated by narrow gaps
+        let (a, zero) = ("1011100010001", "10101000111");
+        assert_eq!(modules(&bars), format!("{a}0{zero}0{a}"));
+
+        assert!(encode(BarcodeSystem::CODABAR, "a0b").is_ok());
+        assert!(encode(BarcodeSystem::CODABAR, "0A").is_err());
+        assert!(encode(BarcodeSystem::CODABAR, "AAA").is_err());
+    }
+
+    #[test]
+    fn test_encode_code128() {
+        let (bars, hri) = encode(BarcodeSystem::CODE128, "AB").unwrap();
+        assert_eq!(hri, "AB");
+        // Start B, "A", "B", check symbol (102) and stop
+        let expected = [
+            "11010010000",
+            "10100011000",
+            "10001011000",
+            "11110101110",
+            "1100011101011",
+        ];
+        assert_eq!(modules(&bars), expected.concat());
+
+        // Every pattern has 11 modules
+        for pattern in CODE128_PATTERNS {
+            assert_eq!(pattern.iter().map(|&width| usize::from(width)).sum::<usize>(), 11);
+        }
+        assert!(encode(BarcodeSystem::CODE128, "").is_err());
+    }
+
+    #[test]
+    fn test_encode_code93() {
+        let (bars, hri) = encode(BarcodeSystem::CODE93, "TEST93").unwrap();
+        assert_eq!(hri, "TEST93");
+        // Start, 6 characters, 2 check characters, stop and termination bar
+        assert_eq!(bars.len(), 10 * 9 + 1);
+        // Check characters: C = "+" (41), K = "6"
+        let bars = modules(&bars);
+        assert_eq!(&bars[63..81], "101110110100100010");
+
+        // Full ASCII: "a" is encoded as (+)A
+        let (bars, _) = encode(BarcodeSystem::CODE93, "a").unwrap();
+        assert_eq!(bars.len(), 6 * 9 + 1);
+        assert!(encode(BarcodeSystem::CODE93, "").is_err());
+    }
+
+    #[test]
+    fn test_encode_gs1_128() {
+        let (bars, hri) = encode(BarcodeSystem::GS1_128, "(01)09501101530003").unwrap();
+        assert_eq!(hri, "(01)09501101530003");
+        // Start C, FNC1, 8 pairs of digits, check symbol and stop
+        assert_eq!(bars.len(), 11 * 11 + 13);
+        assert!(modules(&bars).starts_with("1101001110011110101110"));
+
+        assert!(encode(BarcodeSystem::GS1DataBarExpanded, "(01)09501101530003").is_err());
+    }
+}
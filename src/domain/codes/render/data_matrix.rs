@@ -0,0 +1,537 @@
+//! DataMatrix (ECC 200) rendering
+
+use super::Modules;
+use crate::domain::{DataMatrixOption, DataMatrixType, RasterBitImage};
+use crate::errors::{PrinterError, Result};
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec, vec::Vec};
+
+/// DataMatrix quiet zone (in modules)
+const QUIET_ZONE: usize = 1;
+
+/// Pad codeword
+const PAD: u8 = 129;
+
+/// Upper shift codeword (next character is an extended ASCII character)
+const UPPER_SHIFT: u8 = 235;
+
+/// FNC1 codeword (GS1 DataMatrix in first position)
+const FNC1: u8 = 232;
+
+/// Galois field GF(256) primitive polynomial (x^8 + x^5 + x^3 + x^2 + 1)
+const GF_POLYNOMIAL: u16 = 0x12D;
+
+/// Symbol size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SymbolSize {
+    /// Number of rows (finder patterns included)
+    rows: usize,
+    /// Number of columns (finder patterns included)
+    columns: usize,
+    /// Number of rows of a data region
+    region_rows: usize,
+    /// Number of columns of a data region
+    region_columns: usize,
+    /// Number of data codewords
+    data: usize,
+    /// Number of error correction codewords
+    ecc: usize,
+    /// Number of interleaved blocks
+    blocks: usize,
+}
+
+impl SymbolSize {
+    const fn new(
+        rows: usize,
+        columns: usize,
+        region_rows: usize,
+        region_columns: usize,
+        data: usize,
+        ecc: usize,
+        blocks: usize,
+    ) -> Self {
+        Self {
+            rows,
+            columns,
+            region_rows,
+            region_columns,
+            data,
+            ecc,
+            blocks,
+        }
+    }
+
+    /// Is the symbol square?
+    fn is_square(&self) -> bool {
+        self.rows == self.columns
+    }
+
+    /// Get the number of data regions (horizontally, vertically)
+    fn regions(&self) -> (usize, usize) {
+        (
+            self.columns / (self.region_columns + 2),
+            self.rows / (self.region_rows + 2),
+        )
+    }
+}
+
+/// ECC 200 symbol sizes, from the smallest to the largest
+const SYMBOL_SIZES: [SymbolSize; 30] = [
+    SymbolSize::new(10, 10, 8, 8, 3, 5, 1),
+    SymbolSize::new(12, 12, 10, 10, 5, 7, 1),
+    SymbolSize::new(8, 18, 6, 16, 5, 7, 1),
+    SymbolSize::new(14, 14, 12, 12, 8, 10, 1),
+    SymbolSize::new(8, 32, 6, 14, 10, 11, 1),
+    SymbolSize::new(16, 16, 14, 14, 12, 12, 1),
+    SymbolSize::new(12, 26, 10, 24, 16, 14, 1),
+    SymbolSize::new(18, 18, 16, 16, 18, 14, 1),
+    SymbolSize::new(20, 20, 18, 18, 22, 18, 1),
+    SymbolSize::new(12, 36, 10, 16, 22, 18, 1),
+    SymbolSize::new(22, 22, 20, 20, 30, 20, 1),
+    SymbolSize::new(16, 36, 14, 16, 32, 24, 1),
+    SymbolSize::new(24, 24, 22, 22, 36, 24, 1),
+    SymbolSize::new(26, 26, 24, 24, 44, 28, 1),
+    SymbolSize::new(16, 48, 14, 22, 49, 28, 1),
+    SymbolSize::new(32, 32, 14, 14, 62, 36, 1),
+    SymbolSize::new(36, 36, 16, 16, 86, 42, 1),
+    SymbolSize::new(40, 40, 18, 18, 114, 48, 1),
+    SymbolSize::new(44, 44, 20, 20, 144, 56, 1),
+    SymbolSize::new(48, 48, 22, 22, 174, 68, 1),
+    SymbolSize::new(52, 52, 24, 24, 204, 84, 2),
+    SymbolSize::new(64, 64, 14, 14, 280, 112, 2),
+    SymbolSize::new(72, 72, 16, 16, 368, 144, 4),
+    SymbolSize::new(80, 80, 18, 18, 456, 192, 4),
+    SymbolSize::new(88, 88, 20, 20, 576, 224, 4),
+    SymbolSize::new(96, 96, 22, 22, 696, 272, 4),
+    SymbolSize::new(104, 104, 24, 24, 816, 336, 6),
+    SymbolSize::new(120, 120, 18, 18, 1050, 408, 6),
+    SymbolSize::new(132, 132, 20, 20, 1304, 496, 8),
+    SymbolSize::new(144, 144, 22, 22, 1558, 620, 10),
+];
+
+/// Render a DataMatrix
+pub(crate) fn render(data: &[u8], option: &DataMatrixOption) -> Result<RasterBitImage> {
+    let modules = encode(data, option.code_type(), option.is_gs1())?;
+    let size = u16::from(option.size());
+    modules.raster((QUIET_ZONE, QUIET_ZONE), (size, size))
+}
+
+/// Encode data into a DataMatrix symbol
+///
+/// The size of the symbol is the smallest one of the type which can hold the data
+/// (`0` for the number of rows or columns means automatic).
+/// A GS1 DataMatrix starts with the FNC1 codeword, the GS separators of the data are kept.
+pub(crate) fn encode(data: &[u8], code_type: DataMatrixType, gs1: bool) -> Result<Modules> {
+    let mut codewords = if gs1 { vec![FNC1] } else { Vec::new() };
+    codewords.extend(ascii_encode(data));
+    let size = SYMBOL_SIZES
+        .iter()
+        .filter(|size| match code_type {
+            DataMatrixType::Square(0) => size.is_square(),
+            DataMatrixType::Square(d) => size.is_square() && size.rows == usize::from(d),
+            DataMatrixType::Rectangle(rows, 0) => !size.is_square() && size.rows == usize::from(rows),
+            DataMatrixType::Rectangle(rows, columns) => {
+                size.rows == usize::from(rows) && size.columns == usize::from(columns)
+            }
+        })
+        .find(|size| size.data >= codewords.len())
+        .copied()
+        .ok_or_else(|| {
+            PrinterError::Input(format!(
+                "DataMatrix data is too long for the symbol ({code_type}): {} codewords",
+                codewords.len()
+            ))
+        })?;
+
+    pad(&mut codewords, size.data);
+    let codewords = add_error_correction(&codewords, &size);
+    let mapping = place(
+        &codewords,
+        size.rows - 2 * size.regions().1,
+        size.columns - 2 * size.regions().0,
+    );
+
+    Ok(symbol(&mapping, &size))
+}
+
+/// Encode data with the ASCII encodation (digit pairs are compacted)
+fn ascii_encode(data: &[u8]) -> Vec<u8> {
+    let mut codewords = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        match (data[i], data.get(i + 1)) {
+            (a @ b'0'..=b'9', Some(&b @ b'0'..=b'9')) => {
+                codewords.push(130 + (a - b'0') * 10 + (b - b'0'));
+                i += 1;
+            }
+            (c @ 0..=127, _) => codewords.push(c + 1),
+            (c, _) => codewords.extend([UPPER_SHIFT, c - 127]),
+        }
+        i += 1;
+    }
+    codewords
+}
+
+/// Pad the codewords up to the capacity of the symbol
+fn pad(codewords: &mut Vec<u8>, capacity: usize) {
+    if codewords.len() < capacity {
+        codewords.push(PAD);
+    }
+    while codewords.len() < capacity {
+        // 253-state randomizing algorithm
+        let position = codewords.len() + 1;
+        let value = usize::from(PAD) + (149 * position) % 253 + 1;
+        codewords.push(if value > 254 { value - 254 } else { value } as u8);
+    }
+}
+
+/// Galois field GF(256) log and antilog tables
+fn gf_tables() -> ([u8; 256], [u8; 255]) {
+    let (mut log, mut exp) = ([0; 256], [0; 255]);
+    let mut value: u16 = 1;
+    for (i, exp) in exp.iter_mut().enumerate() {
+        *exp = value as u8;
+        log[usize::from(value)] = i as u8;
+        value <<= 1;
+        if value >= 256 {
+            value ^= GF_POLYNOMIAL;
+        }
+    }
+    (log, exp)
+}
+
+/// Compute the Reed-Solomon error correction codewords of a block
+fn reed_solomon(data: &[u8], ecc_length: usize) -> Vec<u8> {
+    let (log, exp) = gf_tables();
+    let multiply = |a: u8, b: u8| {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            exp[(usize::from(log[usize::from(a)]) + usize::from(log[usize::from(b)])) % 255]
+        }
+    };
+
+    // Generator polynomial (x - 2^1)(x - 2^2)...(x - 2^n), the highest degree first
+    let mut generator = vec![1];
+    for i in 1..=ecc_length {
+        let root = exp[i % 255];
+        let mut next = vec![0; generator.len() + 1];
+        for (j, &coefficient) in generator.iter().enumerate() {
+            next[j] ^= coefficient;
+            next[j + 1] ^= multiply(coefficient, root);
+        }
+        generator = next;
+    }
+
+    let mut ecc = vec![0; ecc_length];
+    for &codeword in data {
+        let factor = codeword ^ ecc[0];
+        ecc.rotate_left(1);
+        ecc[ecc_length - 1] = 0;
+        for (value, &coefficient) in ecc.iter_mut().zip(&generator[1..]) {
+            *value ^= multiply(factor, coefficient);
+        }
+    }
+    ecc
+}
+
+/// Add the error correction codewords (interleaved blocks)
+fn add_error_correction(data: &[u8], size: &SymbolSize) -> Vec<u8> {
+    let ecc_length = size.ecc / size.blocks;
+    let mut codewords = data.to_vec();
+    codewords.resize(size.data + size.ecc, 0);
+
+    for block in 0..size.blocks {
+        let block_data: Vec<u8> = data.iter().skip(block).step_by(size.blocks).copied().collect();
+        for (i, ecc) in reed_solomon(&block_data, ecc_length).into_iter().enumerate() {
+            codewords[size.data + block + i * size.blocks] = ecc;
+        }
+    }
+    codewords
+}
+
+/// Module placement in the mapping matrix (data regions without finder patterns)
+struct Placement {
+    rows: isize,
+    columns: isize,
+    /// Module: `None` if not yet placed, else dark or light
+    modules: Vec<Option<bool>>,
+}
+
+impl Placement {
+    /// Place a bit of a codeword
+    fn module(&mut self, mut row: isize, mut column: isize, codeword: u8, bit: u8) {
+        if row < 0 {
+            row += self.rows;
+            column += 4 - ((self.rows + 4) % 8);
+        }
+        if column < 0 {
+            column += self.columns;
+            row += 4 - ((self.columns + 4) % 8);
+        }
+        self.modules[(row * self.columns + column) as usize] = Some(codeword & (0x80 >> bit) != 0);
+    }
+
+    /// Is the module already placed?
+    fn is_placed(&self, row: isize, column: isize) -> bool {
+        self.modules[(row * self.columns + column) as usize].is_some()
+    }
+
+    /// Place the 8 bits of a codeword from a list of (row, column) positions, the most significant bit first
+    fn codeword(&mut self, positions: [(isize, isize); 8], codeword: u8) {
+        for (bit, (row, column)) in positions.into_iter().enumerate() {
+            self.module(row, column, codeword, bit as u8);
+        }
+    }
+
+    /// Standard codeword shape (the bottom right module is at `row`, `column`)
+    fn utah(&mut self, row: isize, column: isize, codeword: u8) {
+        let (r, c) = (row, column);
+        self.codeword(
+            [
+                (r - 2, c - 2),
+                (r - 2, c - 1),
+                (r - 1, c - 2),
+                (r - 1, c - 1),
+                (r - 1, c),
+                (r, c - 2),
+                (r, c - 1),
+                (r, c),
+            ],
+            codeword,
+        );
+    }
+
+    /// Special corner codeword shapes
+    fn corner(&mut self, corner: u8, codeword: u8) {
+        let (r, c) = (self.rows, self.columns);
+        let positions = match corner {
+            1 => [
+                (r - 1, 0),
+                (r - 1, 1),
+                (r - 1, 2),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 1),
+                (2, c - 1),
+                (3, c - 1),
+            ],
+            2 => [
+                (r - 3, 0),
+                (r - 2, 0),
+                (r - 1, 0),
+                (0, c - 4),
+                (0, c - 3),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 1),
+            ],
+            3 => [
+                (r - 3, 0),
+                (r - 2, 0),
+                (r - 1, 0),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 1),
+                (2, c - 1),
+                (3, c - 1),
+            ],
+            _ => [
+                (r - 1, 0),
+                (r - 1, c - 1),
+                (0, c - 3),
+                (0, c - 2),
+                (0, c - 1),
+                (1, c - 3),
+                (1, c - 2),
+                (1, c - 1),
+            ],
+        };
+        self.codeword(positions, codeword);
+    }
+}
+
+/// Place the codewords in the mapping matrix (ECC 200 diagonal placement)
+///
+/// Returns the modules row by row (`true` for a dark module).
+fn place(codewords: &[u8], rows: usize, columns: usize) -> Vec<bool> {
+    let mut placement = Placement {
+        rows: rows as isize,
+        columns: columns as isize,
+        modules: vec![None; rows * columns],
+    };
+    let (rows, columns) = (placement.rows, placement.columns);
+    let mut codewords = codewords.iter().copied();
+    let mut next = || codewords.next().unwrap_or_default();
+    let (mut row, mut column) = (4, 0);
+
+    loop {
+        // Corner cases
+        if row == rows && column == 0 {
+            placement.corner(1, next());
+        }
+        if row == rows - 2 && column == 0 && columns % 4 != 0 {
+            placement.corner(2, next());
+        }
+        if row == rows - 2 && column == 0 && columns % 8 == 4 {
+            placement.corner(3, next());
+        }
+        if row == rows + 4 && column == 2 && columns % 8 == 0 {
+            placement.corner(4, next());
+        }
+
+        // Sweep upward diagonally
+        loop {
+            if row < rows && column >= 0 && !placement.is_placed(row, column) {
+                placement.utah(row, column, next());
+            }
+            row -= 2;
+            column += 2;
+            if row < 0 || column >= columns {
+                break;
+            }
+        }
+        row += 1;
+        column += 3;
+
+        // Sweep downward diagonally
+        loop {
+            if row >= 0 && column < columns && !placement.is_placed(row, column) {
+                placement.utah(row, column, next());
+            }
+            row += 2;
+            column -= 2;
+            if row >= rows || column < 0 {
+                break;
+            }
+        }
+        row += 3;
+        column += 1;
+
+        if row >= rows && column >= columns {
+            break;
+        }
+    }
+
+    // Fixed pattern in the unused bottom right corner
+    let last = placement.modules.len() - 1;
+    if placement.modules[last].is_none() {
+        placement.modules[last] = Some(true);
+        placement.modules[last - 1] = Some(false);
+        placement.modules[last - placement.columns as usize] = Some(false);
+        placement.modules[last - placement.columns as usize - 1] = Some(true);
+    }
+
+    placement
+        .modules
+        .into_iter()
+        .map(|module| module.unwrap_or_default())
+        .collect()
+}
+
+/// Build the symbol from the mapping matrix: split it in data regions surrounded by finder patterns
+fn symbol(mapping: &[bool], size: &SymbolSize) -> Modules {
+    let mut modules = Modules::new(size.columns, size.rows);
+    let mapping_columns = size.columns - 2 * size.regions().0;
+    let (region_height, region_width) = (size.region_rows + 2, size.region_columns + 2);
+
+    for y in 0..size.rows {
+        for x in 0..size.columns {
+            let (region_y, region_x) = (y % region_height, x % region_width);
+            let dark = if region_x == 0 || region_y == region_height - 1 {
+                // Solid "L" finder pattern
+                true
+            } else if region_y == 0 {
+                // Alternating top timing pattern
+                region_x % 2 == 0
+            } else if region_x == region_width - 1 {
+                // Alternating right timing pattern
+                region_y % 2 == 1
+            } else {
+                let row = y / region_height * size.region_rows + region_y - 1;
+                let column = x / region_width * size.region_columns + region_x - 1;
+                mapping[row * mapping_columns + column]
+            };
+            modules.set(x, y, dark);
+        }
+    }
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_sizes() {
+        for size in SYMBOL_SIZES {
+            let (horizontal, vertical) = size.regions();
+            let mapping = size.rows * size.columns - 2 * (horizontal * size.rows + vertical * size.columns)
+                + 4 * horizontal * vertical;
+            assert_eq!(mapping, horizontal * size.region_columns * vertical * size.region_rows);
+            // Up to 4 modules are not used by the codewords (bottom right corner)
+            assert!(mapping - (size.data + size.ecc) * 8 < 8, "{size:?}");
+            assert_eq!(size.ecc % size.blocks, 0);
+        }
+    }
+
+    #[test]
+    fn test_ascii_encode() {
+        assert_eq!(ascii_encode(b"123456"), vec![142, 164, 186]);
+        assert_eq!(ascii_encode(b"A1b"), vec![66, 50, 99]);
+        assert_eq!(ascii_encode(&[0xE9]), vec![UPPER_SHIFT, 106]);
+    }
+
+    #[test]
+    fn test_pad() {
+        let mut codewords = vec![66];
+        pad(&mut codewords, 5);
+        assert_eq!(codewords, vec![66, PAD, 70, 220, 115]);
+    }
+
+    #[test]
+    fn test_reed_solomon() {
+        // "123456" in a 10 x 10 symbol
+        assert_eq!(reed_solomon(&[142, 164, 186], 5), vec![114, 25, 5, 88, 102]);
+    }
+
+    #[test]
+    fn test_place() {
+        // Every codeword bit is placed once: a codeword with only one bit set gives one dark module
+        for size in SYMBOL_SIZES {
+            let (rows, columns) = (size.rows - 2 * size.regions().1, size.columns - 2 * size.regions().0);
+            let count = size.data + size.ecc;
+            for bit in 0..8 {
+                let mapping = place(&vec![0x80 >> bit; count], rows, columns);
+                let corner = usize::from(rows * columns - count * 8 == 4);
+                assert_eq!(mapping.iter().filter(|&&dark| dark).count(), count + 2 * corner);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let modules = encode(b"123456", DataMatrixType::Square(0), false).unwrap();
+        assert_eq!((modules.width(), modules.height()), (10, 10));
+
+        // Finder patterns
+        assert!((0..10).all(|i| modules.get(0, i) && modules.get(i, 9)));
+        assert!((0..10).all(|i| modules.get(i, 0) == (i % 2 == 0)));
+        assert!((0..10).all(|i| modules.get(9, i) == (i % 2 == 1)));
+
+        let modules = encode(b"123456", DataMatrixType::Rectangle(8, 0), false).unwrap();
+        assert_eq!((modules.width(), modules.height()), (18, 8));
+
+        let modules = encode("A".repeat(100).as_bytes(), DataMatrixType::Square(0), false).unwrap();
+        assert_eq!((modules.width(), modules.height()), (40, 40));
+        // Finder patterns of the 2 x 2 data regions
+        assert!((0..40).all(|i| modules.get(20, i) && modules.get(i, 19)));
+
+        assert!(encode("A".repeat(100).as_bytes(), DataMatrixType::Square(10), false).is_err());
+        assert!(encode("A".repeat(2000).as_bytes(), DataMatrixType::Square(0), false).is_err());
+
+        // GS1: the FNC1 codeword does not fit in the 10 x 10 symbol (3 data codewords)
+        let modules = encode(b"123456", DataMatrixType::Square(0), true).unwrap();
+        assert_eq!((modules.width(), modules.height()), (12, 12));
+    }
+}
@@ -0,0 +1,163 @@
+//! Local rendering of barcodes and 2D codes
+//!
+//! The codes are generated in software and printed as raster bit images,
+//! for printers which do not support (or badly render) the barcode and `GS ( k` commands.
+
+#[cfg(feature = "codes_2d")]
+pub(crate) mod aztec;
+#[cfg(feature = "barcodes")]
+pub(crate) mod barcode;
+#[cfg(feature = "codes_2d")]
+pub(crate) mod data_matrix;
+#[cfg(feature = "codes_2d")]
+pub(crate) mod pdf417;
+#[cfg(feature = "codes_2d")]
+pub(crate) mod qrcode;
+
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+use crate::domain::{BitImageSize, RasterBitImage};
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+use crate::errors::{PrinterError, Result};
+#[cfg(all(not(feature = "std"), any(feature = "barcodes", feature = "codes_2d")))]
+use alloc::{format, vec, vec::Vec};
+use core::fmt;
+
+/// Code rendering
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CodeRendering {
+    /// The code is generated by the printer firmware
+    #[default]
+    Printer,
+    /// The code is generated in software and printed as a raster bit image
+    Image,
+}
+
+impl fmt::Display for CodeRendering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeRendering::Printer => write!(f, "Printer"),
+            CodeRendering::Image => write!(f, "Image"),
+        }
+    }
+}
+
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+/// Modules of a code (`true` for a dark module)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Modules {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+}
+
+#[cfg(any(feature = "barcodes", feature = "codes_2d"))]
+impl Modules {
+    #[cfg(feature = "codes_2d")]
+    /// Create light modules
+    pub(crate) fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            dark: vec![false; width * height],
+        }
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Create a single row of modules (1D barcode)
+    pub(crate) fn from_row(row: Vec<bool>) -> Self {
+        Self {
+            width: row.len(),
+            height: 1,
+            dark: row,
+        }
+    }
+
+    #[cfg(all(test, feature = "codes_2d"))]
+    /// Get the width in modules
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    #[cfg(all(test, feature = "codes_2d"))]
+    /// Get the height in modules
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Is the module dark?
+    pub(crate) fn get(&self, x: usize, y: usize) -> bool {
+        self.dark[y * self.width + x]
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Set a module
+    pub(crate) fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.dark[y * self.width + x] = dark;
+    }
+
+    /// Render the modules as a raster bit image
+    ///
+    /// - `quiet_zone`: number of light modules added around the code (horizontally, vertically)
+    /// - `module_size`: size of a module in dots (width, height)
+    pub(crate) fn raster(&self, quiet_zone: (usize, usize), module_size: (u16, u16)) -> Result<RasterBitImage> {
+        let (module_width, module_height) = (usize::from(module_size.0), usize::from(module_size.1));
+        if module_width == 0 || module_height == 0 {
+            return Err(PrinterError::Input(format!(
+                "invalid code module size: {} x {}",
+                module_size.0, module_size.1
+            )));
+        }
+
+        let width = (self.width + 2 * quiet_zone.0) * module_width;
+        let height = (self.height + 2 * quiet_zone.1) * module_height;
+        let width =
+            u16::try_from(width).map_err(|_| PrinterError::Input(format!("code image is too wide: {width} dots")))?;
+        let height =
+            u32::try_from(height).map_err(|_| PrinterError::Input(format!("code image is too high: {height} dots")))?;
+
+        let width_bytes = usize::from(width.div_ceil(8));
+        let mut line = vec![0; width_bytes];
+        let mut data = Vec::with_capacity(width_bytes * height as usize);
+
+        data.resize(width_bytes * quiet_zone.1 * module_height, 0);
+        for y in 0..self.height {
+            line.fill(0);
+            for x in (0..self.width).filter(|&x| self.get(x, y)) {
+                let start = (quiet_zone.0 + x) * module_width;
+                for dot in start..start + module_width {
+                    line[dot / 8] |= 0x80 >> (dot % 8);
+                }
+            }
+            for _ in 0..module_height {
+                data.extend_from_slice(&line);
+            }
+        }
+        data.resize(width_bytes * height as usize, 0);
+
+        RasterBitImage::new(width, height, data, BitImageSize::Normal)
+    }
+}
+
+#[cfg(all(test, feature = "barcodes", feature = "codes_2d"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modules_raster() {
+        // Diagonal
+        let mut modules = Modules::new(2, 2);
+        modules.set(0, 0, true);
+        modules.set(1, 1, true);
+
+        let raster = modules.raster((1, 0), (2, 1)).unwrap();
+        assert_eq!((raster.width(), raster.height()), (8, 2));
+        assert_eq!(raster.data(), &[0b0011_0000, 0b0000_1100]);
+
+        let raster = modules.raster((0, 1), (1, 2)).unwrap();
+        assert_eq!((raster.width(), raster.height()), (2, 8));
+        assert_eq!(raster.data(), &[0, 0, 0x80, 0x80, 0x40, 0x40, 0, 0]);
+
+        assert!(modules.raster((0, 0), (0, 1)).is_err());
+        assert!(Modules::from_row(vec![true; 20_000]).raster((0, 0), (4, 1)).is_err());
+    }
+}
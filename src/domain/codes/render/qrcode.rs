@@ -0,0 +1,316 @@
+//! QR code rendering
+
+use super::Modules;
+use crate::domain::{QRCodeCorrectionLevel, QRCodeModel, QRCodeOption, RasterBitImage};
+use crate::errors::{PrinterError, Result};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, format, vec, vec::Vec};
+use qrcodegen::{BitBuffer, QrCode, QrCodeEcc, QrSegment, QrSegmentMode, Version};
+
+/// QR code quiet zone (in modules)
+const QUIET_ZONE: usize = 4;
+
+/// FNC1 in first position mode indicator (GS1 QR codes)
+const FNC1_FIRST_POSITION: u32 = 0b0101;
+
+/// Number of error correction codewords per block by error correction level (L, M, Q, H) and version
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 40]; 4] = [
+    [
+        7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28,
+        28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+    ],
+    [
+        13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+    [
+        17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30,
+        30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+    ],
+];
+
+/// Number of error correction blocks by error correction level (L, M, Q, H) and version
+const ERROR_CORRECTION_BLOCKS: [[u8; 40]; 4] = [
+    [
+        1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18,
+        19, 19, 20, 21, 22, 24, 25,
+    ],
+    [
+        1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31,
+        33, 35, 37, 38, 40, 43, 45, 47, 49,
+    ],
+    [
+        1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40,
+        43, 45, 48, 51, 53, 56, 59, 62, 65, 68,
+    ],
+    [
+        1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48,
+        51, 54, 57, 60, 63, 66, 70, 74, 77, 81,
+    ],
+];
+
+impl From<QRCodeCorrectionLevel> for QrCodeEcc {
+    fn from(value: QRCodeCorrectionLevel) -> Self {
+        match value {
+            QRCodeCorrectionLevel::L => QrCodeEcc::Low,
+            QRCodeCorrectionLevel::M => QrCodeEcc::Medium,
+            QRCodeCorrectionLevel::Q => QrCodeEcc::Quartile,
+            QRCodeCorrectionLevel::H => QrCodeEcc::High,
+        }
+    }
+}
+
+/// Render a QR code
+pub(crate) fn render(data: &[u8], option: &QRCodeOption) -> Result<RasterBitImage> {
+    let modules = encode(data, option.model(), option.correction_level(), option.is_gs1())?;
+    let size = u16::from(option.size());
+    modules.raster((QUIET_ZONE, QUIET_ZONE), (size, size))
+}
+
+/// Encode data into a QR code symbol
+///
+/// Only model 2 symbols are generated: model 1 requests are rendered as model 2 symbols
+/// (the data of a model 1 symbol always fits a model 2 one) and Micro QR codes are not supported.
+/// UTF-8 data is split in numeric, alphanumeric and byte segments, other data is encoded in a byte segment.
+/// GS1 data starts with the FNC1 in first position mode indicator.
+pub(crate) fn encode(
+    data: &[u8],
+    model: QRCodeModel,
+    correction_level: QRCodeCorrectionLevel,
+    gs1: bool,
+) -> Result<Modules> {
+    match model {
+        QRCodeModel::Micro => {
+            return Err(PrinterError::Input(
+                "Micro QR codes can not be rendered as images".to_owned(),
+            ));
+        }
+        QRCodeModel::Model1 | QRCodeModel::Model2 => (),
+    }
+
+    let qrcode = if gs1 {
+        encode_gs1(data, correction_level.into())?
+    } else {
+        let segments = match core::str::from_utf8(data) {
+            Ok(text) => QrSegment::make_segments(text),
+            Err(_) => vec![QrSegment::make_bytes(data)],
+        };
+        QrCode::encode_segments_advanced(
+            &segments,
+            correction_level.into(),
+            Version::MIN,
+            Version::MAX,
+            None,
+            false,
+        )
+        .map_err(|err| PrinterError::Input(format!("QR code data is too long: {err}")))?
+    };
+
+    let size = qrcode.size() as usize;
+    let mut modules = Modules::new(size, size);
+    for y in 0..size {
+        for x in 0..size {
+            modules.set(x, y, qrcode.get_module(x as i32, y as i32));
+        }
+    }
+    Ok(modules)
+}
+
+/// Encode GS1 data (element string with GS separators) in the smallest QR code
+///
+/// `qrcodegen` has no FNC1 mode: the data codewords are built here and only the error correction and the
+/// placement are done by `qrcodegen`. In the alphanumeric mode, `%` is escaped as `%%` (`%` alone is FNC1).
+fn encode_gs1(data: &[u8], ecc: QrCodeEcc) -> Result<QrCode> {
+    let text = core::str::from_utf8(data)
+        .map_err(|_| PrinterError::Input("GS1 QR code data must be an element string".to_owned()))?;
+    let segment = if QrSegment::is_numeric(text) {
+        QrSegment::make_numeric(text)
+    } else if QrSegment::is_alphanumeric(text) {
+        QrSegment::make_alphanumeric(&text.replace('%', "%%"))
+    } else {
+        QrSegment::make_bytes(data)
+    };
+
+    let (version, capacity) = (1..=40)
+        .map(|version| (version, data_codewords(version, ecc) * 8))
+        .find(|&(version, capacity)| {
+            let count_bits = char_count_bits(segment.mode(), version);
+            segment.num_chars() < 1 << count_bits && 4 + 4 + usize::from(count_bits) + segment.data().len() <= capacity
+        })
+        .ok_or_else(|| PrinterError::Input(format!("QR code data is too long: {} bits", segment.data().len())))?;
+
+    let mut bits = BitBuffer(Vec::new());
+    bits.append_bits(FNC1_FIRST_POSITION, 4);
+    bits.append_bits(mode_indicator(segment.mode()), 4);
+    bits.append_bits(segment.num_chars() as u32, char_count_bits(segment.mode(), version));
+    bits.0.extend_from_slice(segment.data());
+
+    // Terminator, padding to a byte and pad codewords
+    bits.append_bits(0, (capacity - bits.0.len()).min(4) as u8);
+    bits.append_bits(0, ((8 - bits.0.len() % 8) % 8) as u8);
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if bits.0.len() >= capacity {
+            break;
+        }
+        bits.append_bits(pad, 8);
+    }
+
+    let codewords: Vec<u8> = bits
+        .0
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |codeword, &bit| codeword << 1 | u8::from(bit)))
+        .collect();
+    Ok(QrCode::encode_codewords(Version::new(version), ecc, &codewords, None))
+}
+
+/// Mode indicator of a segment
+fn mode_indicator(mode: QrSegmentMode) -> u32 {
+    match mode {
+        QrSegmentMode::Numeric => 0b0001,
+        QrSegmentMode::Alphanumeric => 0b0010,
+        QrSegmentMode::Byte => 0b0100,
+        QrSegmentMode::Kanji => 0b1000,
+        QrSegmentMode::Eci => 0b0111,
+    }
+}
+
+/// Number of bits of the character count of a segment
+fn char_count_bits(mode: QrSegmentMode, version: u8) -> u8 {
+    let bits = match mode {
+        QrSegmentMode::Numeric => [10, 12, 14],
+        QrSegmentMode::Alphanumeric => [9, 11, 13],
+        QrSegmentMode::Byte => [8, 16, 16],
+        QrSegmentMode::Kanji => [8, 10, 12],
+        QrSegmentMode::Eci => [0, 0, 0],
+    };
+    bits[usize::from((version + 7) / 17)]
+}
+
+/// Number of data codewords of a QR code
+fn data_codewords(version: u8, ecc: QrCodeEcc) -> usize {
+    let level = match ecc {
+        QrCodeEcc::Low => 0,
+        QrCodeEcc::Medium => 1,
+        QrCodeEcc::Quartile => 2,
+        QrCodeEcc::High => 3,
+    };
+    let index = usize::from(version - 1);
+    let version = usize::from(version);
+
+    // Modules of the data and error correction codewords (without the function patterns)
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        modules -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+
+    modules / 8
+        - usize::from(ECC_CODEWORDS_PER_BLOCK[level][index]) * usize::from(ERROR_CORRECTION_BLOCKS[level][index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        // Version 1: 21 x 21 modules
+        let modules = encode(b"Hello", QRCodeModel::Model2, QRCodeCorrectionLevel::M, false).unwrap();
+        assert_eq!((modules.width(), modules.height()), (21, 21));
+        // Finder patterns
+        assert!((0..7).all(|i| modules.get(i, 0) && modules.get(0, i) && modules.get(20 - i, 6)));
+        assert!(!modules.get(7, 0));
+        // Binary data
+        let modules = encode(
+            &[0x00, 0xFF, 0xFE],
+            QRCodeModel::Model2,
+            QRCodeCorrectionLevel::M,
+            false,
+        )
+        .unwrap();
+        assert_eq!(modules.width(), 21);
+
+        // The correction level is not boosted
+        let modules = encode(
+            "1".repeat(40).as_bytes(),
+            QRCodeModel::Model2,
+            QRCodeCorrectionLevel::L,
+            false,
+        )
+        .unwrap();
+        assert_eq!(modules.width(), 21);
+        let modules = encode(
+            "1".repeat(40).as_bytes(),
+            QRCodeModel::Model2,
+            QRCodeCorrectionLevel::H,
+            false,
+        )
+        .unwrap();
+        assert_eq!(modules.width(), 29);
+
+        assert_eq!(
+            encode(b"Hello", QRCodeModel::Model1, QRCodeCorrectionLevel::M, false).unwrap(),
+            encode(b"Hello", QRCodeModel::Model2, QRCodeCorrectionLevel::M, false).unwrap()
+        );
+        assert!(encode(b"Hello", QRCodeModel::Micro, QRCodeCorrectionLevel::M, false).is_err());
+        assert!(
+            encode(
+                "a".repeat(3000).as_bytes(),
+                QRCodeModel::Model2,
+                QRCodeCorrectionLevel::H,
+                false
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_data_codewords() {
+        assert_eq!(data_codewords(1, QrCodeEcc::Low), 19);
+        assert_eq!(data_codewords(1, QrCodeEcc::High), 9);
+        assert_eq!(data_codewords(10, QrCodeEcc::Medium), 216);
+        assert_eq!(data_codewords(40, QrCodeEcc::Low), 2956);
+        assert_eq!(data_codewords(40, QrCodeEcc::High), 1276);
+    }
+
+    #[test]
+    fn test_encode_gs1() {
+        // FNC1 in first position, numeric mode, 16 digits
+        let data = b"0109501101530003";
+        let gs1 = encode_gs1(data, QrCodeEcc::Medium).unwrap();
+        let mut bits = BitBuffer(Vec::new());
+        bits.append_bits(FNC1_FIRST_POSITION, 4);
+        bits.append_bits(0b0001, 4);
+        bits.append_bits(16, 10);
+        bits.0
+            .extend_from_slice(QrSegment::make_numeric("0109501101530003").data());
+        bits.append_bits(0, 4);
+        bits.append_bits(0, ((8 - bits.0.len() % 8) % 8) as u8);
+        let mut codewords: Vec<u8> = bits
+            .0
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |codeword, &bit| codeword << 1 | u8::from(bit)))
+            .collect();
+        codewords.extend([0xEC, 0x11].into_iter().cycle().take(16 - codewords.len()));
+        let expected = QrCode::encode_codewords(Version::new(1), QrCodeEcc::Medium, &codewords, None);
+        assert!(gs1 == expected);
+
+        // The GS separators are encoded in a byte segment
+        let modules = encode(
+            b"0109501101530003\x1d10AB-123",
+            QRCodeModel::Model2,
+            QRCodeCorrectionLevel::M,
+            true,
+        )
+        .unwrap();
+        assert_eq!(modules.width(), 25);
+        assert!(encode(&[0xFF], QRCodeModel::Model2, QRCodeCorrectionLevel::M, true).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Size information of the 2D code symbols
+//!
+//! The printer sends the size of the symbol built from the data stored in the symbol storage area
+//! (`GS ( k` function 082 of each symbology).
+//!
+//! [Epson Documentation](https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_lparen_lk.html)
+
+#![cfg(feature = "codes_2d")]
+
+use crate::domain::common::{get_response_data, parse_decimal};
+use crate::errors::{PrinterError, Result};
+use alloc::format;
+
+/// Response header of the symbol size information
+const SYMBOL_SIZE_RESPONSE_HEADER: u8 = 0x37;
+/// Response identifier of the symbol size information
+const SYMBOL_SIZE_RESPONSE_IDENTIFIER: u8 = 0x76;
+/// Separator of the symbol size information fields
+const SYMBOL_SIZE_SEPARATOR: u8 = 0x1F;
+
+/// Size of a 2D code symbol sent by the printer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeSymbolSize {
+    width: u32,
+    height: u32,
+    printable: bool,
+}
+
+impl CodeSymbolSize {
+    /// Parse a symbol size information response
+    ///
+    /// The response is `Header` `Identifier` width `1F` height `1F` printable (`0`) or not (`1`) `NUL`.
+    pub(crate) fn parse(response: &[u8]) -> Result<Self> {
+        let data = get_response_data(response, SYMBOL_SIZE_RESPONSE_HEADER, SYMBOL_SIZE_RESPONSE_IDENTIFIER)?;
+        let mut fields = data.split(|&c| c == SYMBOL_SIZE_SEPARATOR);
+
+        match (fields.next(), fields.next(), fields.next(), fields.next()) {
+            (Some(width), Some(height), Some(printable @ (b"0" | b"1")), None) => Ok(Self {
+                width: parse_decimal(width)?,
+                height: parse_decimal(height)?,
+                printable: printable == b"0",
+            }),
+            _ => Err(PrinterError::InvalidResponse(format!(
+                "invalid symbol size information: {response:02X?}"
+            ))),
+        }
+    }
+
+    /// Get the width (in dots)
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height (in dots)
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Does the symbol fit the print area?
+    pub fn fits(&self) -> bool {
+        self.printable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_symbol_size_parse() {
+        let size = CodeSymbolSize::parse(b"\x37\x76168\x1F168\x1F0\x00").unwrap();
+        assert_eq!((size.width(), size.height(), size.fits()), (168, 168, true));
+
+        let size = CodeSymbolSize::parse(b"\x37\x76640\x1F96\x1F1\x00").unwrap();
+        assert_eq!((size.width(), size.height(), size.fits()), (640, 96, false));
+
+        assert!(CodeSymbolSize::parse(b"\x37\x76168\x1F168\x00").is_err());
+        assert!(CodeSymbolSize::parse(b"\x37\x76168\x1F168\x1F2\x00").is_err());
+        assert!(CodeSymbolSize::parse(b"\x37\x76168\x1F168\x1F0\x1F0\x00").is_err());
+        assert!(CodeSymbolSize::parse(b"\x37\x30168\x1F168\x1F0\x00").is_err());
+    }
+}
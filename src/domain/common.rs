@@ -1,23 +1,54 @@
 //! Common functions
 
+#[cfg(any(feature = "graphics", feature = "codes_2d"))]
+use super::constants::NUL;
 use crate::errors::{PrinterError, Result};
 use alloc::format;
 
 /// Get parameters pL and pH
 #[cfg(feature = "codes_2d")]
-pub(crate) fn get_parameters_number_2(data: &str, padding: u8) -> Result<(u8, u8)> {
-    let bytes = data.as_bytes();
-    let data_len = bytes.len() + (padding as usize);
+pub(crate) fn get_parameters_number_2(data: &[u8], padding: u8) -> Result<(u8, u8)> {
+    let data_len = data.len() + (padding as usize);
     let ph = data_len / 256;
     let pl = data_len
         .checked_add_signed(-256 * isize::try_from(ph)?)
         .ok_or(PrinterError::Input(format!(
-            "invalid parameter numbers (pL, pH) for data: {data}"
+            "invalid parameter numbers (pL, pH) for data of {} bytes",
+            data.len()
         )))?;
 
     Ok((u8::try_from(pl)?, u8::try_from(ph)?))
 }
 
+/// Get the data of a printer response block
+///
+/// Responses to `GS ( L`, `GS ( k`, etc. are formatted as `Header` `Identifier` `data` `NUL`.
+#[cfg(any(feature = "graphics", feature = "codes_2d"))]
+pub(crate) fn get_response_data(response: &[u8], header: u8, identifier: u8) -> Result<&[u8]> {
+    match response {
+        [h, i, data @ .., NUL] if *h == header && *i == identifier => Ok(data),
+        _ => Err(PrinterError::InvalidResponse(format!(
+            "invalid response block (header: {header:#04X}, identifier: {identifier:#04X}): {response:02X?}"
+        ))),
+    }
+}
+
+/// Parse a decimal number sent as ASCII digits by the printer
+#[cfg(any(feature = "graphics", feature = "codes_2d"))]
+pub(crate) fn parse_decimal(data: &[u8]) -> Result<u32> {
+    if data.is_empty() || !data.iter().all(u8::is_ascii_digit) {
+        return Err(PrinterError::InvalidResponse(format!(
+            "invalid decimal number in response: {data:02X?}"
+        )));
+    }
+
+    data.iter().try_fold(0u32, |acc, digit| {
+        acc.checked_mul(10)
+            .and_then(|acc| acc.checked_add(u32::from(digit - b'0')))
+            .ok_or_else(|| PrinterError::InvalidResponse(format!("number too large in response: {data:02X?}")))
+    })
+}
+
 /// Get the number of characters
 ///
 /// # Examples
@@ -38,21 +69,48 @@ pub fn chars_number(width: u8, size: u8) -> Result<u8> {
     Ok(width / size)
 }
 
-#[cfg(all(test, feature = "codes_2d"))]
+#[cfg(all(test, any(feature = "codes_2d", feature = "graphics")))]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "codes_2d")]
     #[test]
     fn test_get_parameters_number_2() {
-        assert_eq!(get_parameters_number_2("test123456", 3).unwrap(), (13, 0));
+        assert_eq!(get_parameters_number_2(b"test123456", 3).unwrap(), (13, 0));
         assert_eq!(
-            get_parameters_number_2("test123456".repeat(200).as_str(), 4).unwrap(),
+            get_parameters_number_2("test123456".repeat(200).as_bytes(), 4).unwrap(),
             (212, 7)
         );
         assert_eq!(
-            get_parameters_number_2("1".repeat(65_531).as_str(), 4).unwrap(),
+            get_parameters_number_2("1".repeat(65_531).as_bytes(), 4).unwrap(),
             (255, 255)
         );
-        assert!(get_parameters_number_2("1".repeat(65_600).as_str(), 4).is_err());
+        assert!(get_parameters_number_2("1".repeat(65_600).as_bytes(), 4).is_err());
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_get_response_data() {
+        assert_eq!(
+            get_response_data(&[0x37, 0x30, 0x31, 0x32, 0x00], 0x37, 0x30).unwrap(),
+            &[0x31, 0x32]
+        );
+        assert_eq!(
+            get_response_data(&[0x37, 0x30, 0x00], 0x37, 0x30).unwrap(),
+            &[] as &[u8]
+        );
+        assert!(get_response_data(&[0x37, 0x31, 0x31, 0x00], 0x37, 0x30).is_err());
+        assert!(get_response_data(&[0x37, 0x30, 0x31], 0x37, 0x30).is_err());
+        assert!(get_response_data(&[0x37], 0x37, 0x30).is_err());
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal(b"0").unwrap(), 0);
+        assert_eq!(parse_decimal(b"262144").unwrap(), 262_144);
+        assert!(parse_decimal(b"").is_err());
+        assert!(parse_decimal(b"12a").is_err());
+        assert!(parse_decimal(b"99999999999").is_err());
     }
 }
@@ -10,6 +10,7 @@ pub const DLE: u8 = 0x10; // Data link escape
 pub const ESC: u8 = 0x1B;
 pub const GS: u8 = 0x1D; // Group separator
 pub const CAN: u8 = 0x18; // Cancel
+pub const ACK: u8 = 0x06; // Acknowledge
 
 // Hardware
 pub const ESC_HARDWARE_INIT: &[u8] = &[ESC, b'@'];
@@ -67,6 +68,7 @@ pub const ESC_TEXT_UPSIDE_DOWN_ON: &[u8] = &[ESC, b'{', 1];
 
 // Printer Status
 pub const DLE_REAL_TIME_STATUS: &[u8] = &[DLE, EOT];
+pub const GS_TRANSMIT_PAPER_SENSOR_STATUS: &[u8] = &[GS, b'r', 1];
 
 // Barcodes
 #[cfg(feature = "barcodes")]
@@ -91,6 +93,8 @@ pub const GS_2D_QRCODE_SIZE: &[u8] = &[GS, b'(', b'k', 3, 0, 49, 67];
 pub const GS_2D_QRCODE_CORRECTION_LEVEL: &[u8] = &[GS, b'(', b'k', 3, 0, 49, 69];
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_QRCODE_PRINT_SYMBOL_DATA: &[u8] = &[GS, b'(', b'k', 3, 0, 49, 81, 48];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_QRCODE_SIZE_INFO: &[u8] = &[GS, b'(', b'k', 3, 0, 49, 82, 48];
 
 // GS1 DataBar
 #[cfg(feature = "codes_2d")]
@@ -99,6 +103,8 @@ pub const GS_2D_GS1_DATABAR_WIDTH: &[u8] = &[GS, b'(', b'k', 3, 0, 51, 67];
 pub const GS_2D_GS1_DATABAR_WIDTH_EXTENDED: &[u8] = &[GS, b'(', b'k', 3, 0, 51, 71];
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_GS1_DATABAR_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 51, 81, 48];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_GS1_DATABAR_SIZE_INFO: &[u8] = &[GS, b'(', b'k', 3, 0, 51, 82, 48];
 
 // PDF417
 #[cfg(feature = "codes_2d")]
@@ -115,12 +121,16 @@ pub const GS_2D_PDF417_CORRECTION_LEVEL: &[u8] = &[GS, b'(', b'k', 3, 0, 48, 69]
 pub const GS_2D_PDF417_TYPE: &[u8] = &[GS, b'(', b'k', 3, 0, 48, 70];
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_PDF417_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 48, 81, 48];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_PDF417_SIZE_INFO: &[u8] = &[GS, b'(', b'k', 3, 0, 48, 82, 48];
 
 // MaxiCode
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_MAXI_CODE_MODE: &[u8] = &[GS, b'(', b'k', 3, 0, 50, 65];
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_MAXI_CODE_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 50, 81, 48];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_MAXI_CODE_SIZE_INFO: &[u8] = &[GS, b'(', b'k', 3, 0, 50, 82, 48];
 
 // DataMatrix
 #[cfg(feature = "codes_2d")]
@@ -129,6 +139,8 @@ pub const GS_2D_DATA_MATRIX_TYPE: &[u8] = &[GS, b'(', b'k', 5, 0, 54, 66];
 pub const GS_2D_DATA_MATRIX_SIZE: &[u8] = &[GS, b'(', b'k', 3, 0, 54, 67];
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_DATA_MATRIX_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 54, 81, 48];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_DATA_MATRIX_SIZE_INFO: &[u8] = &[GS, b'(', b'k', 3, 0, 54, 82, 48];
 
 // Aztec code
 #[cfg(feature = "codes_2d")]
@@ -139,10 +151,12 @@ pub const GS_2D_AZTEC_CODE_SIZE: &[u8] = &[GS, b'(', b'k', 3, 0, 53, 67];
 pub const GS_2D_AZTEC_CODE_CORRECTION_LEVEL: &[u8] = &[GS, b'(', b'k', 4, 0, 53, 69];
 #[cfg(feature = "codes_2d")]
 pub const GS_2D_AZTEC_CODE_PRINT: &[u8] = &[GS, b'(', b'k', 3, 0, 53, 81, 48];
+#[cfg(feature = "codes_2d")]
+pub const GS_2D_AZTEC_CODE_SIZE_INFO: &[u8] = &[GS, b'(', b'k', 3, 0, 53, 82, 48];
 
 // Image
-#[cfg(feature = "graphics")]
 pub const GS_IMAGE_BITMAP_PREFIX: &[u8] = &[GS, b'v', b'0'];
+pub const ESC_IMAGE_COLUMN_PREFIX: &[u8] = &[ESC, b'*'];
 #[cfg(feature = "graphics")]
 pub const GS_IMAGE_LOW_PREFIX: &[u8] = &[GS, b'(', b'L'];
 #[cfg(feature = "graphics")]
@@ -152,5 +166,19 @@ pub const GS_IMAGE_DENSITY: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 49];
 #[cfg(feature = "graphics")]
 pub const GS_IMAGE_PRINT: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 50];
 
+// NV graphics
+#[cfg(feature = "graphics")]
+pub const GS_IMAGE_NV_CAPACITY: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 48];
+#[cfg(feature = "graphics")]
+pub const GS_IMAGE_NV_REMAINING_CAPACITY: &[u8] = &[GS, b'(', b'L', 2, 0, 48, 51];
+#[cfg(feature = "graphics")]
+pub const GS_IMAGE_NV_KEY_CODES: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 64, b'K', b'C'];
+#[cfg(feature = "graphics")]
+pub const GS_IMAGE_NV_DELETE_ALL: &[u8] = &[GS, b'(', b'L', 5, 0, 48, 65, b'C', b'L', b'R'];
+#[cfg(feature = "graphics")]
+pub const GS_IMAGE_NV_DELETE: &[u8] = &[GS, b'(', b'L', 4, 0, 48, 66];
+#[cfg(feature = "graphics")]
+pub const GS_IMAGE_NV_PRINT: &[u8] = &[GS, b'(', b'L', 6, 0, 48, 69];
+
 // Others
 pub const GS_SET_MOTION_UNITS: &[u8] = &[GS, b'P'];
@@ -0,0 +1,224 @@
+//! Dithering
+//!
+//! Conversion of grayscale images to the few tones a printer can print.
+
+#![cfg(feature = "graphics")]
+
+use crate::errors::{PrinterError, Result};
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+use core::fmt;
+
+/// 8x8 Bayer matrix used by the ordered dithering
+const BAYER_MATRIX: [[u8; 8]; 8] = [
+    [0, 32, 8, 40, 2, 34, 10, 42],
+    [48, 16, 56, 24, 50, 18, 58, 26],
+    [12, 44, 4, 36, 14, 46, 6, 38],
+    [60, 28, 52, 20, 62, 30, 54, 22],
+    [3, 35, 11, 43, 1, 33, 9, 41],
+    [51, 19, 59, 27, 49, 17, 57, 25],
+    [15, 47, 7, 39, 13, 45, 5, 37],
+    [63, 31, 55, 23, 61, 29, 53, 21],
+];
+
+/// Floyd-Steinberg error diffusion: (dx, dy, weight), weights divided by 16
+const FLOYD_STEINBERG_DIFFUSION: &[(isize, usize, i32)] = &[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)];
+/// Atkinson error diffusion: (dx, dy, weight), weights divided by 8 (only 3/4 of the error is diffused)
+const ATKINSON_DIFFUSION: &[(isize, usize, i32)] = &[(1, 0, 1), (2, 0, 1), (-1, 1, 1), (0, 1, 1), (1, 1, 1), (0, 2, 1)];
+
+/// Dithering algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dithering {
+    /// Fixed threshold: a pixel is printed if its luma is lower than or equal to the threshold
+    ///
+    /// With multiple tones, each pixel is rounded to the nearest tone and the threshold is not used.
+    Threshold(u8),
+    /// Floyd-Steinberg error diffusion
+    FloydSteinberg,
+    /// Atkinson error diffusion (lighter result with more contrast than Floyd-Steinberg)
+    Atkinson,
+    /// Ordered dithering with an 8x8 Bayer matrix
+    Bayer,
+}
+
+impl Default for Dithering {
+    fn default() -> Self {
+        Self::Threshold(128)
+    }
+}
+
+impl fmt::Display for Dithering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dithering::Threshold(threshold) => write!(f, "Threshold ({threshold})"),
+            Dithering::FloydSteinberg => write!(f, "Floyd-Steinberg"),
+            Dithering::Atkinson => write!(f, "Atkinson"),
+            Dithering::Bayer => write!(f, "Bayer"),
+        }
+    }
+}
+
+/// Check a gamma value
+pub(crate) fn check_gamma(gamma: f32) -> Result<f32> {
+    if !gamma.is_finite() || gamma <= 0.0 {
+        return Err(PrinterError::Input("gamma must be a positive number".to_owned()));
+    }
+
+    Ok(gamma)
+}
+
+/// Adjust the luma of the pixels before the dithering
+///
+/// - `auto_contrast`: stretch the luma so that the darkest pixel is black and the lightest one is white
+/// - `gamma`: gamma correction (`luma = 255 * (luma / 255) ^ (1 / gamma)`),
+///   a value greater than 1 lightens the image
+pub(crate) fn adjust(luma: &mut [u8], auto_contrast: bool, gamma: Option<f32>) {
+    if auto_contrast
+        && let (Some(&min), Some(&max)) = (luma.iter().min(), luma.iter().max())
+        && max > min
+    {
+        let (min, range) = (u32::from(min), u32::from(max - min));
+        for value in luma.iter_mut() {
+            *value = ((u32::from(*value) - min) * 255 / range) as u8;
+        }
+    }
+
+    if let Some(gamma) = gamma {
+        let table: Vec<u8> = (0..=255u8)
+            .map(|value| ((f32::from(value) / 255.0).powf(1.0 / gamma) * 255.0).round() as u8)
+            .collect();
+        for value in luma.iter_mut() {
+            *value = table[usize::from(*value)];
+        }
+    }
+}
+
+/// Dither a grayscale image (one luma byte per pixel, row by row)
+///
+/// Returns the tone of each pixel, from `0` (white) to `tones - 1` (black).
+pub(crate) fn dither(luma: &[u8], width: usize, height: usize, dithering: Dithering, tones: u8) -> Vec<u8> {
+    debug_assert_eq!(luma.len(), width * height);
+    debug_assert!(tones >= 2);
+
+    let max_tone = i32::from(tones - 1);
+    // Darkness from 0 (white) to 255 * max_tone (black), scaled to avoid rounding errors
+    let darkness = |value: u8| i32::from(255 - value) * max_tone;
+    let nearest = |value: i32| ((value + 127) / 255).clamp(0, max_tone);
+
+    match dithering {
+        Dithering::Threshold(threshold) if tones == 2 => {
+            luma.iter().map(|&value| u8::from(value <= threshold)).collect()
+        }
+        Dithering::Threshold(_) => luma.iter().map(|&value| nearest(darkness(value)) as u8).collect(),
+        Dithering::Bayer => luma
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let (x, y) = (i % width, i / width);
+                // Threshold between 0 and 255, centered in each matrix cell
+                let threshold = (i32::from(BAYER_MATRIX[y % 8][x % 8]) * 2 + 1) * 255 / 128;
+                ((darkness(value) + 255 - threshold) / 255).clamp(0, max_tone) as u8
+            })
+            .collect(),
+        Dithering::FloydSteinberg => diffuse(luma, width, height, tones, FLOYD_STEINBERG_DIFFUSION, 16),
+        Dithering::Atkinson => diffuse(luma, width, height, tones, ATKINSON_DIFFUSION, 8),
+    }
+}
+
+/// Error diffusion dithering
+fn diffuse(
+    luma: &[u8],
+    width: usize,
+    height: usize,
+    tones: u8,
+    diffusion: &[(isize, usize, i32)],
+    divisor: i32,
+) -> Vec<u8> {
+    let max_tone = i32::from(tones - 1);
+    let mut values: Vec<i32> = luma.iter().map(|&value| i32::from(255 - value) * max_tone).collect();
+    let mut result = vec![0; luma.len()];
+
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            let tone = ((values[i] + 127) / 255).clamp(0, max_tone);
+            let error = values[i] - tone * 255;
+            result[i] = tone as u8;
+
+            for &(dx, dy, weight) in diffusion {
+                if let Some(nx) = x.checked_add_signed(dx)
+                    && nx < width
+                    && y + dy < height
+                {
+                    values[(y + dy) * width + nx] += error * weight / divisor;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of printed dots of a black and white dithering
+    fn dots(tones: &[u8]) -> usize {
+        tones.iter().filter(|&&tone| tone == 1).count()
+    }
+
+    #[test]
+    fn test_dither_threshold() {
+        let luma = [0, 100, 128, 129, 255];
+        assert_eq!(dither(&luma, 5, 1, Dithering::default(), 2), vec![1, 1, 1, 0, 0]);
+        assert_eq!(dither(&luma, 5, 1, Dithering::Threshold(99), 2), vec![1, 0, 0, 0, 0]);
+        assert_eq!(dither(&luma, 5, 1, Dithering::default(), 4), vec![3, 2, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_dither_uniform_gray() {
+        let luma = [128; 64 * 64];
+        for dithering in [Dithering::FloydSteinberg, Dithering::Bayer] {
+            let count = dots(&dither(&luma, 64, 64, dithering, 2));
+            assert!((2_000..=2_100).contains(&count), "{dithering}: {count}");
+        }
+
+        // Atkinson only diffuses 3/4 of the error
+        let count = dots(&dither(&luma, 64, 64, Dithering::Atkinson, 2));
+        assert!((1_500..=2_100).contains(&count), "Atkinson: {count}");
+
+        assert_eq!(dots(&dither(&luma, 64, 64, Dithering::default(), 2)), 64 * 64);
+    }
+
+    #[test]
+    fn test_dither_extremes() {
+        for dithering in [Dithering::FloydSteinberg, Dithering::Atkinson, Dithering::Bayer] {
+            assert!(dither(&[0; 64], 8, 8, dithering, 2).iter().all(|&tone| tone == 1));
+            assert!(dither(&[255; 64], 8, 8, dithering, 2).iter().all(|&tone| tone == 0));
+            assert!(dither(&[0; 64], 8, 8, dithering, 4).iter().all(|&tone| tone == 3));
+        }
+    }
+
+    #[test]
+    fn test_adjust() {
+        let mut luma = [64, 128, 192];
+        adjust(&mut luma, true, None);
+        assert_eq!(luma, [0, 127, 255]);
+
+        let mut luma = [0, 64, 255];
+        adjust(&mut luma, false, Some(2.0));
+        assert_eq!(luma, [0, 128, 255]);
+
+        let mut luma = [100, 100];
+        adjust(&mut luma, true, None);
+        assert_eq!(luma, [100, 100]);
+    }
+
+    #[test]
+    fn test_check_gamma() {
+        assert!(check_gamma(1.8).is_ok());
+        assert!(check_gamma(0.0).is_err());
+        assert!(check_gamma(f32::NAN).is_err());
+    }
+}
@@ -2,15 +2,17 @@
 
 #![cfg(feature = "graphics")]
 
+use super::dithering::{self, Dithering};
+use super::image_transform::*;
 use crate::errors::{PrinterError, Result};
-use image::{DynamicImage, GenericImageView, Rgba};
+use image::{DynamicImage, GenericImageView, Pixel, Rgba};
 
 #[cfg(not(feature = "std"))]
-use alloc::{borrow::ToOwned, string::String, string::ToString, vec, vec::Vec};
+use alloc::{borrow::ToOwned, format, string::String, string::ToString, vec, vec::Vec};
 use core::fmt;
 
 /// Graphic density
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GraphicDensity {
     /// 180dpi x 180dpi
     Low,
@@ -37,7 +39,7 @@ impl fmt::Display for GraphicDensity {
 }
 
 /// Graphic tone
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GraphicTone {
     Monochrome,
     Multiple,
@@ -62,7 +64,7 @@ impl fmt::Display for GraphicTone {
 }
 
 /// Graphic color
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GraphicColor {
     Color1,
     Color2,
@@ -93,7 +95,7 @@ impl fmt::Display for GraphicColor {
 }
 
 /// Graphic size
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GraphicSize {
     Normal,
     Double,
@@ -117,6 +119,16 @@ impl fmt::Display for GraphicSize {
     }
 }
 
+/// Graphic max width (in dots)
+pub(crate) const GRAPHIC_MAX_WIDTH: u32 = 2400;
+/// Graphic max height (in dots)
+pub(crate) const GRAPHIC_MAX_HEIGHT: u32 = 2400;
+
+/// Luma under which a pixel is printed in monochrome
+const GRAPHIC_THRESHOLD: u8 = 128;
+/// Minimum difference between the red channel and the other channels to print a pixel with the second color
+const GRAPHIC_RED_DIFFERENCE: u8 = 64;
+
 #[derive(Debug)]
 // TODO: Make fields private
 pub struct GraphicOption {
@@ -130,6 +142,22 @@ pub struct GraphicOption {
     pub tone: GraphicTone,
     /// Image color
     pub color: GraphicColor,
+    /// Color used for the red pixels (2-color printing, monochrome tone only)
+    pub second_color: Option<GraphicColor>,
+    /// Fit mode in the max width x max height box
+    pub fit: ImageFit,
+    /// Resampling filter
+    pub filter: ImageFilter,
+    /// Horizontal alignment (the image is padded to the max width)
+    pub alignment: ImageAlignment,
+    /// Rotation (applied before scaling)
+    pub rotation: ImageRotation,
+    /// Dithering algorithm
+    pub dithering: Dithering,
+    /// Stretch the image contrast before the dithering
+    pub auto_contrast: bool,
+    /// Gamma correction applied before the dithering (a value greater than 1 lightens the image)
+    pub gamma: Option<f32>,
     /// Width size
     pub width_size: GraphicSize,
     /// Height size
@@ -144,6 +172,14 @@ impl Default for GraphicOption {
             density: GraphicDensity::Low,
             tone: GraphicTone::Monochrome,
             color: GraphicColor::Color1,
+            second_color: None,
+            fit: ImageFit::default(),
+            filter: ImageFilter::default(),
+            alignment: ImageAlignment::default(),
+            rotation: ImageRotation::default(),
+            dithering: Dithering::default(),
+            auto_contrast: false,
+            gamma: None,
             width_size: GraphicSize::Normal,
             height_size: GraphicSize::Normal,
         }
@@ -167,12 +203,31 @@ impl GraphicOption {
             density,
             tone,
             color,
+            second_color: None,
+            fit: ImageFit::default(),
+            filter: ImageFilter::default(),
+            alignment: ImageAlignment::default(),
+            rotation: ImageRotation::default(),
+            dithering: Dithering::default(),
+            auto_contrast: false,
+            gamma: None,
             width_size,
             height_size,
         }
     }
 }
 
+/// Graphic raster data of one color
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphicPlane {
+    /// Tone
+    pub tone: GraphicTone,
+    /// Color
+    pub color: GraphicColor,
+    /// Raster data (1 bit per dot)
+    pub data: Vec<u8>,
+}
+
 #[derive(Debug)]
 pub struct Graphic {
     /// Image path
@@ -186,25 +241,48 @@ impl Graphic {
     /// Create a new image
     pub fn new(path: &str, option: Option<GraphicOption>) -> Result<Self> {
         let img = image::open(path)?;
-        let option = option.unwrap_or_default();
+        Self::from_dynamic_image(img, option.unwrap_or_default(), path)
+    }
 
-        // Resize image with max width and max height constraints and convert to grayscale
-        let img = match (option.max_width, option.max_height) {
-            (Some(max_width), None) => {
-                let resized = img.resize(max_width, max_width, image::imageops::Nearest);
-                resized.grayscale()
-            }
-            (None, Some(max_height)) => {
-                let resized = img.resize(max_height, max_height, image::imageops::Nearest);
-                resized.grayscale()
-            }
-            (Some(max_width), Some(max_height)) => {
-                let resized = img.resize(max_width, max_height, image::imageops::Nearest);
-                resized.grayscale()
-            }
-            _ => img.grayscale(),
+    /// Create a new image from bytes
+    pub fn from_bytes(bytes: &[u8], option: Option<GraphicOption>) -> Result<Self> {
+        let img = image::load_from_memory(bytes)?;
+        Self::from_dynamic_image(img, option.unwrap_or_default(), "")
+    }
+
+    /// Create a new image from `DynamicImage`
+    fn from_dynamic_image(img: DynamicImage, option: GraphicOption, path: &str) -> Result<Self> {
+        if matches!(option.tone, GraphicTone::Multiple) && option.second_color.is_some() {
+            return Err(PrinterError::Input(
+                "graphic second color is only available with monochrome tone".to_owned(),
+            ));
+        }
+
+        if let Some(gamma) = option.gamma {
+            dithering::check_gamma(gamma)?;
+        }
+
+        let transform = ImageTransform {
+            max_width: option.max_width,
+            max_height: option.max_height,
+            fit: option.fit,
+            filter: option.filter,
+            alignment: option.alignment,
+            rotation: option.rotation,
         };
 
+        // Rotate, resize, align and remove alpha canal (transparent pixels become white)
+        let img = transform.apply(img);
+
+        if img.width() == 0 || img.height() == 0 || img.width() > GRAPHIC_MAX_WIDTH || img.height() > GRAPHIC_MAX_HEIGHT
+        {
+            return Err(PrinterError::Input(format!(
+                "invalid graphic size (1-{GRAPHIC_MAX_WIDTH} x 1-{GRAPHIC_MAX_HEIGHT}): {} x {}",
+                img.width(),
+                img.height()
+            )));
+        }
+
         Ok(Self {
             path: path.to_string(),
             option,
@@ -242,11 +320,21 @@ impl Graphic {
         &self.image
     }
 
+    /// Get option
+    pub fn option(&self) -> &GraphicOption {
+        &self.option
+    }
+
     /// Get pixel
     pub fn pixel(&self, x: u32, y: u32) -> Rgba<u8> {
         self.image.get_pixel(x, y)
     }
 
+    /// Get pixel luma
+    fn luma(&self, x: u32, y: u32) -> u8 {
+        self.pixel(x, y).to_luma().0[0]
+    }
+
     /// Is pixel transparent or white?
     pub fn is_blank_pixel(&self, x: u32, y: u32) -> bool {
         let pixel = self.pixel(x, y);
@@ -254,6 +342,37 @@ impl Graphic {
         pixel[3] == 0 || (pixel[0] & pixel[1] & pixel[2]) == 0xFF
     }
 
+    /// Is pixel light? (luma above the monochrome threshold, not printed without dithering)
+    pub fn is_light_pixel(&self, x: u32, y: u32) -> bool {
+        self.luma(x, y) > GRAPHIC_THRESHOLD
+    }
+
+    /// Is pixel red? (printed with the second color)
+    fn is_red_pixel(&self, x: u32, y: u32) -> bool {
+        let [r, g, b, _] = self.pixel(x, y).0;
+        r.saturating_sub(g.max(b)) >= GRAPHIC_RED_DIFFERENCE
+    }
+
+    /// Get the tone of each pixel (row by row) after the contrast adjustment and the dithering
+    ///
+    /// Red pixels are considered white if `without_red` is `true`.
+    fn tones(&self, tones: u8, without_red: bool) -> Vec<u8> {
+        let (width, height) = self.dimensions();
+        let mut luma = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                luma.push(if without_red && self.is_red_pixel(x, y) {
+                    255
+                } else {
+                    self.luma(x, y)
+                });
+            }
+        }
+
+        dithering::adjust(&mut luma, self.option.auto_contrast, self.option.gamma);
+        dithering::dither(&luma, width as usize, height as usize, self.option.dithering, tones)
+    }
+
     /// Get density
     pub fn density(&self) -> u8 {
         self.option.density.into()
@@ -279,49 +398,8 @@ impl Graphic {
         self.option.height_size.into()
     }
 
-    /// Get (p1, p2, p3, p4)
-    pub fn data_size(&self) -> Result<(u8, u8, u8, u8)> {
-        let length = self.image.as_bytes().len() - 11;
-        let p4 = length / 16_777_216;
-        let p3 = length
-            .checked_add_signed(-16_777_216 * isize::try_from(p4)?)
-            .ok_or(PrinterError::Input("graphics invalid (pL, pH)".to_owned()))?
-            / 65_536;
-        let p2 = length
-            .checked_add_signed(-16_777_216 * isize::try_from(p4)?)
-            .ok_or(PrinterError::Input("graphics invalid (pL, pH)".to_owned()))?
-            .checked_add_signed(-65_536 * isize::try_from(p3)?)
-            .ok_or(PrinterError::Input("graphics invalid (pL, pH)".to_owned()))?
-            / 256;
-        let p1 = length
-            .checked_add_signed(-256 * isize::try_from(p2)?)
-            .ok_or(PrinterError::Input("graphics invalid (pL, pH)".to_owned()))?
-            .checked_add_signed(-65_536 * isize::try_from(p3)?)
-            .ok_or(PrinterError::Input("graphics invalid (pL, pH)".to_owned()))?
-            .checked_add_signed(-16_777_216 * isize::try_from(p4)?)
-            .ok_or(PrinterError::Input("graphics invalid (pL, pH)".to_owned()))?;
-
-        Ok((
-            u8::try_from(p1)?,
-            u8::try_from(p2)?,
-            u8::try_from(p3)?,
-            u8::try_from(p4)?,
-        ))
-    }
-
-    /// Get (xL, xH) or (yL, yH) number of dots
-    // TODO: Use get_parameters_number_2 instead
-    pub fn dots_per_direction(&self, length: usize) -> Result<(u8, u8)> {
-        let ph = length / 256;
-        let pl = length
-            .checked_add_signed(-256 * isize::try_from(ph)?)
-            .ok_or(PrinterError::Input("graphics invalid dots per direction".to_owned()))?;
-
-        Ok((u8::try_from(pl)?, u8::try_from(ph)?))
-    }
-
-    /// Data in raster mode
-    pub fn data(&self) -> Result<Vec<u8>> {
+    /// Build raster data with the dots for which `is_dot` returns `true` (pixel index, row by row)
+    fn raster(&self, is_dot: impl Fn(usize) -> bool) -> Vec<u8> {
         let width = self.width_bytes();
         let height = self.height();
 
@@ -330,14 +408,73 @@ impl Graphic {
             for x in 0..width {
                 for b in 0..8 {
                     let i = x * 8 + b;
-                    if i < self.width() && !self.is_blank_pixel(i, y) {
-                        data[(y * width + x) as usize] += 0x80 >> (b & 0x7);
+                    if i < self.width() && is_dot((y * self.width() + i) as usize) {
+                        data[(y * width + x) as usize] |= 0x80 >> b;
                     }
                 }
             }
         }
 
-        Ok(data)
+        data
+    }
+
+    /// Data in raster mode (monochrome)
+    pub fn data(&self) -> Result<Vec<u8>> {
+        let tones = self.tones(2, false);
+        Ok(self.raster(|i| tones[i] == 1))
+    }
+
+    /// Data of each color to store in the print buffer
+    ///
+    /// - Monochrome: one plane with the dark pixels, and a second plane with the red pixels
+    ///   if `second_color` is set
+    /// - Multiple tone: the 4 gray levels are split in two planes (`Color1` for the most significant bit
+    ///   and `Color2` for the least significant bit)
+    pub fn planes(&self) -> Vec<GraphicPlane> {
+        match (self.option.tone, self.option.second_color) {
+            (GraphicTone::Multiple, _) => {
+                let tones = self.tones(4, false);
+                vec![
+                    GraphicPlane {
+                        tone: GraphicTone::Multiple,
+                        color: GraphicColor::Color1,
+                        data: self.raster(|i| tones[i] & 0b10 != 0),
+                    },
+                    GraphicPlane {
+                        tone: GraphicTone::Multiple,
+                        color: GraphicColor::Color2,
+                        data: self.raster(|i| tones[i] & 0b01 != 0),
+                    },
+                ]
+            }
+            (GraphicTone::Monochrome, Some(second_color)) => {
+                let tones = self.tones(2, true);
+                let width = self.width();
+                vec![
+                    GraphicPlane {
+                        tone: GraphicTone::Monochrome,
+                        color: self.option.color,
+                        data: self.raster(|i| tones[i] == 1),
+                    },
+                    GraphicPlane {
+                        tone: GraphicTone::Monochrome,
+                        color: second_color,
+                        data: self.raster(|i| {
+                            let i = i as u32;
+                            self.is_red_pixel(i % width, i / width)
+                        }),
+                    },
+                ]
+            }
+            (GraphicTone::Monochrome, None) => {
+                let tones = self.tones(2, false);
+                vec![GraphicPlane {
+                    tone: GraphicTone::Monochrome,
+                    color: self.option.color,
+                    data: self.raster(|i| tones[i] == 1),
+                }]
+            }
+        }
     }
 }
 
@@ -356,4 +493,22 @@ mod tests {
         let graphic = Graphic::new("./resources/images/rust-logo.png", None).unwrap();
         assert_eq!(graphic.height(), 1_000);
     }
+
+    #[test]
+    fn test_graphic_blank_and_light_pixels() {
+        let pixels = [
+            [255, 255, 255, 255],
+            [0, 0, 0, 0],
+            [200, 200, 200, 255],
+            [20, 20, 20, 255],
+        ]
+        .concat();
+        let image = DynamicImage::ImageRgba8(image::RgbaImage::from_raw(4, 1, pixels).unwrap());
+        let graphic = Graphic::from_dynamic_image(image, GraphicOption::default(), "").unwrap();
+
+        assert!(graphic.is_blank_pixel(0, 0) && graphic.is_blank_pixel(1, 0));
+        assert!(!graphic.is_blank_pixel(2, 0) && !graphic.is_blank_pixel(3, 0));
+        assert!(graphic.is_light_pixel(0, 0) && graphic.is_light_pixel(2, 0));
+        assert!(!graphic.is_light_pixel(3, 0));
+    }
 }
@@ -0,0 +1,301 @@
+//! Image transformations
+//!
+//! Rotation, scaling and alignment applied to the images before printing.
+
+#![cfg(feature = "graphics")]
+
+use core::fmt;
+use image::{DynamicImage, Rgba, RgbaImage, imageops};
+
+/// Image fit mode in the max width x max height box
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Shrink the image to fit in the box (the image is never enlarged)
+    #[default]
+    Shrink,
+    /// Scale the image to the max width
+    Width,
+    /// Scale the image to the max height
+    Height,
+    /// Scale the image to fit in the box
+    Contain,
+    /// Scale the image to fill the box and crop what overflows (centered)
+    Cover,
+    /// No scaling, the image is cropped to the box
+    None,
+}
+
+impl fmt::Display for ImageFit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageFit::Shrink => write!(f, "Shrink"),
+            ImageFit::Width => write!(f, "Fit width"),
+            ImageFit::Height => write!(f, "Fit height"),
+            ImageFit::Contain => write!(f, "Contain"),
+            ImageFit::Cover => write!(f, "Cover"),
+            ImageFit::None => write!(f, "No scaling"),
+        }
+    }
+}
+
+/// Image resampling filter
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFilter {
+    /// Nearest neighbor (fastest, best for pixel art and sharp logos)
+    #[default]
+    Nearest,
+    /// Linear
+    Triangle,
+    /// Cubic
+    CatmullRom,
+    /// Gaussian
+    Gaussian,
+    /// Lanczos with window 3 (best for photos)
+    Lanczos3,
+}
+
+impl From<ImageFilter> for imageops::FilterType {
+    fn from(value: ImageFilter) -> Self {
+        match value {
+            ImageFilter::Nearest => imageops::FilterType::Nearest,
+            ImageFilter::Triangle => imageops::FilterType::Triangle,
+            ImageFilter::CatmullRom => imageops::FilterType::CatmullRom,
+            ImageFilter::Gaussian => imageops::FilterType::Gaussian,
+            ImageFilter::Lanczos3 => imageops::FilterType::Lanczos3,
+        }
+    }
+}
+
+impl fmt::Display for ImageFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageFilter::Nearest => write!(f, "Nearest"),
+            ImageFilter::Triangle => write!(f, "Triangle"),
+            ImageFilter::CatmullRom => write!(f, "Catmull-Rom"),
+            ImageFilter::Gaussian => write!(f, "Gaussian"),
+            ImageFilter::Lanczos3 => write!(f, "Lanczos3"),
+        }
+    }
+}
+
+/// Image horizontal alignment
+///
+/// The image is padded with white dots to the max width.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl fmt::Display for ImageAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageAlignment::Left => write!(f, "Left"),
+            ImageAlignment::Center => write!(f, "Center"),
+            ImageAlignment::Right => write!(f, "Right"),
+        }
+    }
+}
+
+/// Image rotation (clockwise)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageRotation {
+    #[default]
+    None,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl fmt::Display for ImageRotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageRotation::None => write!(f, "0°"),
+            ImageRotation::Rotate90 => write!(f, "90°"),
+            ImageRotation::Rotate180 => write!(f, "180°"),
+            ImageRotation::Rotate270 => write!(f, "270°"),
+        }
+    }
+}
+
+/// Image transformation
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ImageTransform {
+    pub(crate) max_width: Option<u32>,
+    pub(crate) max_height: Option<u32>,
+    pub(crate) fit: ImageFit,
+    pub(crate) filter: ImageFilter,
+    pub(crate) alignment: ImageAlignment,
+    pub(crate) rotation: ImageRotation,
+}
+
+impl ImageTransform {
+    /// Rotate, scale and align the image
+    ///
+    /// Transparent pixels are blended with white.
+    pub(crate) fn apply(&self, img: DynamicImage) -> DynamicImage {
+        let img = match self.rotation {
+            ImageRotation::None => img,
+            ImageRotation::Rotate90 => img.rotate90(),
+            ImageRotation::Rotate180 => img.rotate180(),
+            ImageRotation::Rotate270 => img.rotate270(),
+        };
+
+        let img = self.scale(img);
+
+        // Remove alpha canal
+        let mut rgba = img.to_rgba8();
+        for pixel in rgba.pixels_mut() {
+            let alpha = u32::from(pixel[3]);
+            for channel in 0..3 {
+                pixel[channel] = ((u32::from(pixel[channel]) * alpha + (255 - alpha) * 255) / 255) as u8;
+            }
+            pixel[3] = 255;
+        }
+
+        DynamicImage::ImageRgba8(self.align(rgba))
+    }
+
+    /// Scale the image according to the fit mode
+    fn scale(&self, img: DynamicImage) -> DynamicImage {
+        let filter = self.filter.into();
+        let (width, height) = (img.width(), img.height());
+
+        match (self.fit, self.max_width, self.max_height) {
+            (ImageFit::Shrink, Some(max_width), max_height)
+                if width > max_width || max_height.is_some_and(|max_height| height > max_height) =>
+            {
+                img.resize(max_width, max_height.unwrap_or(u32::MAX), filter)
+            }
+            (ImageFit::Shrink, None, Some(max_height)) if height > max_height => {
+                img.resize(u32::MAX, max_height, filter)
+            }
+            (ImageFit::Width, Some(max_width), _) => img.resize(max_width, u32::MAX, filter),
+            (ImageFit::Height, _, Some(max_height)) => img.resize(u32::MAX, max_height, filter),
+            (ImageFit::Contain, max_width, max_height) if max_width.is_some() || max_height.is_some() => {
+                img.resize(max_width.unwrap_or(u32::MAX), max_height.unwrap_or(u32::MAX), filter)
+            }
+            (ImageFit::Cover, Some(max_width), Some(max_height)) => img.resize_to_fill(max_width, max_height, filter),
+            (ImageFit::None, max_width, max_height) => img.crop_imm(
+                0,
+                0,
+                max_width.unwrap_or(width).min(width),
+                max_height.unwrap_or(height).min(height),
+            ),
+            _ => img,
+        }
+    }
+
+    /// Pad the image with white pixels to the max width
+    fn align(&self, img: RgbaImage) -> RgbaImage {
+        let Some(max_width) = self.max_width else {
+            return img;
+        };
+        if self.alignment == ImageAlignment::Left || img.width() >= max_width {
+            return img;
+        }
+
+        let x = match self.alignment {
+            ImageAlignment::Left => 0,
+            ImageAlignment::Center => (max_width - img.width()) / 2,
+            ImageAlignment::Right => max_width - img.width(),
+        };
+        let mut padded = RgbaImage::from_pixel(max_width, img.height(), Rgba([255, 255, 255, 255]));
+        imageops::replace(&mut padded, &img, i64::from(x), 0);
+        padded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(fit: ImageFit, max_width: Option<u32>, max_height: Option<u32>) -> ImageTransform {
+        ImageTransform {
+            max_width,
+            max_height,
+            fit,
+            ..Default::default()
+        }
+    }
+
+    fn dimensions(transform: ImageTransform, width: u32, height: u32) -> (u32, u32) {
+        let img = transform.apply(DynamicImage::ImageRgba8(RgbaImage::new(width, height)));
+        (img.width(), img.height())
+    }
+
+    #[test]
+    fn test_fit() {
+        let (some, none) = (Some, None);
+        assert_eq!(
+            dimensions(transform(ImageFit::Shrink, some(100), none), 200, 400),
+            (100, 200)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::Shrink, some(100), some(100)), 200, 400),
+            (50, 100)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::Shrink, some(100), some(100)), 20, 40),
+            (20, 40)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::Width, some(100), some(10)), 20, 40),
+            (100, 200)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::Height, some(100), some(10)), 20, 40),
+            (5, 10)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::Contain, some(100), some(100)), 20, 40),
+            (50, 100)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::Cover, some(100), some(100)), 20, 40),
+            (100, 100)
+        );
+        assert_eq!(
+            dimensions(transform(ImageFit::None, some(100), some(30)), 200, 40),
+            (100, 30)
+        );
+        assert_eq!(dimensions(transform(ImageFit::None, none, none), 200, 40), (200, 40));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let transform = ImageTransform {
+            rotation: ImageRotation::Rotate90,
+            ..Default::default()
+        };
+        assert_eq!(dimensions(transform, 20, 40), (40, 20));
+    }
+
+    #[test]
+    fn test_alignment() {
+        let black = DynamicImage::ImageRgba8(RgbaImage::from_pixel(2, 1, Rgba([0, 0, 0, 255])));
+        let row = |alignment| {
+            let transform = ImageTransform {
+                max_width: Some(6),
+                alignment,
+                ..Default::default()
+            };
+            transform
+                .apply(black.clone())
+                .to_luma8()
+                .into_raw()
+                .into_iter()
+                .map(|luma| luma == 0)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(row(ImageAlignment::Left), vec![true, true]);
+        assert_eq!(
+            row(ImageAlignment::Center),
+            vec![false, false, true, true, false, false]
+        );
+        assert_eq!(row(ImageAlignment::Right), vec![false, false, false, false, true, true]);
+    }
+}
@@ -3,9 +3,13 @@ mod character;
 mod codes;
 pub(crate) mod common;
 mod constants;
+mod dithering;
 mod graphics;
+mod image_transform;
+pub(crate) mod nv_graphics;
 mod page_codes;
 mod protocol;
+mod raster;
 mod status;
 mod types;
 
@@ -18,10 +22,19 @@ pub use codes::*;
 pub use common::chars_number;
 pub use constants::*;
 pub use protocol::*;
+pub use raster::*;
 pub use status::*;
 pub use types::*;
 
 #[cfg(feature = "graphics")]
 pub use bit_image::*;
 #[cfg(feature = "graphics")]
+pub use codes::CodeRendering;
+#[cfg(feature = "graphics")]
+pub use dithering::Dithering;
+#[cfg(feature = "graphics")]
 pub use graphics::*;
+#[cfg(feature = "graphics")]
+pub use image_transform::{ImageAlignment, ImageFilter, ImageFit, ImageRotation};
+#[cfg(feature = "graphics")]
+pub use nv_graphics::NvGraphicKeyCode;
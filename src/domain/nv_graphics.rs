@@ -0,0 +1,193 @@
+//! NV graphics
+//!
+//! Graphics stored in the printer non-volatile memory and printed by key code.
+//!
+//! [Epson Documentation](https://download4.epson.biz/sec_pubs/pos/reference_en/escpos/gs_lparen_cl.html)
+
+#![cfg(feature = "graphics")]
+
+use super::common::{get_response_data, parse_decimal};
+use crate::errors::{PrinterError, Result};
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Response header of the NV graphics functions
+pub(crate) const NV_GRAPHICS_RESPONSE_HEADER: u8 = 0x37;
+/// Response identifier of the NV graphics memory capacity (function 48)
+pub(crate) const NV_GRAPHICS_CAPACITY_IDENTIFIER: u8 = 0x30;
+/// Response identifier of the NV graphics memory remaining capacity (function 51)
+pub(crate) const NV_GRAPHICS_REMAINING_CAPACITY_IDENTIFIER: u8 = 0x31;
+/// Response identifier of the NV graphics key code list (function 64)
+pub(crate) const NV_GRAPHICS_KEY_CODES_IDENTIFIER: u8 = 0x72;
+
+/// Maximum width (in dots) of an NV graphic
+pub(crate) const NV_GRAPHIC_MAX_WIDTH: u32 = 8192;
+/// Maximum height (in dots) of an NV graphic
+pub(crate) const NV_GRAPHIC_MAX_HEIGHT: u32 = 2304;
+
+/// Key code list status: all the key codes have been sent
+const KEY_CODES_STATUS_END: u8 = 0x40;
+/// Key code list status: more key codes will be sent after an `ACK`
+const KEY_CODES_STATUS_CONTINUE: u8 = 0x41;
+
+/// NV graphic key code
+///
+/// A key code is made of two characters in the range `0x20` - `0x7E` (e.g. `"LG"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NvGraphicKeyCode {
+    kc1: u8,
+    kc2: u8,
+}
+
+impl NvGraphicKeyCode {
+    /// Create a new `NvGraphicKeyCode`
+    ///
+    /// ```
+    /// use escpos::utils::NvGraphicKeyCode;
+    ///
+    /// assert!(NvGraphicKeyCode::new(b'L', b'G').is_ok());
+    /// assert!(NvGraphicKeyCode::new(0x1F, b'G').is_err());
+    /// ```
+    pub fn new(kc1: u8, kc2: u8) -> Result<Self> {
+        if !(32..=126).contains(&kc1) || !(32..=126).contains(&kc2) {
+            return Err(PrinterError::Input(format!(
+                "invalid NV graphic key code (32-126): ({kc1}, {kc2})"
+            )));
+        }
+
+        Ok(Self { kc1, kc2 })
+    }
+
+    /// Get the first key code byte
+    pub fn kc1(&self) -> u8 {
+        self.kc1
+    }
+
+    /// Get the second key code byte
+    pub fn kc2(&self) -> u8 {
+        self.kc2
+    }
+
+    /// Parse a key code list response block (function 64)
+    ///
+    /// Returns the key codes and `true` if the printer has more key codes to send.
+    pub(crate) fn parse_list(response: &[u8]) -> Result<(Vec<Self>, bool)> {
+        let data = get_response_data(response, NV_GRAPHICS_RESPONSE_HEADER, NV_GRAPHICS_KEY_CODES_IDENTIFIER)?;
+        let (has_more, codes) = match data {
+            [KEY_CODES_STATUS_END, codes @ ..] => (false, codes),
+            [KEY_CODES_STATUS_CONTINUE, codes @ ..] => (true, codes),
+            _ => {
+                return Err(PrinterError::InvalidResponse(format!(
+                    "invalid NV graphics key code list status: {data:02X?}"
+                )));
+            }
+        };
+
+        if codes.len() % 2 != 0 {
+            return Err(PrinterError::InvalidResponse(format!(
+                "invalid NV graphics key code list: {codes:02X?}"
+            )));
+        }
+
+        let codes = codes
+            .chunks_exact(2)
+            .map(|kc| Self::new(kc[0], kc[1]))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((codes, has_more))
+    }
+}
+
+impl TryFrom<&str> for NvGraphicKeyCode {
+    type Error = PrinterError;
+
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
+        match value.as_bytes() {
+            [kc1, kc2] => Self::new(*kc1, *kc2),
+            _ => Err(PrinterError::Input(format!(
+                "NV graphic key code must be made of 2 characters: {value}"
+            ))),
+        }
+    }
+}
+
+impl From<NvGraphicKeyCode> for (u8, u8) {
+    fn from(value: NvGraphicKeyCode) -> Self {
+        (value.kc1, value.kc2)
+    }
+}
+
+impl fmt::Display for NvGraphicKeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", char::from(self.kc1), char::from(self.kc2))
+    }
+}
+
+/// Parse an NV graphics memory capacity response (function 48 or 51)
+pub(crate) fn parse_nv_graphics_capacity(response: &[u8], identifier: u8) -> Result<u32> {
+    parse_decimal(get_response_data(response, NV_GRAPHICS_RESPONSE_HEADER, identifier)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_nv_graphic_key_code_new() {
+        assert!(NvGraphicKeyCode::new(32, 126).is_ok());
+        assert!(NvGraphicKeyCode::new(31, 65).is_err());
+        assert!(NvGraphicKeyCode::new(65, 127).is_err());
+    }
+
+    #[test]
+    fn test_nv_graphic_key_code_try_from_str() {
+        let key_code = NvGraphicKeyCode::try_from("LG").unwrap();
+        assert_eq!((key_code.kc1(), key_code.kc2()), (b'L', b'G'));
+        assert_eq!(key_code.to_string(), "LG");
+
+        assert!(NvGraphicKeyCode::try_from("L").is_err());
+        assert!(NvGraphicKeyCode::try_from("LGO").is_err());
+        assert!(NvGraphicKeyCode::try_from("é").is_err());
+    }
+
+    #[test]
+    fn test_nv_graphic_key_code_parse_list() {
+        let (codes, has_more) =
+            NvGraphicKeyCode::parse_list(&[0x37, 0x72, 0x40, b'L', b'G', b'A', b'1', 0x00]).unwrap();
+        assert_eq!(
+            codes,
+            vec![
+                NvGraphicKeyCode::new(b'L', b'G').unwrap(),
+                NvGraphicKeyCode::new(b'A', b'1').unwrap()
+            ]
+        );
+        assert!(!has_more);
+
+        let (codes, has_more) = NvGraphicKeyCode::parse_list(&[0x37, 0x72, 0x41, b'L', b'G', 0x00]).unwrap();
+        assert_eq!(codes.len(), 1);
+        assert!(has_more);
+
+        let (codes, has_more) = NvGraphicKeyCode::parse_list(&[0x37, 0x72, 0x40, 0x00]).unwrap();
+        assert!(codes.is_empty());
+        assert!(!has_more);
+
+        assert!(NvGraphicKeyCode::parse_list(&[0x37, 0x72, 0x42, 0x00]).is_err());
+        assert!(NvGraphicKeyCode::parse_list(&[0x37, 0x72, 0x40, b'L', 0x00]).is_err());
+        assert!(NvGraphicKeyCode::parse_list(&[0x37, 0x30, 0x40, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_parse_nv_graphics_capacity() {
+        assert_eq!(
+            parse_nv_graphics_capacity(b"\x37\x30262144\x00", NV_GRAPHICS_CAPACITY_IDENTIFIER).unwrap(),
+            262_144
+        );
+        assert_eq!(
+            parse_nv_graphics_capacity(b"\x37\x311024\x00", NV_GRAPHICS_REMAINING_CAPACITY_IDENTIFIER).unwrap(),
+            1_024
+        );
+        assert!(parse_nv_graphics_capacity(b"\x37\x311024\x00", NV_GRAPHICS_CAPACITY_IDENTIFIER).is_err());
+    }
+}
@@ -6,6 +6,11 @@ use super::bit_image::*;
 use super::codes::*;
 #[cfg(feature = "codes_2d")]
 use super::common::get_parameters_number_2;
+#[cfg(feature = "graphics")]
+use super::graphics::*;
+#[cfg(feature = "graphics")]
+use super::nv_graphics::*;
+use super::raster::{BIT_IMAGE_COLUMN_LINE_SPACING, BitImageMode, RasterBitImage};
 use super::{RealTimeStatusRequest, character::*, constants::*, types::*};
 #[cfg(feature = "ui")]
 use crate::domain::ui::{UIComponent, line::Line};
@@ -23,6 +28,8 @@ use alloc::borrow::ToOwned;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use alloc::{format, vec};
+#[cfg(feature = "graphics")]
+use image::DynamicImage;
 
 /// Protocol used to communicate with the printer
 #[derive(Default, Clone)]
@@ -239,6 +246,11 @@ impl Protocol {
         cmd
     }
 
+    /// Transmit paper sensor status (`GS r 1`, processed after the previous data)
+    pub(crate) fn transmit_paper_sensor_status(&self) -> Command {
+        GS_TRANSMIT_PAPER_SENSOR_STATUS.to_vec()
+    }
+
     /// Transmit real-time status
     pub(crate) fn real_time_status(&self, status: RealTimeStatusRequest) -> Command {
         let mut cmd = DLE_REAL_TIME_STATUS.to_vec();
@@ -297,6 +309,19 @@ impl Protocol {
         cmd
     }
 
+    #[cfg(feature = "barcodes")]
+    /// Print barcode with the function B (`GS k m n d1...dn`)
+    fn barcode_print_function_b(&self, system: BarcodeSystem, data: &[u8]) -> Result<Command> {
+        let length = u8::try_from(data.len())
+            .map_err(|_| PrinterError::Input(format!("{system} barcode data is too long: {} bytes", data.len())))?;
+
+        let mut cmd = GS_BARCODE_PRINT.to_vec();
+        cmd.push(system.into());
+        cmd.push(length);
+        cmd.extend_from_slice(data);
+        Ok(cmd)
+    }
+
     #[cfg(feature = "barcodes")]
     /// Configure and print barcode
     pub(crate) fn barcode(&self, data: &str, system: BarcodeSystem, option: BarcodeOption) -> Result<Vec<Command>> {
@@ -305,7 +330,19 @@ impl Protocol {
             self.barcode_height(option.height().into())?,
             self.barcode_font(option.font()),
             self.barcode_position(option.position()),
-            self.barcode_print(system, data),
+            match system {
+                BarcodeSystem::CODE128 => {
+                    self.barcode_print_function_b(system, &code128::escpos_data(data.as_bytes()))?
+                }
+                BarcodeSystem::GS1_128 | BarcodeSystem::GS1DataBarExpanded => {
+                    self.barcode_print_function_b(system, &gs1::escpos_data(&gs1::parse(data)?))?
+                }
+                BarcodeSystem::CODE93
+                | BarcodeSystem::GS1DataBarOmnidirectional
+                | BarcodeSystem::GS1DataBarTruncated
+                | BarcodeSystem::GS1DataBarLimited => self.barcode_print_function_b(system, data.as_bytes())?,
+                _ => self.barcode_print(system, data),
+            },
         ])
     }
 
@@ -337,11 +374,11 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// QR code data
-    fn qrcode_data(&self, data: &str) -> Result<Command> {
+    fn qrcode_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.append(&mut vec![pl, ph, 49, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -352,17 +389,32 @@ impl Protocol {
     }
 
     #[cfg(feature = "codes_2d")]
-    /// QR code print
-    pub(crate) fn qrcode(&self, data: &str, option: QRCodeOption) -> Result<Vec<Command>> {
+    /// QR code settings and data storage
+    fn qrcode_store(&self, data: &[u8], option: &QRCodeOption) -> Result<Vec<Command>> {
         Ok(vec![
             self.qrcode_model(option.model()),
             self.qrcode_size(option.size()),
             self.qrcode_correction_level(option.correction_level()),
             self.qrcode_data(data)?,
-            self.qrcode_print(),
         ])
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// QR code print
+    pub(crate) fn qrcode(&self, data: &[u8], option: QRCodeOption) -> Result<Vec<Command>> {
+        let mut commands = self.qrcode_store(data, &option)?;
+        commands.push(self.qrcode_print());
+        Ok(commands)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// QR code symbol size information request
+    pub(crate) fn qrcode_size_info(&self, data: &[u8], option: &QRCodeOption) -> Result<Command> {
+        let mut cmd = self.qrcode_store(data, option)?.concat();
+        cmd.extend_from_slice(GS_2D_QRCODE_SIZE_INFO);
+        Ok(cmd)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// 2D GS1 DataBar width
     fn gs1_databar_2d_width(&self, size: GS1DataBar2DWidth) -> Command {
@@ -384,12 +436,12 @@ impl Protocol {
     /// 2D GS1 DataBar data
     fn gs1_databar_2d_data(&self, data: &str, code_type: GS1DataBar2DType) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
-        let (pl, ph) = get_parameters_number_2(data, 4)?;
+        let (pl, ph) = get_parameters_number_2(data.as_bytes(), 4)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![51, 80, 48]);
         cmd.push(code_type.into());
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data.as_bytes());
 
         Ok(cmd)
     }
@@ -401,16 +453,31 @@ impl Protocol {
     }
 
     #[cfg(feature = "codes_2d")]
-    /// 2D GS1 DataBar
-    pub(crate) fn gs1_databar_2d(&self, data: &str, option: GS1DataBar2DOption) -> Result<Vec<Command>> {
+    /// 2D GS1 DataBar settings and data storage
+    fn gs1_databar_2d_store(&self, data: &str, option: &GS1DataBar2DOption) -> Result<Vec<Command>> {
         Ok(vec![
             self.gs1_databar_2d_width(option.width()),
             self.gs1_databar_2d_expanded_width(0),
             self.gs1_databar_2d_data(data, option.code_type())?,
-            self.gs1_databar_2d_print(),
         ])
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// 2D GS1 DataBar
+    pub(crate) fn gs1_databar_2d(&self, data: &str, option: GS1DataBar2DOption) -> Result<Vec<Command>> {
+        let mut commands = self.gs1_databar_2d_store(data, &option)?;
+        commands.push(self.gs1_databar_2d_print());
+        Ok(commands)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// 2D GS1 DataBar symbol size information request
+    pub(crate) fn gs1_databar_2d_size_info(&self, data: &str, option: &GS1DataBar2DOption) -> Result<Command> {
+        let mut cmd = self.gs1_databar_2d_store(data, option)?.concat();
+        cmd.extend_from_slice(GS_2D_GS1_DATABAR_SIZE_INFO);
+        Ok(cmd)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// PDF417 number of columns
     fn pdf417_columns(&self, option: &Pdf417Option) -> Command {
@@ -463,13 +530,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// PDF417 data
-    fn pdf417_data(&self, data: &str) -> Result<Command> {
+    fn pdf417_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![48, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -480,20 +547,35 @@ impl Protocol {
     }
 
     #[cfg(feature = "codes_2d")]
-    /// PDF417
-    pub(crate) fn pdf417(&self, data: &str, option: Pdf417Option) -> Result<Vec<Command>> {
+    /// PDF417 settings and data storage
+    fn pdf417_store(&self, data: &[u8], option: &Pdf417Option) -> Result<Vec<Command>> {
         Ok(vec![
-            self.pdf417_columns(&option),
-            self.pdf417_rows(&option),
-            self.pdf417_width(&option),
-            self.pdf417_row_height(&option),
-            self.pdf417_correction_level(&option)?,
-            self.pdf417_type(&option),
+            self.pdf417_columns(option),
+            self.pdf417_rows(option),
+            self.pdf417_width(option),
+            self.pdf417_row_height(option),
+            self.pdf417_correction_level(option)?,
+            self.pdf417_type(option),
             self.pdf417_data(data)?,
-            self.pdf417_print(),
         ])
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// PDF417
+    pub(crate) fn pdf417(&self, data: &[u8], option: Pdf417Option) -> Result<Vec<Command>> {
+        let mut commands = self.pdf417_store(data, &option)?;
+        commands.push(self.pdf417_print());
+        Ok(commands)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// PDF417 symbol size information request
+    pub(crate) fn pdf417_size_info(&self, data: &[u8], option: &Pdf417Option) -> Result<Command> {
+        let mut cmd = self.pdf417_store(data, option)?.concat();
+        cmd.extend_from_slice(GS_2D_PDF417_SIZE_INFO);
+        Ok(cmd)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// MaxiCode mode
     fn maxi_code_mode(&self, mode: MaxiCodeMode) -> Command {
@@ -504,13 +586,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// MaxiCode data
-    fn maxi_code_data(&self, data: &str) -> Result<Command> {
+    fn maxi_code_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![50, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -520,16 +602,26 @@ impl Protocol {
         GS_2D_MAXI_CODE_PRINT.to_vec()
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// MaxiCode mode and data storage
+    fn maxi_code_store(&self, data: &[u8], mode: MaxiCodeMode) -> Result<Vec<Command>> {
+        Ok(vec![self.maxi_code_mode(mode), self.maxi_code_data(data)?])
+    }
+
     #[cfg(feature = "codes_2d")]
     /// MaxiCode
-    pub(crate) fn maxi_code(&self, data: &str, mode: MaxiCodeMode) -> Result<Vec<Command>> {
-        let code = MaxiCode::new(data, mode);
+    pub(crate) fn maxi_code(&self, data: &[u8], mode: MaxiCodeMode) -> Result<Vec<Command>> {
+        let mut commands = self.maxi_code_store(data, mode)?;
+        commands.push(self.maxi_code_print());
+        Ok(commands)
+    }
 
-        Ok(vec![
-            self.maxi_code_mode(code.mode),
-            self.maxi_code_data(&code.data)?,
-            self.maxi_code_print(),
-        ])
+    #[cfg(feature = "codes_2d")]
+    /// MaxiCode symbol size information request
+    pub(crate) fn maxi_code_size_info(&self, data: &[u8], mode: MaxiCodeMode) -> Result<Command> {
+        let mut cmd = self.maxi_code_store(data, mode)?.concat();
+        cmd.extend_from_slice(GS_2D_MAXI_CODE_SIZE_INFO);
+        Ok(cmd)
     }
 
     #[cfg(feature = "codes_2d")]
@@ -553,13 +645,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// DataMatrix data
-    fn data_matrix_data(&self, data: &str) -> Result<Command> {
+    fn data_matrix_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![54, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -570,16 +662,31 @@ impl Protocol {
     }
 
     #[cfg(feature = "codes_2d")]
-    /// DataMatrix
-    pub(crate) fn data_matrix(&self, data: &str, option: DataMatrixOption) -> Result<Vec<Command>> {
+    /// DataMatrix settings and data storage
+    fn data_matrix_store(&self, data: &[u8], option: &DataMatrixOption) -> Result<Vec<Command>> {
         Ok(vec![
             self.data_matrix_type(option.code_type())?,
             self.data_matrix_size(option.size()),
             self.data_matrix_data(data)?,
-            self.data_matrix_print(),
         ])
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix
+    pub(crate) fn data_matrix(&self, data: &[u8], option: DataMatrixOption) -> Result<Vec<Command>> {
+        let mut commands = self.data_matrix_store(data, &option)?;
+        commands.push(self.data_matrix_print());
+        Ok(commands)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix symbol size information request
+    pub(crate) fn data_matrix_size_info(&self, data: &[u8], option: &DataMatrixOption) -> Result<Command> {
+        let mut cmd = self.data_matrix_store(data, option)?.concat();
+        cmd.extend_from_slice(GS_2D_DATA_MATRIX_SIZE_INFO);
+        Ok(cmd)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// Aztec code mode
     fn aztec_mode(&self, mode: AztecMode) -> Result<Command> {
@@ -608,13 +715,13 @@ impl Protocol {
 
     #[cfg(feature = "codes_2d")]
     /// Aztec code data
-    fn aztec_data(&self, data: &str) -> Result<Command> {
+    fn aztec_data(&self, data: &[u8]) -> Result<Command> {
         let mut cmd = GS_2D.to_vec();
         let (pl, ph) = get_parameters_number_2(data, 3)?;
         cmd.push(pl);
         cmd.push(ph);
         cmd.append(&mut vec![53, 80, 48]);
-        cmd.append(&mut data.as_bytes().to_vec());
+        cmd.extend_from_slice(data);
         Ok(cmd)
     }
 
@@ -625,49 +732,294 @@ impl Protocol {
     }
 
     #[cfg(feature = "codes_2d")]
-    /// Aztec code
-    pub(crate) fn aztec(&self, data: &str, option: AztecOption) -> Result<Vec<Command>> {
-        let code = Aztec::new(data, option);
+    /// Aztec code settings and data storage
+    fn aztec_store(&self, data: &[u8], option: &AztecOption) -> Result<Vec<Command>> {
         Ok(vec![
-            self.aztec_mode(code.option.mode())?,
-            self.aztec_size(code.option.size()),
-            self.aztec_correction_level(code.option.correction_level()),
+            self.aztec_mode(option.mode())?,
+            self.aztec_size(option.size()),
+            self.aztec_correction_level(option.correction_level()),
             self.aztec_data(data)?,
-            self.aztec_print(),
         ])
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code
+    pub(crate) fn aztec(&self, data: &[u8], option: AztecOption) -> Result<Vec<Command>> {
+        let mut commands = self.aztec_store(data, &option)?;
+        commands.push(self.aztec_print());
+        Ok(commands)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code symbol size information request
+    pub(crate) fn aztec_size_info(&self, data: &[u8], option: &AztecOption) -> Result<Command> {
+        let mut cmd = self.aztec_store(data, option)?.concat();
+        cmd.extend_from_slice(GS_2D_AZTEC_CODE_SIZE_INFO);
+        Ok(cmd)
+    }
+
     #[cfg(feature = "graphics")]
     /// Print bit image
-    pub(crate) fn bit_image(&self, path: &str, option: BitImageOption) -> Result<Command> {
+    pub(crate) fn bit_image(
+        &self,
+        path: &str,
+        option: BitImageOption,
+        line_spacing: Option<u8>,
+    ) -> Result<Vec<Command>> {
         let bit_image = BitImage::new(path, option)?;
-        self.build_bit_image(bit_image)
+        self.build_bit_image(bit_image, line_spacing)
     }
 
     #[cfg(feature = "graphics")]
     /// Print bit image from bytes
-    pub(crate) fn bit_image_from_bytes(&self, bytes: &[u8], option: BitImageOption) -> Result<Command> {
+    pub(crate) fn bit_image_from_bytes(
+        &self,
+        bytes: &[u8],
+        option: BitImageOption,
+        line_spacing: Option<u8>,
+    ) -> Result<Vec<Command>> {
         let bit_image = BitImage::from_bytes(bytes, option)?;
-        self.build_bit_image(bit_image)
+        self.build_bit_image(bit_image, line_spacing)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print bit image from `DynamicImage`
+    pub(crate) fn bit_image_from_dynamic_image(
+        &self,
+        img: DynamicImage,
+        option: BitImageOption,
+        line_spacing: Option<u8>,
+    ) -> Result<Vec<Command>> {
+        let bit_image = BitImage::from_dynamic_image(img, option)?;
+        self.build_bit_image(bit_image, line_spacing)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print bit image from RGBA pixels
+    pub(crate) fn bit_image_from_rgba(
+        &self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        option: BitImageOption,
+        line_spacing: Option<u8>,
+    ) -> Result<Vec<Command>> {
+        let bit_image = BitImage::from_rgba(pixels, width, height, option)?;
+        self.build_bit_image(bit_image, line_spacing)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print bit image from luma pixels
+    pub(crate) fn bit_image_from_luma(
+        &self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        option: BitImageOption,
+        line_spacing: Option<u8>,
+    ) -> Result<Vec<Command>> {
+        let bit_image = BitImage::from_luma(pixels, width, height, option)?;
+        self.build_bit_image(bit_image, line_spacing)
     }
 
     #[cfg(feature = "graphics")]
-    fn build_bit_image(&self, bit_image: BitImage) -> Result<Command> {
+    fn build_bit_image(&self, bit_image: BitImage, line_spacing: Option<u8>) -> Result<Vec<Command>> {
+        Ok(self.raster_bit_image(
+            &bit_image.raster()?,
+            bit_image.mode(),
+            bit_image.band_height(),
+            line_spacing,
+        ))
+    }
+
+    /// Print raster bit image (`GS v 0` or `ESC *` according to the mode)
+    ///
+    /// The image is split in bands of `band_height` lines (65,535 lines max.), one command per band.
+    /// In column format, the band height is rounded up to a multiple of the column height
+    /// and the line spacing is restored after the image (`ESC 3` with `line_spacing`, `ESC 2` otherwise).
+    pub(crate) fn raster_bit_image(
+        &self,
+        raster: &RasterBitImage,
+        mode: BitImageMode,
+        band_height: Option<u16>,
+        line_spacing: Option<u8>,
+    ) -> Vec<Command> {
+        let band_height = u32::from(band_height.unwrap_or(u16::MAX).max(1));
+
+        let Some((m, dots)) = mode.column() else {
+            return (0..raster.height())
+                .step_by(band_height as usize)
+                .map(|y| self.raster_format_bit_image(raster, y, band_height.min(raster.height() - y)))
+                .collect();
+        };
+
+        let dots_u32 = u32::from(dots);
+        let band_height = band_height.div_ceil(dots_u32) * dots_u32;
+        let mut commands: Vec<Command> = (0..raster.height())
+            .step_by(band_height as usize)
+            .map(|band| {
+                let mut cmd = Vec::new();
+                let end = band.saturating_add(band_height).min(raster.height());
+                for y in (band..end).step_by(usize::from(dots)) {
+                    cmd.extend_from_slice(ESC_IMAGE_COLUMN_PREFIX);
+                    cmd.push(m);
+                    cmd.extend_from_slice(&raster.width().to_le_bytes());
+                    cmd.append(&mut raster.column_band(y, dots));
+                    cmd.push(LF);
+                }
+                cmd
+            })
+            .collect();
+
+        // Lines are printed without gap between them
+        if let Some(first) = commands.first_mut() {
+            first.splice(0..0, self.line_spacing(BIT_IMAGE_COLUMN_LINE_SPACING));
+        }
+        if let Some(last) = commands.last_mut() {
+            last.append(&mut match line_spacing {
+                Some(value) => self.line_spacing(value),
+                None => self.reset_line_spacing(),
+            });
+        }
+
+        commands
+    }
+
+    /// Raster format bit image (`GS v 0`) of `lines` lines starting at line `y`
+    fn raster_format_bit_image(&self, raster: &RasterBitImage, y: u32, lines: u32) -> Command {
         let mut cmd = GS_IMAGE_BITMAP_PREFIX.to_vec();
 
         // Size
-        cmd.push(bit_image.size().into());
+        cmd.push((&raster.size()).into());
 
-        // Width and height
-        cmd.append(&mut bit_image.with_bytes_u8()?);
-        cmd.append(&mut bit_image.height_u8()?);
+        // Width (in bytes) and height (`lines` is lower than 65,536)
+        cmd.extend_from_slice(&raster.width_bytes().to_le_bytes());
+        cmd.extend_from_slice(&(lines as u16).to_le_bytes());
 
         // Data
-        cmd.append(&mut bit_image.raster_data()?);
+        cmd.extend_from_slice(raster.lines(y, lines));
+
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Graphics command (`GS ( L` or `GS 8 L` if the parameters are too long)
+    fn graphics_data(&self, mut parameters: Vec<u8>) -> Result<Command> {
+        let length = parameters.len();
+        let mut cmd = match u16::try_from(length) {
+            Ok(length) => {
+                let mut cmd = GS_IMAGE_LOW_PREFIX.to_vec();
+                cmd.extend_from_slice(&length.to_le_bytes());
+                cmd
+            }
+            Err(_) => {
+                let mut cmd = GS_IMAGE_HIGH_PREFIX.to_vec();
+                cmd.extend_from_slice(&u32::try_from(length)?.to_le_bytes());
+                cmd
+            }
+        };
+        cmd.append(&mut parameters);
 
         Ok(cmd)
     }
 
+    #[cfg(feature = "graphics")]
+    /// Store NV graphic
+    pub(crate) fn nv_graphic_store(
+        &self,
+        key_code: NvGraphicKeyCode,
+        path: &str,
+        option: BitImageOption,
+    ) -> Result<Command> {
+        let bit_image = BitImage::new(path, option)?;
+        self.build_nv_graphic(key_code, bit_image)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store NV graphic from bytes
+    pub(crate) fn nv_graphic_store_from_bytes(
+        &self,
+        key_code: NvGraphicKeyCode,
+        bytes: &[u8],
+        option: BitImageOption,
+    ) -> Result<Command> {
+        let bit_image = BitImage::from_bytes(bytes, option)?;
+        self.build_nv_graphic(key_code, bit_image)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Define NV graphics data in raster format (function 67)
+    fn build_nv_graphic(&self, key_code: NvGraphicKeyCode, bit_image: BitImage) -> Result<Command> {
+        let width = bit_image.width()?;
+        let height = bit_image.height()?;
+        if !(1..=NV_GRAPHIC_MAX_WIDTH).contains(&u32::from(width))
+            || !(1..=NV_GRAPHIC_MAX_HEIGHT).contains(&u32::from(height))
+        {
+            return Err(PrinterError::Input(format!(
+                "invalid NV graphic size (1-{NV_GRAPHIC_MAX_WIDTH} x 1-{NV_GRAPHIC_MAX_HEIGHT}): {width} x {height}"
+            )));
+        }
+
+        let (kc1, kc2) = key_code.into();
+        // m, fn, a (monochrome), kc1, kc2, b (number of colors)
+        let mut parameters = vec![48, 67, 48, kc1, kc2, 1];
+        parameters.extend_from_slice(&width.to_le_bytes());
+        parameters.extend_from_slice(&height.to_le_bytes());
+        // c (color 1)
+        parameters.push(49);
+        parameters.append(&mut bit_image.raster_data()?);
+
+        self.graphics_data(parameters)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print NV graphic (function 69)
+    pub(crate) fn nv_graphic_print(
+        &self,
+        key_code: NvGraphicKeyCode,
+        width_size: GraphicSize,
+        height_size: GraphicSize,
+    ) -> Command {
+        let mut cmd = GS_IMAGE_NV_PRINT.to_vec();
+        let (kc1, kc2) = key_code.into();
+        cmd.append(&mut vec![kc1, kc2, width_size.into(), height_size.into()]);
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete NV graphic (function 66)
+    pub(crate) fn nv_graphic_delete(&self, key_code: NvGraphicKeyCode) -> Command {
+        let mut cmd = GS_IMAGE_NV_DELETE.to_vec();
+        let (kc1, kc2) = key_code.into();
+        cmd.push(kc1);
+        cmd.push(kc2);
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete all NV graphics (function 65)
+    pub(crate) fn nv_graphics_delete_all(&self) -> Command {
+        GS_IMAGE_NV_DELETE_ALL.to_vec()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Transmit the key code list of NV graphics (function 64)
+    pub(crate) fn nv_graphics_key_codes(&self) -> Command {
+        GS_IMAGE_NV_KEY_CODES.to_vec()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Transmit the NV graphics memory capacity (function 48)
+    pub(crate) fn nv_graphics_capacity(&self) -> Command {
+        GS_IMAGE_NV_CAPACITY.to_vec()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Transmit the remaining capacity of the NV graphics memory (function 51)
+    pub(crate) fn nv_graphics_remaining_capacity(&self) -> Command {
+        GS_IMAGE_NV_REMAINING_CAPACITY.to_vec()
+    }
+
     #[cfg(feature = "ui")]
     pub(crate) fn draw_line(
         &self,
@@ -679,68 +1031,70 @@ impl Protocol {
         Ok(commands)
     }
 
-    // #[cfg(feature = "graphics")]
-    // /// Graphic density
-    // pub(crate) fn graphic_density(&self, density: GraphicDensity) -> Command {
-    //     let mut cmd = GS_IMAGE_DENSITY.to_vec();
-    //     cmd.push(density.into());
-    //     cmd.push(density.into());
-    //     cmd
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // /// Print graphic
-    // pub(crate) fn graphic_print(&self) -> Command {
-    //     GS_IMAGE_PRINT.to_vec()
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // /// Print graphic
-    // pub(crate) fn graphic_data(&self, path: &str) -> Result<Command> {
-    //     let mut cmd = GS_IMAGE_HIGH_PREFIX.to_vec();
-    //
-    //     // pL, pH => Parameters
-    //     let graphic = Graphic::new(path, None)?;
-    //     let (p1, p2, p3, p4) = graphic.data_size()?;
-    //     cmd.push(p1);
-    //     cmd.push(p2);
-    //     cmd.push(p3);
-    //     cmd.push(p4);
-    //     cmd.push(48);
-    //     cmd.push(112);
-    //
-    //     // Tone
-    //     cmd.push(graphic.tone());
-    //
-    //     // bx, by
-    //     cmd.push(1);
-    //     cmd.push(1);
-    //
-    //     // Color
-    //     cmd.push(graphic.color());
-    //
-    //     // Number of dots
-    //     let (xl, xh) = graphic.dots_per_direction(graphic.width() as usize)?;
-    //     let (yl, yh) = graphic.dots_per_direction(graphic.height() as usize)?;
-    //     cmd.push(xl);
-    //     cmd.push(xh);
-    //     cmd.push(yl);
-    //     cmd.push(yh);
-    //
-    //     dbg!(p1, p2, p3, p4, xl, xh, yl, yh);
-    //
-    //     // Image data
-    //     for i in graphic.data()? {
-    //         cmd.push(i);
-    //     }
-    //
-    //     Ok(cmd)
-    // }
+    #[cfg(feature = "graphics")]
+    /// Set the reference dot density for graphics (function 49)
+    pub(crate) fn graphic_density(&self, density: GraphicDensity) -> Command {
+        let mut cmd = GS_IMAGE_DENSITY.to_vec();
+        cmd.push(density.into());
+        cmd.push(density.into());
+        cmd
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print the graphics data in the print buffer (function 50)
+    pub(crate) fn graphic_print(&self) -> Command {
+        GS_IMAGE_PRINT.to_vec()
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store the graphics data of one color in the print buffer (function 112)
+    pub(crate) fn graphic_data(&self, graphic: &Graphic, plane: GraphicPlane) -> Result<Command> {
+        let mut parameters = vec![
+            48,
+            112,
+            plane.tone.into(),
+            graphic.width_size(),
+            graphic.height_size(),
+            plane.color.into(),
+        ];
+        parameters.extend_from_slice(&u16::try_from(graphic.width())?.to_le_bytes());
+        parameters.extend_from_slice(&u16::try_from(graphic.height())?.to_le_bytes());
+        parameters.extend(plane.data);
+
+        self.graphics_data(parameters)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic
+    pub(crate) fn graphic(&self, path: &str, option: GraphicOption) -> Result<Vec<Command>> {
+        let graphic = Graphic::new(path, Some(option))?;
+        self.build_graphic(graphic)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic from bytes
+    pub(crate) fn graphic_from_bytes(&self, bytes: &[u8], option: GraphicOption) -> Result<Vec<Command>> {
+        let graphic = Graphic::from_bytes(bytes, Some(option))?;
+        self.build_graphic(graphic)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the density, store each color in the print buffer and print it
+    fn build_graphic(&self, graphic: Graphic) -> Result<Vec<Command>> {
+        let mut commands = vec![self.graphic_density(graphic.option().density)];
+        for plane in graphic.planes() {
+            commands.push(self.graphic_data(&graphic, plane)?);
+        }
+        commands.push(self.graphic_print());
+
+        Ok(commands)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::BitImageSize;
     #[cfg(feature = "ui")]
     use crate::domain::ui::line::{LineBuilder, LineStyle};
     use alloc::vec::Vec;
@@ -1191,6 +1545,49 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "barcodes")]
+    #[test]
+    fn test_barcode_print_function_b() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol
+                .barcode_print_function_b(BarcodeSystem::CODE128, b"{BNo.{C\x0c\x22")
+                .unwrap(),
+            vec![29, 107, 73, 9, b'{', b'B', b'N', b'o', b'.', b'{', b'C', 12, 34]
+        );
+        assert!(
+            protocol
+                .barcode_print_function_b(BarcodeSystem::CODE128, &[b'a'; 256])
+                .is_err()
+        );
+
+        let commands = protocol
+            .barcode("No.1234", BarcodeSystem::CODE128, BarcodeOption::default())
+            .unwrap();
+        assert_eq!(
+            commands.last().unwrap(),
+            &vec![29, 107, 73, 9, b'{', b'B', b'N', b'o', b'.', b'{', b'C', 12, 34]
+        );
+
+        let commands = protocol
+            .barcode("(10)AB(21)1", BarcodeSystem::GS1_128, BarcodeOption::default())
+            .unwrap();
+        let mut expected = vec![29, 107, 74, 13];
+        expected.extend(b"(10)AB{1(21)1");
+        assert_eq!(commands.last().unwrap(), &expected);
+
+        let commands = protocol
+            .barcode(
+                "0950110153000",
+                BarcodeSystem::GS1DataBarOmnidirectional,
+                BarcodeOption::default(),
+            )
+            .unwrap();
+        let mut expected = vec![29, 107, 75, 13];
+        expected.extend(b"0950110153000");
+        assert_eq!(commands.last().unwrap(), &expected);
+    }
+
     #[cfg(feature = "barcodes")]
     #[test]
     fn test_barcode() {
@@ -1272,13 +1669,13 @@ mod tests {
     fn test_qrcode_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.qrcode_data("test data qrcode").unwrap(),
+            protocol.qrcode_data(b"test data qrcode").unwrap(),
             vec![
                 29, 40, 107, 19, 0, 49, 80, 48, 116, 101, 115, 116, 32, 100, 97, 116, 97, 32, 113, 114, 99, 111, 100,
                 101
             ]
         );
-        assert_eq!(protocol.qrcode_data("").unwrap(), vec![29, 40, 107, 3, 0, 49, 80, 48]);
+        assert_eq!(protocol.qrcode_data(b"").unwrap(), vec![29, 40, 107, 3, 0, 49, 80, 48]);
     }
 
     #[cfg(feature = "codes_2d")]
@@ -1302,7 +1699,7 @@ mod tests {
         assert_eq!(
             protocol
                 .qrcode(
-                    "test",
+                    b"test",
                     QRCodeOption::new(QRCodeModel::Model1, 4, QRCodeCorrectionLevel::L)
                 )
                 .unwrap(),
@@ -1310,6 +1707,24 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_qrcode_size_info() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::L);
+        assert_eq!(
+            protocol.qrcode_size_info(b"test", &option).unwrap(),
+            [
+                &[29, 40, 107, 4, 0, 49, 65, 50, 0][..],
+                &[29, 40, 107, 3, 0, 49, 67, 4],
+                &[29, 40, 107, 3, 0, 49, 69, 48],
+                &[29, 40, 107, 7, 0, 49, 80, 48, 116, 101, 115, 116],
+                &[29, 40, 107, 3, 0, 49, 82, 48],
+            ]
+            .concat()
+        );
+    }
+
     #[cfg(feature = "codes_2d")]
     #[test]
     fn test_gs1_databar_2d_width() {
@@ -1447,7 +1862,7 @@ mod tests {
     fn test_pdf417_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.pdf417_data("test").unwrap(),
+            protocol.pdf417_data(b"test").unwrap(),
             vec![29, 40, 107, 7, 0, 48, 80, 48, 116, 101, 115, 116]
         );
     }
@@ -1465,7 +1880,7 @@ mod tests {
         let protocol = Protocol::new(Encoder::default());
         let option = Pdf417Option::default();
         assert_eq!(
-            protocol.pdf417("test", option).unwrap(),
+            protocol.pdf417(b"test", option).unwrap(),
             vec![
                 vec![29, 40, 107, 3, 0, 48, 65, 0],
                 vec![29, 40, 107, 3, 0, 48, 66, 0],
@@ -1479,6 +1894,17 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_pdf417_size_info() {
+        let protocol = Protocol::new(Encoder::default());
+        let cmd = protocol.pdf417_size_info(b"test", &Pdf417Option::default()).unwrap();
+        assert!(cmd.starts_with(&[29, 40, 107, 3, 0, 48, 65, 0]));
+        assert!(cmd.ends_with(&[
+            29, 40, 107, 7, 0, 48, 80, 48, 116, 101, 115, 116, 29, 40, 107, 3, 0, 48, 82, 48
+        ]));
+    }
+
     #[cfg(feature = "codes_2d")]
     #[test]
     fn test_maxi_code_mode() {
@@ -1514,7 +1940,7 @@ mod tests {
     fn test_maxi_code_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.maxi_code_data("1245").unwrap(),
+            protocol.maxi_code_data(b"1245").unwrap(),
             vec![29, 40, 107, 7, 0, 50, 80, 48, 49, 50, 52, 53]
         );
     }
@@ -1531,7 +1957,7 @@ mod tests {
     fn test_maxi_code() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.maxi_code("1245", MaxiCodeMode::default()).unwrap(),
+            protocol.maxi_code(b"1245", MaxiCodeMode::default()).unwrap(),
             vec![
                 vec![29, 40, 107, 3, 0, 50, 65, 50],
                 vec![29, 40, 107, 7, 0, 50, 80, 48, 49, 50, 52, 53],
@@ -1539,7 +1965,7 @@ mod tests {
             ]
         );
         assert_eq!(
-            protocol.maxi_code("test1245", MaxiCodeMode::default()).unwrap(),
+            protocol.maxi_code(b"test1245", MaxiCodeMode::default()).unwrap(),
             vec![
                 vec![29, 40, 107, 3, 0, 50, 65, 50],
                 vec![29, 40, 107, 11, 0, 50, 80, 48, 116, 101, 115, 116, 49, 50, 52, 53],
@@ -1583,7 +2009,7 @@ mod tests {
     fn test_data_matrix_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.data_matrix_data("test123").unwrap(),
+            protocol.data_matrix_data(b"test123").unwrap(),
             vec![29, 40, 107, 10, 0, 54, 80, 48, 116, 101, 115, 116, 49, 50, 51]
         );
     }
@@ -1600,7 +2026,7 @@ mod tests {
     fn test_data_matrix() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.data_matrix("test123", DataMatrixOption::default()).unwrap(),
+            protocol.data_matrix(b"test123", DataMatrixOption::default()).unwrap(),
             vec![
                 vec![29, 40, 107, 5, 0, 54, 66, 0, 0, 0],
                 vec![29, 40, 107, 3, 0, 54, 67, 3],
@@ -1610,7 +2036,7 @@ mod tests {
         );
         let option = DataMatrixOption::new(DataMatrixType::Rectangle(8, 0), 16).unwrap();
         assert_eq!(
-            protocol.data_matrix("test123", option).unwrap(),
+            protocol.data_matrix(b"test123", option).unwrap(),
             vec![
                 vec![29, 40, 107, 5, 0, 54, 66, 1, 8, 0],
                 vec![29, 40, 107, 3, 0, 54, 67, 16],
@@ -1659,7 +2085,7 @@ mod tests {
     fn test_aztec_data() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.aztec_data("test123").unwrap(),
+            protocol.aztec_data(b"test123").unwrap(),
             vec![29, 40, 107, 10, 0, 53, 80, 48, 116, 101, 115, 116, 49, 50, 51]
         );
     }
@@ -1676,7 +2102,7 @@ mod tests {
     fn test_aztec() {
         let protocol = Protocol::new(Encoder::default());
         assert_eq!(
-            protocol.aztec("test123", AztecOption::default()).unwrap(),
+            protocol.aztec(b"test123", AztecOption::default()).unwrap(),
             vec![
                 vec![29, 40, 107, 4, 0, 53, 66, 0, 0],
                 vec![29, 40, 107, 3, 0, 53, 67, 3],
@@ -1696,12 +2122,13 @@ mod tests {
                 .bit_image(
                     "./resources/images/small.jpg",
                     BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
+                    None,
                 )
                 .unwrap(),
-            vec![
+            vec![vec![
                 29, 118, 48, 0, 2, 0, 16, 0, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 255, 255, 255,
                 255, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128
-            ]
+            ]]
         );
     }
 
@@ -1716,15 +2143,209 @@ mod tests {
                 .bit_image_from_bytes(
                     &bytes,
                     BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
+                    None,
                 )
                 .unwrap(),
-            vec![
+            vec![vec![
                 29, 118, 48, 0, 2, 0, 16, 0, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 255, 255, 255,
                 255, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128
+            ]]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_bit_image_from_pixels() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = || BitImageOption::new(None, None, BitImageSize::default()).unwrap();
+        let expected = vec![vec![29, 118, 48, 0, 2, 0, 1, 0, 0b1010_0000, 0b0100_0000]];
+
+        // 10 x 1: black, white, black, then white except the 10th pixel
+        let luma: Vec<u8> = (0..10)
+            .map(|x| if x == 0 || x == 2 || x == 9 { 0 } else { 255 })
+            .collect();
+        assert_eq!(
+            protocol.bit_image_from_luma(&luma, 10, 1, option(), None).unwrap(),
+            expected
+        );
+
+        let rgba: Vec<u8> = luma.iter().flat_map(|&l| [l, l, l, 255]).collect();
+        assert_eq!(
+            protocol.bit_image_from_rgba(&rgba, 10, 1, option(), None).unwrap(),
+            expected
+        );
+
+        let img = image::DynamicImage::ImageLuma8(image::GrayImage::from_raw(10, 1, luma.clone()).unwrap());
+        assert_eq!(
+            protocol.bit_image_from_dynamic_image(img, option(), None).unwrap(),
+            expected
+        );
+
+        assert!(protocol.bit_image_from_luma(&luma, 10, 2, option(), None).is_err());
+        assert!(protocol.bit_image_from_rgba(&luma, 10, 1, option(), None).is_err());
+    }
+
+    #[test]
+    fn test_raster_bit_image() {
+        let protocol = Protocol::new(Encoder::default());
+        let raster = RasterBitImage::new(10, 2, vec![0xFF, 0xC0, 0x80, 0x40], BitImageSize::DoubleWidth).unwrap();
+        assert_eq!(
+            protocol.raster_bit_image(&raster, BitImageMode::Raster, None, None),
+            vec![vec![29, 118, 48, 1, 2, 0, 2, 0, 0xFF, 0xC0, 0x80, 0x40]]
+        );
+    }
+
+    #[test]
+    fn test_raster_bit_image_bands() {
+        let protocol = Protocol::new(Encoder::default());
+        let raster = RasterBitImage::new(8, 5, vec![1, 2, 3, 4, 5], BitImageSize::Normal).unwrap();
+        assert_eq!(
+            protocol.raster_bit_image(&raster, BitImageMode::Raster, Some(2), None),
+            vec![
+                vec![29, 118, 48, 0, 1, 0, 2, 0, 1, 2],
+                vec![29, 118, 48, 0, 1, 0, 2, 0, 3, 4],
+                vec![29, 118, 48, 0, 1, 0, 1, 0, 5],
+            ]
+        );
+
+        // Taller than 65,535 lines
+        let raster = RasterBitImage::new(8, 70_000, vec![0; 70_000], BitImageSize::Normal).unwrap();
+        let commands = protocol.raster_bit_image(&raster, BitImageMode::Raster, None, None);
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0][..8], [29, 118, 48, 0, 1, 0, 255, 255]);
+        assert_eq!(commands[1][..8], [29, 118, 48, 0, 1, 0, 0x71, 0x11]);
+    }
+
+    #[test]
+    fn test_column_bit_image() {
+        let protocol = Protocol::new(Encoder::default());
+        // 2 x 10 dots: first column black
+        let raster = RasterBitImage::new(2, 10, vec![0x80; 10], BitImageSize::Normal).unwrap();
+        assert_eq!(
+            protocol.raster_bit_image(&raster, BitImageMode::Column8DoubleDensity, None, None),
+            vec![vec![
+                27, 51, 24, 27, 42, 1, 2, 0, 0xFF, 0, 10, 27, 42, 1, 2, 0, 0xC0, 0, 10, 27, 50
+            ]]
+        );
+        assert_eq!(
+            protocol.raster_bit_image(&raster, BitImageMode::Column24SingleDensity, None, None),
+            vec![vec![27, 51, 24, 27, 42, 32, 2, 0, 0xFF, 0xC0, 0, 0, 0, 0, 10, 27, 50]]
+        );
+
+        // Band height rounded up to 8 lines
+        assert_eq!(
+            protocol.raster_bit_image(&raster, BitImageMode::Column8DoubleDensity, Some(5), None),
+            vec![
+                vec![27, 51, 24, 27, 42, 1, 2, 0, 0xFF, 0, 10],
+                vec![27, 42, 1, 2, 0, 0xC0, 0, 10, 27, 50]
+            ]
+        );
+
+        // The line spacing set with `ESC 3` is restored
+        assert_eq!(
+            protocol.raster_bit_image(&raster, BitImageMode::Column24SingleDensity, None, Some(40)),
+            vec![vec![
+                27, 51, 24, 27, 42, 32, 2, 0, 0xFF, 0xC0, 0, 0, 0, 0, 10, 27, 51, 40
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_transmit_paper_sensor_status() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.transmit_paper_sensor_status(), vec![29, 114, 1]);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphics_data() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.graphics_data(vec![48, 50]).unwrap(),
+            vec![29, 40, 76, 2, 0, 48, 50]
+        );
+
+        let cmd = protocol.graphics_data(vec![0; 65_536]).unwrap();
+        assert_eq!(cmd[..7], [29, 56, 76, 0, 0, 1, 0]);
+        assert_eq!(cmd.len(), 65_543);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphic_store() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol
+                .nv_graphic_store(
+                    NvGraphicKeyCode::try_from("LG").unwrap(),
+                    "./resources/images/small.jpg",
+                    BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
+                )
+                .unwrap(),
+            vec![
+                29, 40, 76, 43, 0, 48, 67, 48, 76, 71, 1, 16, 0, 16, 0, 49, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1,
+                128, 1, 128, 255, 255, 255, 255, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128, 1, 128
             ]
         );
     }
 
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphic_store_from_bytes() {
+        let protocol = Protocol::new(Encoder::default());
+        let bytes = std::fs::read("./resources/images/small.jpg").unwrap();
+        let cmd = protocol
+            .nv_graphic_store_from_bytes(
+                NvGraphicKeyCode::try_from("A1").unwrap(),
+                &bytes,
+                BitImageOption::new(None, None, BitImageSize::default()).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(cmd[..16], [29, 40, 76, 43, 0, 48, 67, 48, 65, 49, 1, 16, 0, 16, 0, 49]);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphic_print() {
+        let protocol = Protocol::new(Encoder::default());
+        let key_code = NvGraphicKeyCode::try_from("LG").unwrap();
+        assert_eq!(
+            protocol.nv_graphic_print(key_code, GraphicSize::Normal, GraphicSize::Normal),
+            vec![29, 40, 76, 6, 0, 48, 69, 76, 71, 1, 1]
+        );
+        assert_eq!(
+            protocol.nv_graphic_print(key_code, GraphicSize::Double, GraphicSize::Normal),
+            vec![29, 40, 76, 6, 0, 48, 69, 76, 71, 2, 1]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphic_delete() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.nv_graphic_delete(NvGraphicKeyCode::try_from("LG").unwrap()),
+            vec![29, 40, 76, 4, 0, 48, 66, 76, 71]
+        );
+        assert_eq!(
+            protocol.nv_graphics_delete_all(),
+            vec![29, 40, 76, 5, 0, 48, 65, 67, 76, 82]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphics_requests() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.nv_graphics_key_codes(), vec![29, 40, 76, 4, 0, 48, 64, 75, 67]);
+        assert_eq!(protocol.nv_graphics_capacity(), vec![29, 40, 76, 2, 0, 48, 48]);
+        assert_eq!(
+            protocol.nv_graphics_remaining_capacity(),
+            vec![29, 40, 76, 2, 0, 48, 51]
+        );
+    }
+
     #[cfg(feature = "ui")]
     #[test]
     fn test_draw_line() {
@@ -1752,34 +2373,203 @@ mod tests {
         );
     }
 
-    // #[cfg(feature = "graphics")]
-    // #[test]
-    // fn test_graphic_density() {
-    //     let protocol = Protocol::new(Encoder::default());
-    //     assert_eq!(
-    //         protocol.graphic_density(GraphicDensity::Low),
-    //         vec![29, 40, 76, 4, 0, 48, 49, 50, 50]
-    //     );
-    //     assert_eq!(
-    //         protocol.graphic_density(GraphicDensity::High),
-    //         vec![29, 40, 76, 4, 0, 48, 49, 51, 51]
-    //     );
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // #[test]
-    // fn test_graphic_print() {
-    //     let protocol = Protocol::new(Encoder::default());
-    //     assert_eq!(protocol.graphic_print(), vec![29, 40, 76, 2, 0, 48, 50]);
-    // }
-
-    // #[cfg(feature = "graphics")]
-    // #[test]
-    // fn test_graphic_data() {
-    //     let protocol = Protocol::new(Encoder::default());
-    //     assert_eq!(
-    //         protocol.graphic_data("./resources/images/rust-logo-small.png").unwrap(),
-    //         vec![29, 40, 76, 48, 1, 1, 49, 200, 0, 200, 0]
-    //     );
-    // }
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_density() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(
+            protocol.graphic_density(GraphicDensity::Low),
+            vec![29, 40, 76, 4, 0, 48, 49, 50, 50]
+        );
+        assert_eq!(
+            protocol.graphic_density(GraphicDensity::High),
+            vec![29, 40, 76, 4, 0, 48, 49, 51, 51]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_print() {
+        let protocol = Protocol::new(Encoder::default());
+        assert_eq!(protocol.graphic_print(), vec![29, 40, 76, 2, 0, 48, 50]);
+    }
+
+    #[cfg(feature = "graphics")]
+    fn graphic_test_image() -> Vec<u8> {
+        use image::{ImageFormat, Rgb, RgbImage};
+        use std::io::Cursor;
+
+        // Black, dark gray, light gray, white, red
+        let colors = [[0, 0, 0], [80, 80, 80], [170, 170, 170], [255, 255, 255], [255, 0, 0]];
+        let img = RgbImage::from_fn(10, 1, |x, _| Rgb(colors[(x as usize) % colors.len()]));
+        let mut bytes = Vec::new();
+        img.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png).unwrap();
+        bytes
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_monochrome() {
+        let protocol = Protocol::new(Encoder::default());
+        let commands = protocol
+            .graphic_from_bytes(&graphic_test_image(), GraphicOption::default())
+            .unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                vec![29, 40, 76, 4, 0, 48, 49, 50, 50],
+                vec![
+                    29,
+                    40,
+                    76,
+                    12,
+                    0,
+                    48,
+                    112,
+                    48,
+                    1,
+                    1,
+                    49,
+                    10,
+                    0,
+                    1,
+                    0,
+                    0b1100_1110,
+                    0b0100_0000
+                ],
+                vec![29, 40, 76, 2, 0, 48, 50],
+            ]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_two_colors() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = GraphicOption {
+            density: GraphicDensity::High,
+            second_color: Some(GraphicColor::Color2),
+            width_size: GraphicSize::Double,
+            ..Default::default()
+        };
+        let commands = protocol.graphic_from_bytes(&graphic_test_image(), option).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                vec![29, 40, 76, 4, 0, 48, 49, 51, 51],
+                vec![
+                    29,
+                    40,
+                    76,
+                    12,
+                    0,
+                    48,
+                    112,
+                    48,
+                    2,
+                    1,
+                    49,
+                    10,
+                    0,
+                    1,
+                    0,
+                    0b1100_0110,
+                    0b0000_0000
+                ],
+                vec![
+                    29,
+                    40,
+                    76,
+                    12,
+                    0,
+                    48,
+                    112,
+                    48,
+                    2,
+                    1,
+                    50,
+                    10,
+                    0,
+                    1,
+                    0,
+                    0b0000_1000,
+                    0b0100_0000
+                ],
+                vec![29, 40, 76, 2, 0, 48, 50],
+            ]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_multiple_tone() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = GraphicOption {
+            tone: GraphicTone::Multiple,
+            ..Default::default()
+        };
+        let commands = protocol.graphic_from_bytes(&graphic_test_image(), option).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                vec![29, 40, 76, 4, 0, 48, 49, 50, 50],
+                vec![
+                    29,
+                    40,
+                    76,
+                    12,
+                    0,
+                    48,
+                    112,
+                    52,
+                    1,
+                    1,
+                    49,
+                    10,
+                    0,
+                    1,
+                    0,
+                    0b1100_1110,
+                    0b0100_0000
+                ],
+                vec![
+                    29,
+                    40,
+                    76,
+                    12,
+                    0,
+                    48,
+                    112,
+                    52,
+                    1,
+                    1,
+                    50,
+                    10,
+                    0,
+                    1,
+                    0,
+                    0b1010_0101,
+                    0b0000_0000
+                ],
+                vec![29, 40, 76, 2, 0, 48, 50],
+            ]
+        );
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_graphic_invalid_option() {
+        let protocol = Protocol::new(Encoder::default());
+        let option = GraphicOption {
+            tone: GraphicTone::Multiple,
+            second_color: Some(GraphicColor::Color2),
+            ..Default::default()
+        };
+        assert!(protocol.graphic_from_bytes(&graphic_test_image(), option).is_err());
+        assert!(
+            protocol
+                .graphic("./resources/images/rust-logo.png", GraphicOption::default())
+                .is_ok()
+        );
+    }
 }
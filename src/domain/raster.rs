@@ -0,0 +1,235 @@
+//! Raster bit image
+//!
+//! Monochrome image already packed in raster format (1 bit per dot), printed with `GS v 0`.
+//! It does not depend on the `image` crate and can be used in `no_std` environments.
+
+use crate::errors::{PrinterError, Result};
+#[cfg(not(feature = "std"))]
+use alloc::{format, vec::Vec};
+use core::fmt;
+
+/// Line spacing used between the bands of a column format image
+///
+/// 24 vertical motion units (1/180 inch): 24 dots at 180 dpi or 8 dots at 60 dpi.
+pub(crate) const BIT_IMAGE_COLUMN_LINE_SPACING: u8 = 24;
+
+/// BitImage size
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BitImageSize {
+    #[default]
+    Normal,
+    DoubleWidth,
+    DoubleHeight,
+    DoubleWidthAndHeight,
+}
+
+impl fmt::Display for BitImageSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitImageSize::Normal => write!(f, "Normal"),
+            BitImageSize::DoubleWidth => write!(f, "Double width"),
+            BitImageSize::DoubleHeight => write!(f, "Double height"),
+            BitImageSize::DoubleWidthAndHeight => write!(f, "Double width and height"),
+        }
+    }
+}
+
+impl From<&BitImageSize> for u8 {
+    fn from(size: &BitImageSize) -> Self {
+        match size {
+            BitImageSize::Normal => 0,
+            BitImageSize::DoubleWidth => 1,
+            BitImageSize::DoubleHeight => 2,
+            BitImageSize::DoubleWidthAndHeight => 3,
+        }
+    }
+}
+
+/// Bit image print mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BitImageMode {
+    /// Raster format (`GS v 0`)
+    #[default]
+    Raster,
+    /// Column format (`ESC *`), 8-dot single density
+    Column8SingleDensity,
+    /// Column format (`ESC *`), 8-dot double density
+    Column8DoubleDensity,
+    /// Column format (`ESC *`), 24-dot single density
+    Column24SingleDensity,
+    /// Column format (`ESC *`), 24-dot double density
+    Column24DoubleDensity,
+}
+
+impl BitImageMode {
+    /// Get the `ESC *` mode (`m`) and the number of dots of a column, `None` for the raster format
+    pub(crate) fn column(&self) -> Option<(u8, u16)> {
+        match self {
+            BitImageMode::Raster => None,
+            BitImageMode::Column8SingleDensity => Some((0, 8)),
+            BitImageMode::Column8DoubleDensity => Some((1, 8)),
+            BitImageMode::Column24SingleDensity => Some((32, 24)),
+            BitImageMode::Column24DoubleDensity => Some((33, 24)),
+        }
+    }
+}
+
+impl fmt::Display for BitImageMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitImageMode::Raster => write!(f, "Raster"),
+            BitImageMode::Column8SingleDensity => write!(f, "Column 8-dot single density"),
+            BitImageMode::Column8DoubleDensity => write!(f, "Column 8-dot double density"),
+            BitImageMode::Column24SingleDensity => write!(f, "Column 24-dot single density"),
+            BitImageMode::Column24DoubleDensity => write!(f, "Column 24-dot double density"),
+        }
+    }
+}
+
+/// Raster bit image
+///
+/// Each line is `width.div_ceil(8)` bytes long, the most significant bit is the leftmost dot
+/// and a bit set to `1` is printed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RasterBitImage {
+    /// Width in dots
+    width: u16,
+    /// Height in dots
+    height: u32,
+    /// Raster data
+    data: Vec<u8>,
+    /// Image size
+    size: BitImageSize,
+}
+
+impl RasterBitImage {
+    /// Create a new `RasterBitImage` from packed data
+    ///
+    /// ```
+    /// use escpos::utils::{BitImageSize, RasterBitImage};
+    ///
+    /// // 10 x 2 dots: 2 bytes per line
+    /// let raster = RasterBitImage::new(10, 2, vec![0xFF, 0xC0, 0x80, 0x40], BitImageSize::Normal).unwrap();
+    /// assert_eq!(raster.width_bytes(), 2);
+    ///
+    /// assert!(RasterBitImage::new(10, 2, vec![0xFF, 0xC0], BitImageSize::Normal).is_err());
+    /// ```
+    pub fn new(width: u16, height: u32, data: Vec<u8>, size: BitImageSize) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(PrinterError::Input(format!(
+                "invalid raster bit image size: {width} x {height}"
+            )));
+        }
+
+        let expected = usize::from(width.div_ceil(8)) * height as usize;
+        if data.len() != expected {
+            return Err(PrinterError::Input(format!(
+                "invalid raster bit image data length (expected {expected} bytes): {}",
+                data.len()
+            )));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            data,
+            size,
+        })
+    }
+
+    /// Get the width in dots
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Get the height in dots
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the width in bytes
+    pub fn width_bytes(&self) -> u16 {
+        self.width.div_ceil(8)
+    }
+
+    /// Get the raster data
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the image size
+    pub fn size(&self) -> BitImageSize {
+        self.size
+    }
+
+    /// Get the raster data of `count` lines starting at line `y`
+    pub(crate) fn lines(&self, y: u32, count: u32) -> &[u8] {
+        let line_size = usize::from(self.width_bytes());
+        let start = y as usize * line_size;
+        let end = (y.saturating_add(count).min(self.height) as usize) * line_size;
+        &self.data[start..end]
+    }
+
+    /// Is the dot printed?
+    fn dot(&self, x: u16, y: u32) -> bool {
+        let byte = self.data[y as usize * usize::from(self.width_bytes()) + usize::from(x / 8)];
+        byte & (0x80 >> (x % 8)) != 0
+    }
+
+    /// Get the column format data of the band starting at line `y` (`dots` lines high)
+    ///
+    /// Each column is `dots / 8` bytes long, the most significant bit is the top dot.
+    /// The lines after the end of the image are blank.
+    pub(crate) fn column_band(&self, y: u32, dots: u16) -> Vec<u8> {
+        let mut data = Vec::with_capacity(usize::from(self.width) * usize::from(dots / 8));
+        for x in 0..self.width {
+            for byte_offset in (0..dots).step_by(8) {
+                let mut byte = 0;
+                for bit in 0..8 {
+                    let line = y + u32::from(byte_offset + bit);
+                    if line < self.height && self.dot(x, line) {
+                        byte |= 0x80 >> bit;
+                    }
+                }
+                data.push(byte);
+            }
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_raster_bit_image_new() {
+        let raster = RasterBitImage::new(9, 1, vec![0xFF, 0x80], BitImageSize::Normal).unwrap();
+        assert_eq!((raster.width(), raster.height(), raster.width_bytes()), (9, 1, 2));
+        assert_eq!(raster.data(), &[0xFF, 0x80]);
+
+        let raster = RasterBitImage::new(8, 3, vec![1, 2, 3], BitImageSize::Normal).unwrap();
+        assert_eq!(raster.lines(1, 5), &[2, 3]);
+        assert_eq!(raster.lines(0, 2), &[1, 2]);
+
+        assert!(RasterBitImage::new(0, 1, vec![], BitImageSize::Normal).is_err());
+        assert!(RasterBitImage::new(8, 2, vec![0xFF], BitImageSize::Normal).is_err());
+    }
+
+    #[test]
+    fn test_raster_bit_image_column_band() {
+        // 3 x 10 dots: first column black, diagonal on the second and third columns
+        let mut data = vec![0b1000_0000; 10];
+        data[1] = 0b1100_0000;
+        data[9] = 0b1010_0000;
+        let raster = RasterBitImage::new(3, 10, data, BitImageSize::Normal).unwrap();
+
+        assert_eq!(raster.column_band(0, 8), vec![0xFF, 0b0100_0000, 0]);
+        assert_eq!(raster.column_band(8, 8), vec![0b1100_0000, 0, 0b0100_0000]);
+        assert_eq!(
+            raster.column_band(0, 24),
+            vec![0xFF, 0b1100_0000, 0, 0b0100_0000, 0, 0, 0, 0b0100_0000, 0]
+        );
+    }
+}
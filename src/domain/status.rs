@@ -41,6 +41,28 @@ impl From<RealTimeStatusRequest> for (u8, u8) {
     }
 }
 
+impl TryFrom<(u8, u8)> for RealTimeStatusRequest {
+    type Error = PrinterError;
+
+    /// Get the request from the `n` and `a` parameters of `DLE EOT` (`a` is ignored for `n` from 1 to 4)
+    fn try_from(value: (u8, u8)) -> Result<Self, Self::Error> {
+        match value {
+            (1, _) => Ok(RealTimeStatusRequest::Printer),
+            (2, _) => Ok(RealTimeStatusRequest::OfflineCause),
+            (3, _) => Ok(RealTimeStatusRequest::ErrorCause),
+            (4, _) => Ok(RealTimeStatusRequest::RollPaperSensor),
+            (7, 1) => Ok(RealTimeStatusRequest::InkA),
+            (7, 2) => Ok(RealTimeStatusRequest::InkB),
+            (8, 3) => Ok(RealTimeStatusRequest::Peeler),
+            (18, 1) => Ok(RealTimeStatusRequest::Interface),
+            (18, 2) => Ok(RealTimeStatusRequest::DMD),
+            (n, a) => Err(PrinterError::Input(format!(
+                "invalid real-time status request: {n} {a}"
+            ))),
+        }
+    }
+}
+
 /// Printer real-time status response
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum RealTimeStatusResponse {
@@ -111,57 +133,79 @@ impl RealTimeStatusResponse {
             )));
         }
 
-        let mut result = HashMap::new();
+        Ok(Self::bits(request)
+            .iter()
+            .map(|&(status, mask, value)| (status, response & mask == value))
+            .collect())
+    }
+
+    /// Encode a response, i.e. the inverse of [`RealTimeStatusResponse::parse`]
+    ///
+    /// The bits of the statuses missing in `status` are set to `0`.
+    ///
+    /// ```
+    /// use escpos::utils::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let status = HashMap::from([(RealTimeStatusResponse::Online, false)]);
+    /// let response = RealTimeStatusResponse::encode(RealTimeStatusRequest::Printer, &status);
+    /// assert_eq!(response, 0b00011010);
+    /// ```
+    pub fn encode(request: RealTimeStatusRequest, status: &HashMap<Self, bool>) -> u8 {
+        Self::bits(request)
+            .iter()
+            .fold(0b00010010, |response, &(status_type, mask, value)| {
+                match status.get(&status_type) {
+                    Some(true) => response | value,
+                    Some(false) => response | (mask ^ value),
+                    None => response,
+                }
+            })
+    }
+
+    /// Bits of the statuses of a request: status, bit mask and value of the bits when the status is `true`
+    fn bits(request: RealTimeStatusRequest) -> &'static [(Self, u8, u8)] {
         match request {
-            RealTimeStatusRequest::Printer => {
-                result.insert(Self::DrawerKickOutConnectorPin3Low, binary[2] == 0);
-                result.insert(Self::Online, binary[3] == 0);
-                result.insert(Self::WaitingForOnlineRecovery, binary[5] == 1);
-                result.insert(Self::PaperFeedButtonPressed, binary[6] == 1);
-            }
-            RealTimeStatusRequest::OfflineCause => {
-                result.insert(Self::CoverClosed, binary[2] == 0);
-                result.insert(Self::PaperFedByPaperFeedButton, binary[3] == 1);
-                result.insert(Self::PrintingStopsDueToPaperEnd, binary[5] == 1);
-                result.insert(Self::ErrorOccurred, binary[6] == 1);
-            }
-            RealTimeStatusRequest::ErrorCause => {
-                result.insert(Self::RecoverableErrorOccurred, binary[2] == 1);
-                result.insert(Self::AutocutterErrorOccurred, binary[3] == 1);
-                result.insert(Self::UnrecoverableErrorOccurred, binary[5] == 1);
-                result.insert(Self::AutoRecoverableErrorOccurred, binary[6] == 1);
-            }
-            RealTimeStatusRequest::RollPaperSensor => {
-                result.insert(
-                    Self::RollPaperNearEndSensorPaperAdequate,
-                    binary[2] == 0 && binary[3] == 0,
-                );
-                result.insert(Self::RollPaperEndSensorPaperPresent, binary[5] == 0 && binary[6] == 0);
-            }
-            RealTimeStatusRequest::InkA => {
-                result.insert(Self::InkNearEndDetected, binary[2] == 1);
-                result.insert(Self::InkEndDetected, binary[3] == 1);
-                result.insert(Self::InkCartridgeDetected, binary[5] == 0);
-                result.insert(Self::CleaningPerformed, binary[6] == 1);
-            }
-            RealTimeStatusRequest::InkB => {
-                result.insert(Self::InkNearEndDetected, binary[2] == 1);
-                result.insert(Self::InkEndDetected, binary[3] == 1);
-                result.insert(Self::InkCartridgeDetected, binary[5] == 0);
-            }
-            RealTimeStatusRequest::Peeler => {
-                result.insert(Self::WaitingForLabelToBeRemoved, binary[2] == 1);
-                result.insert(Self::PaperPresentInLabelPeelingDetector, binary[5] == 0);
-            }
-            RealTimeStatusRequest::Interface => {
-                result.insert(Self::PrintingMultipleInterfacesEnabled, binary[2] == 1);
-            }
-            RealTimeStatusRequest::DMD => {
-                result.insert(Self::DMDTransmissionStatusReady, binary[2] == 0);
-            }
+            RealTimeStatusRequest::Printer => &[
+                (Self::DrawerKickOutConnectorPin3Low, 0b0000_0100, 0),
+                (Self::Online, 0b0000_1000, 0),
+                (Self::WaitingForOnlineRecovery, 0b0010_0000, 0b0010_0000),
+                (Self::PaperFeedButtonPressed, 0b0100_0000, 0b0100_0000),
+            ],
+            RealTimeStatusRequest::OfflineCause => &[
+                (Self::CoverClosed, 0b0000_0100, 0),
+                (Self::PaperFedByPaperFeedButton, 0b0000_1000, 0b0000_1000),
+                (Self::PrintingStopsDueToPaperEnd, 0b0010_0000, 0b0010_0000),
+                (Self::ErrorOccurred, 0b0100_0000, 0b0100_0000),
+            ],
+            RealTimeStatusRequest::ErrorCause => &[
+                (Self::RecoverableErrorOccurred, 0b0000_0100, 0b0000_0100),
+                (Self::AutocutterErrorOccurred, 0b0000_1000, 0b0000_1000),
+                (Self::UnrecoverableErrorOccurred, 0b0010_0000, 0b0010_0000),
+                (Self::AutoRecoverableErrorOccurred, 0b0100_0000, 0b0100_0000),
+            ],
+            RealTimeStatusRequest::RollPaperSensor => &[
+                (Self::RollPaperNearEndSensorPaperAdequate, 0b0000_1100, 0),
+                (Self::RollPaperEndSensorPaperPresent, 0b0110_0000, 0),
+            ],
+            RealTimeStatusRequest::InkA => &[
+                (Self::InkNearEndDetected, 0b0000_0100, 0b0000_0100),
+                (Self::InkEndDetected, 0b0000_1000, 0b0000_1000),
+                (Self::InkCartridgeDetected, 0b0010_0000, 0),
+                (Self::CleaningPerformed, 0b0100_0000, 0b0100_0000),
+            ],
+            RealTimeStatusRequest::InkB => &[
+                (Self::InkNearEndDetected, 0b0000_0100, 0b0000_0100),
+                (Self::InkEndDetected, 0b0000_1000, 0b0000_1000),
+                (Self::InkCartridgeDetected, 0b0010_0000, 0),
+            ],
+            RealTimeStatusRequest::Peeler => &[
+                (Self::WaitingForLabelToBeRemoved, 0b0000_0100, 0b0000_0100),
+                (Self::PaperPresentInLabelPeelingDetector, 0b0010_0000, 0),
+            ],
+            RealTimeStatusRequest::Interface => &[(Self::PrintingMultipleInterfacesEnabled, 0b0000_0100, 0b0000_0100)],
+            RealTimeStatusRequest::DMD => &[(Self::DMDTransmissionStatusReady, 0b0000_0100, 0)],
         }
-
-        Ok(result)
     }
 }
 
@@ -265,4 +309,57 @@ mod tests {
         let response = RealTimeStatusResponse::parse(RealTimeStatusRequest::DMD, 0b00010010).unwrap();
         assert_eq!(response[&RealTimeStatusResponse::DMDTransmissionStatusReady], true);
     }
+
+    #[test]
+    fn test_try_from_u8_tuple_to_real_time_status_request() {
+        for request in [
+            RealTimeStatusRequest::Printer,
+            RealTimeStatusRequest::OfflineCause,
+            RealTimeStatusRequest::ErrorCause,
+            RealTimeStatusRequest::RollPaperSensor,
+            RealTimeStatusRequest::InkA,
+            RealTimeStatusRequest::InkB,
+            RealTimeStatusRequest::Peeler,
+            RealTimeStatusRequest::Interface,
+            RealTimeStatusRequest::DMD,
+        ] {
+            let value: (u8, u8) = request.into();
+            let result: (u8, u8) = RealTimeStatusRequest::try_from(value).unwrap().into();
+            assert_eq!(result, value);
+        }
+        assert!(matches!(
+            RealTimeStatusRequest::try_from((1, 5)),
+            Ok(RealTimeStatusRequest::Printer)
+        ));
+        assert!(RealTimeStatusRequest::try_from((7, 0)).is_err());
+        assert!(RealTimeStatusRequest::try_from((5, 0)).is_err());
+    }
+
+    #[test]
+    fn test_encode_real_time_status_response() {
+        for (request, response) in [
+            (RealTimeStatusRequest::Printer, 0b01111110),
+            (RealTimeStatusRequest::OfflineCause, 0b01011110),
+            (RealTimeStatusRequest::RollPaperSensor, 0b00011110),
+            (RealTimeStatusRequest::RollPaperSensor, 0b01110010),
+            (RealTimeStatusRequest::InkA, 0b01011010),
+            (RealTimeStatusRequest::DMD, 0b00010110),
+        ] {
+            let status = RealTimeStatusResponse::parse(request, response).unwrap();
+            assert_eq!(RealTimeStatusResponse::encode(request, &status), response);
+        }
+
+        assert_eq!(
+            RealTimeStatusResponse::encode(RealTimeStatusRequest::Printer, &HashMap::new()),
+            0b00010010
+        );
+        let status = HashMap::from([
+            (RealTimeStatusResponse::RollPaperEndSensorPaperPresent, false),
+            (RealTimeStatusResponse::Online, false),
+        ]);
+        assert_eq!(
+            RealTimeStatusResponse::encode(RealTimeStatusRequest::RollPaperSensor, &status),
+            0b01110010
+        );
+    }
 }
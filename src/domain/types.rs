@@ -61,6 +61,8 @@ pub(crate) struct Instruction {
     pub(crate) name: String,
     pub(crate) commands: Vec<Command>,
     pub(crate) debug_mode: Option<DebugMode>,
+    /// Wait for a response of the printer after sending the commands
+    pub(crate) wait_response: bool,
 }
 
 impl Instruction {
@@ -70,6 +72,15 @@ impl Instruction {
             name: name.to_string(),
             commands: commands.to_vec(),
             debug_mode,
+            wait_response: false,
+        }
+    }
+
+    /// Create a new instruction waiting for a response of the printer
+    pub(crate) fn with_response(name: &str, commands: &[Command], debug_mode: Option<DebugMode>) -> Self {
+        Instruction {
+            wait_response: true,
+            ..Self::new(name, commands, debug_mode)
         }
     }
 
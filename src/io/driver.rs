@@ -1,40 +1,87 @@
 //! Drivers used to send data to the printer (Network or USB)
 
 #[cfg(feature = "std")]
-use crate::errors::PrinterError;
-use crate::errors::Result;
-#[cfg(feature = "std")]
+use crate::domain::{DLE, EOT, ESC, GS, NUL};
+use crate::domain::{Protocol, RealTimeStatusRequest, RealTimeStatusResponse};
+use crate::errors::{PrinterError, Result};
 use alloc::borrow::ToOwned;
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::rc::Rc;
 use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use core::cell::RefCell;
+use core::time::Duration;
 #[cfg(feature = "hidapi")]
 use hidapi::{HidApi, HidDevice};
 #[cfg(feature = "native_usb")]
 use nusb::{MaybeFuture, transfer::EndpointType};
 #[cfg(feature = "usb")]
-use rusb::{Context, DeviceHandle, Direction, TransferType, UsbContext, UsbOption};
+use rusb::{
+    ConfigDescriptor, Context, Device, DeviceDescriptor, DeviceHandle, Direction, TransferType, UsbContext, UsbOption,
+};
 #[cfg(feature = "serial_port")]
-use serialport::SerialPort;
+pub use serialport::{DataBits, Parity, StopBits};
+#[cfg(feature = "serial_port")]
+use serialport::{FlowControl, SerialPort, SerialPortType};
+#[cfg(feature = "tcp_keepalive")]
+use socket2::{SockRef, TcpKeepalive};
+#[cfg(feature = "hidapi")]
+use std::ffi::CString;
+#[cfg(feature = "ble")]
+use std::fmt;
+#[cfg(all(any(feature = "usblp", feature = "bluetooth"), target_os = "linux"))]
+use std::os::fd::AsRawFd;
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+use std::path::PathBuf;
 #[cfg(feature = "std")]
-use std::sync::{Arc, Mutex};
-#[cfg(all(feature = "usbprint", feature = "std", target_os = "windows"))]
-use std::{ffi::OsString, mem, os::windows::ffi::OsStringExt, ptr};
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(feature = "std")]
+use std::time::Instant;
 #[cfg(feature = "std")]
 use std::{
-    fs::File,
-    fs::OpenOptions,
+    collections::{HashMap, VecDeque},
+    fs::{self, File, OpenOptions},
     io::{self, Read, Write},
-    net::{IpAddr, SocketAddr, TcpStream},
+    net::{TcpStream, ToSocketAddrs},
     path::Path,
-    time::Duration,
+    thread,
 };
+#[cfg(all(feature = "usbprint", feature = "std", target_os = "windows"))]
+use std::{ffi::OsString, mem, os::windows::ffi::OsStringExt, ptr};
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+use std::{mem, os::fd::FromRawFd};
 
 /// Default timeout in seconds for read/write operations
 #[cfg(feature = "std")]
 const DEFAULT_TIMEOUT_SECONDS: u64 = 5;
 
+/// Capabilities of a driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriverCapabilities {
+    /// Data can be read from the printer (status and responses)
+    pub bidirectional: bool,
+    /// The timeout given to `read_timeout` is applied (otherwise the driver timeout is used)
+    pub read_timeout: bool,
+    /// `available` returns the number of bytes which can be read (otherwise it returns `0`)
+    pub available: bool,
+}
+
+impl Default for DriverCapabilities {
+    fn default() -> Self {
+        Self {
+            bidirectional: true,
+            read_timeout: false,
+            available: false,
+        }
+    }
+}
+
 /// Printer driver trait
 ///
-/// A custom driver can be implemented by implementing this trait.
+/// A custom driver can be implemented by implementing this trait. The methods take `&self`, so the connection is
+/// usually wrapped in a `Arc<Mutex<_>>` or a `RefCell`: a driver can also implement [`DriverMut`] and be used as a
+/// `Driver` with [`SharedDriver`].
 pub trait Driver {
     /// Driver name
     fn name(&self) -> String;
@@ -45,8 +92,238 @@ pub trait Driver {
     /// Read data
     fn read(&self, buf: &mut [u8]) -> Result<usize>;
 
+    /// Read data, waiting at most `timeout` instead of the driver timeout
+    ///
+    /// The default implementation ignores the timeout and calls [`Driver::read`].
+    fn read_timeout(&self, buf: &mut [u8], _timeout: Duration) -> Result<usize> {
+        self.read(buf)
+    }
+
+    /// Get the number of bytes which can be read without waiting
+    ///
+    /// The default implementation returns `0`.
+    fn available(&self) -> Result<usize> {
+        Ok(0)
+    }
+
     /// Flush data
     fn flush(&self) -> Result<()>;
+
+    /// Flush the data and close the connection
+    ///
+    /// The default implementation only flushes the data.
+    fn close(&self) -> Result<()> {
+        self.flush()
+    }
+
+    /// Driver capabilities
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities::default()
+    }
+}
+
+/// Printer driver trait with mutable access
+///
+/// Unlike [`Driver`], the methods take `&mut self`, so the driver can own its connection without interior mutability.
+/// It is used as a [`Driver`] (for example by a [`Printer`](crate::printer::Printer)) with [`SharedDriver`].
+///
+/// All the [`Driver`] references are also `DriverMut`, so a function taking a `DriverMut` accepts `&driver`.
+pub trait DriverMut {
+    /// Driver name
+    fn name(&self) -> String;
+
+    /// Write data
+    fn write(&mut self, data: &[u8]) -> Result<()>;
+
+    /// Read data
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+    /// Read data, waiting at most `timeout` instead of the driver timeout
+    ///
+    /// The default implementation ignores the timeout and calls [`DriverMut::read`].
+    fn read_timeout(&mut self, buf: &mut [u8], _timeout: Duration) -> Result<usize> {
+        self.read(buf)
+    }
+
+    /// Get the number of bytes which can be read without waiting
+    ///
+    /// The default implementation returns `0`.
+    fn available(&mut self) -> Result<usize> {
+        Ok(0)
+    }
+
+    /// Flush data
+    fn flush(&mut self) -> Result<()>;
+
+    /// Flush the data and close the connection
+    ///
+    /// The default implementation only flushes the data.
+    fn close(&mut self) -> Result<()> {
+        self.flush()
+    }
+
+    /// Driver capabilities
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities::default()
+    }
+}
+
+impl<D: Driver + ?Sized> DriverMut for &D {
+    fn name(&self) -> String {
+        Driver::name(*self)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        Driver::write(*self, data)
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        Driver::read(*self, buf)
+    }
+
+    fn read_timeout(&mut self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        Driver::read_timeout(*self, buf, timeout)
+    }
+
+    fn available(&mut self) -> Result<usize> {
+        Driver::available(*self)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Driver::flush(*self)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        Driver::close(*self)
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        Driver::capabilities(*self)
+    }
+}
+
+// ================ Shared driver ================
+
+/// Driver sharing a [`DriverMut`] to use it as a [`Driver`]
+///
+/// The driver is wrapped in a `Arc<Mutex<_>>` (a `Rc<RefCell<_>>` in `no_std` mode), and the clones share the same
+/// driver.
+///
+/// # Example
+///
+/// ```rust
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::{driver::*, errors::Result};
+///
+/// #[derive(Default)]
+/// struct MemoryDriver {
+///     data: Vec<u8>,
+/// }
+///
+/// impl DriverMut for MemoryDriver {
+///     fn name(&self) -> String {
+///         "memory".to_owned()
+///     }
+///
+///     fn write(&mut self, data: &[u8]) -> Result<()> {
+///         self.data.extend_from_slice(data);
+///         Ok(())
+///     }
+///
+///     fn read(&mut self, _buf: &mut [u8]) -> Result<usize> {
+///         Ok(0)
+///     }
+///
+///     fn flush(&mut self) -> Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// let driver = SharedDriver::new(MemoryDriver::default());
+/// Printer::new(driver.clone(), Protocol::default(), None).init().unwrap().print().unwrap();
+/// assert_eq!(driver.lock().unwrap().data, [0x1B, 0x40]);
+/// ```
+pub struct SharedDriver<D: DriverMut> {
+    #[cfg(feature = "std")]
+    driver: Arc<Mutex<D>>,
+    #[cfg(not(feature = "std"))]
+    driver: Rc<RefCell<D>>,
+}
+
+impl<D: DriverMut> Clone for SharedDriver<D> {
+    fn clone(&self) -> Self {
+        Self {
+            driver: self.driver.clone(),
+        }
+    }
+}
+
+impl<D: DriverMut> SharedDriver<D> {
+    /// Share a driver
+    pub fn new(driver: D) -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            driver: Arc::new(Mutex::new(driver)),
+            #[cfg(not(feature = "std"))]
+            driver: Rc::new(RefCell::new(driver)),
+        }
+    }
+
+    /// Get a mutable access to the driver
+    #[cfg(feature = "std")]
+    pub fn lock(&self) -> Result<std::sync::MutexGuard<'_, D>> {
+        Ok(self.driver.lock()?)
+    }
+
+    /// Get a mutable access to the driver
+    #[cfg(not(feature = "std"))]
+    pub fn lock(&self) -> Result<core::cell::RefMut<'_, D>> {
+        Ok(self.driver.borrow_mut())
+    }
+
+    /// Read the driver, even if a thread panicked while holding the lock (the name and the capabilities stay valid)
+    fn inspect<T>(&self, f: impl FnOnce(&D) -> T) -> T {
+        #[cfg(feature = "std")]
+        let driver = self.driver.lock().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        let driver = self.driver.borrow();
+        f(&driver)
+    }
+}
+
+impl<D: DriverMut> Driver for SharedDriver<D> {
+    fn name(&self) -> String {
+        self.inspect(|driver| driver.name())
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.lock()?.write(data)
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.lock()?.read(buf)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        self.lock()?.read_timeout(buf, timeout)
+    }
+
+    fn available(&self) -> Result<usize> {
+        self.lock()?.available()
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.lock()?.flush()
+    }
+
+    fn close(&self) -> Result<()> {
+        self.lock()?.close()
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        self.inspect(|driver| driver.capabilities())
+    }
 }
 
 // ================ Console driver ================
@@ -97,322 +374,359 @@ impl Driver for ConsoleDriver {
     fn flush(&self) -> Result<()> {
         Ok(())
     }
-}
-
-// ================ Network driver ================
 
-/// Driver for network printer
-#[cfg(feature = "std")]
-#[derive(Clone)]
-pub struct NetworkDriver {
-    host: String,
-    port: u16,
-    stream: Arc<Mutex<TcpStream>>,
-    timeout: Duration,
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            bidirectional: false,
+            ..DriverCapabilities::default()
+        }
+    }
 }
 
-#[cfg(feature = "std")]
-impl NetworkDriver {
-    /// Open the network driver
-    ///
-    /// # Example
+// ================ Chunked driver ================
+
+/// Pacing between the chunks written by [`ChunkedDriver`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkPacing {
+    /// The chunks are written one after another
+    #[default]
+    None,
+    /// Wait a fixed delay between two chunks
+    Delay(Duration),
+    /// Before each write, request the printer status (`DLE EOT 1`) and wait until the printer is online, polling the
+    /// status every `poll` delay at most `attempts` times, then wait `poll` between the chunks of the write
     ///
-    /// ```no_run
-    /// use escpos::printer::Printer;
-    /// use escpos::utils::*;
-    /// use escpos::driver::*;
-    /// use std::time::Duration;
+    /// The status is only requested between two writes, never between the chunks of a write: a `DLE EOT` inside the
+    /// data of a command (`GS v 0` image, `GS ( L` graphics, `GS ( k` 2D code…) would be printed as data. Each
+    /// [`Printer`](crate::printer::Printer) write holds whole commands.
     ///
-    /// let driver = NetworkDriver::open("192.168.1.248", 9100, Some(Duration::from_secs(1))).unwrap();
-    /// let mut printer = Printer::new(driver, Protocol::default(), None);
-    /// ```
-    pub fn open(host: &str, port: u16, timeout: Option<Duration>) -> Result<Self> {
-        let stream = match timeout {
-            Some(timeout) => {
-                let addr = SocketAddr::new(
-                    host.parse::<IpAddr>().map_err(|e| PrinterError::Io(e.to_string()))?,
-                    port,
-                );
-                TcpStream::connect_timeout(&addr, timeout)?
-            }
-            None => TcpStream::connect((host, port))?,
-        };
-        let timeout = timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
+    /// `DLE EOT` does not report the receive buffer state: an online printer can still have a full buffer. The
+    /// status only stops the writes while the printer does not read data (cover open, paper end, error…).
+    Status {
+        /// Delay between two status requests and between two chunks
+        poll: Duration,
+        /// Max number of status requests after the first one
+        attempts: u32,
+    },
+}
 
-        Ok(Self {
-            host: host.to_string(),
-            port,
-            stream: Arc::new(Mutex::new(stream)),
-            timeout,
-        })
-    }
+/// Driver wrapper splitting the writes in chunks, for the printers with a small receive buffer
+///
+/// Each [`Printer`](crate::printer::Printer) instruction is sent with one write (a large image can be several tens
+/// of kilobytes): this wrapper splits it in chunks of a given size, with an optional delay or status based
+/// backpressure between the chunks.
+///
+/// The delays use `std::thread::sleep` with the `std` feature. In `no_std` mode, a sleep function must be provided with
+/// [`ChunkedDriver::sleep`], otherwise the writes needing a delay return an error.
+///
+/// # Example
+///
+/// ```rust
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::driver::*;
+/// use std::time::Duration;
+///
+/// let driver = ChunkedDriver::new(ConsoleDriver::open(false), 4_096).pacing(ChunkPacing::Delay(Duration::from_millis(50)));
+/// let mut printer = Printer::new(driver, Protocol::default(), None);
+/// ```
+#[derive(Clone)]
+pub struct ChunkedDriver<D: Driver> {
+    driver: D,
+    chunk_size: usize,
+    pacing: ChunkPacing,
+    sleep: Option<fn(Duration)>,
 }
 
-#[cfg(feature = "std")]
-impl Driver for NetworkDriver {
-    fn name(&self) -> String {
-        format!("network ({}:{})", self.host, self.port)
+impl<D: Driver> ChunkedDriver<D> {
+    /// Wrap a driver to write chunks of `chunk_size` bytes at most
+    pub fn new(driver: D, chunk_size: usize) -> Self {
+        Self {
+            driver,
+            chunk_size: chunk_size.max(1),
+            pacing: ChunkPacing::None,
+            #[cfg(feature = "std")]
+            sleep: Some(std::thread::sleep),
+            #[cfg(not(feature = "std"))]
+            sleep: None,
+        }
     }
 
-    fn write(&self, data: &[u8]) -> Result<()> {
-        let mut stream = self.stream.lock()?;
-        stream.set_write_timeout(Some(self.timeout))?;
-
-        Ok(stream.write_all(data)?)
+    /// Set the pacing between the chunks
+    pub fn pacing(mut self, pacing: ChunkPacing) -> Self {
+        self.pacing = pacing;
+        self
     }
 
-    fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        let mut stream = self.stream.lock()?;
-        stream.set_read_timeout(Some(self.timeout))?;
+    /// Set the function used to wait between the chunks (required in `no_std` mode to wait)
+    pub fn sleep(mut self, sleep: fn(Duration)) -> Self {
+        self.sleep = Some(sleep);
+        self
+    }
 
-        Ok(stream.read(buf)?)
+    /// Get the wrapped driver
+    pub fn driver(&self) -> &D {
+        &self.driver
     }
 
-    fn flush(&self) -> Result<()> {
-        Ok(self.stream.lock()?.flush()?)
+    /// Release the wrapped driver
+    pub fn into_inner(self) -> D {
+        self.driver
     }
-}
 
-// ================ File driver ================
+    /// Wait for the given duration
+    fn wait(&self, duration: Duration) -> Result<()> {
+        if duration.is_zero() {
+            return Ok(());
+        }
 
-/// Driver for USB printer using file
-#[cfg(feature = "std")]
-#[derive(Clone)]
-pub struct FileDriver {
-    path: String,
-    file: Arc<Mutex<File>>,
-}
+        match self.sleep {
+            Some(sleep) => {
+                sleep(duration);
+                Ok(())
+            }
+            None => Err(PrinterError::Input(
+                "a sleep function is required to wait between the chunks (see ChunkedDriver::sleep)".to_owned(),
+            )),
+        }
+    }
 
-#[cfg(feature = "std")]
-impl FileDriver {
-    /// Open the file driver
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use escpos::printer::Printer;
-    /// use escpos::utils::*;
-    /// use escpos::driver::*;
-    /// use std::path::Path;
-    ///
-    /// let path = Path::new("./foo/bar.txt");
-    /// let driver = FileDriver::open(&path).unwrap();
-    /// let mut printer = Printer::new(driver, Protocol::default(), None);
-    /// ```
-    pub fn open(path: &Path) -> Result<Self> {
-        let file = File::options().read(true).append(true).open(path)?;
-        Ok(Self {
-            path: path.to_string_lossy().to_string(),
-            file: Arc::new(Mutex::new(file)),
-        })
+    /// Wait between two chunks of a write
+    fn wait_chunk(&self) -> Result<()> {
+        match self.pacing {
+            ChunkPacing::None => Ok(()),
+            ChunkPacing::Delay(delay) | ChunkPacing::Status { poll: delay, .. } => self.wait(delay),
+        }
     }
 
-    /// Open the file driver using custom options
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use escpos::printer::Printer;
-    /// use escpos::utils::*;
-    /// use escpos::driver::*;
-    /// use std::fs::OpenOptions;
-    /// use std::path::Path;
-    ///
-    /// let path = Path::new("./foo/bar.txt");
-    /// let mut options = OpenOptions::new();
-    /// options.write(true).create(true).truncate(true);
-    /// let driver = FileDriver::open_with_options(&path, &options).unwrap();
-    /// let mut printer = Printer::new(driver, Protocol::default(), None);
-    /// ```
-    pub fn open_with_options(path: &Path, options: &OpenOptions) -> Result<Self> {
-        let file = options.open(path)?;
-        Ok(Self {
-            path: path.to_string_lossy().to_string(),
-            file: Arc::new(Mutex::new(file)),
-        })
+    /// Wait until the printer is online before a write (only in status mode)
+    fn wait_ready(&self) -> Result<()> {
+        match self.pacing {
+            ChunkPacing::None | ChunkPacing::Delay(_) => Ok(()),
+            ChunkPacing::Status { poll, attempts } => {
+                let request = Protocol::default().real_time_status(RealTimeStatusRequest::Printer);
+                for attempt in 0..=attempts {
+                    if attempt > 0 {
+                        self.wait(poll)?;
+                    }
+
+                    self.driver.write(&request)?;
+                    self.driver.flush()?;
+                    let mut buffer = [0; 1];
+                    if self.driver.read(&mut buffer)? == 0 {
+                        return Err(PrinterError::InvalidResponse(
+                            "no response to the printer status request".to_owned(),
+                        ));
+                    }
+                    let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::Printer, buffer[0])?;
+                    if status.get(&RealTimeStatusResponse::Online) == Some(&true) {
+                        return Ok(());
+                    }
+                }
+
+                Err(PrinterError::Io(
+                    "printer busy: still offline after the status requests".to_owned(),
+                ))
+            }
+        }
     }
 }
 
-#[cfg(feature = "std")]
-impl Driver for FileDriver {
+impl<D: Driver> Driver for ChunkedDriver<D> {
     fn name(&self) -> String {
-        format!("file ({})", self.path)
+        format!("{} (chunks of {} bytes)", self.driver.name(), self.chunk_size)
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        self.file.lock()?.write_all(data)?;
+        if !data.is_empty() {
+            self.wait_ready()?;
+        }
+
+        for (i, chunk) in data.chunks(self.chunk_size).enumerate() {
+            if i > 0 {
+                self.wait_chunk()?;
+            }
+            self.driver.write(chunk)?;
+            if self.pacing != ChunkPacing::None {
+                self.driver.flush()?;
+            }
+        }
+
         Ok(())
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        Ok(self.file.lock()?.read(buf)?)
+        self.driver.read(buf)
     }
 
-    fn flush(&self) -> Result<()> {
-        Ok(self.file.lock()?.flush()?)
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        self.driver.read_timeout(buf, timeout)
     }
-}
 
-// ================ USB drivers ================
+    fn available(&self) -> Result<usize> {
+        self.driver.available()
+    }
 
-/// Driver for USB printer
-#[cfg(feature = "usb")]
-#[derive(Clone)]
-pub struct UsbDriver {
-    vendor_id: u16,
-    product_id: u16,
-    output_endpoint: u8,
-    input_endpoint: u8,
-    device: Arc<Mutex<DeviceHandle<Context>>>,
-    timeout: Duration,
-}
+    fn flush(&self) -> Result<()> {
+        self.driver.flush()
+    }
 
-#[cfg(feature = "usb")]
-impl UsbDriver {
-    /// Open a new USB connection
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use escpos::printer::Printer;
-    /// use escpos::utils::*;
-    /// use escpos::driver::*;
-    ///
-    /// let driver = UsbDriver::open(0x0525, 0xa700, None, None).unwrap();
-    /// let mut printer = Printer::new(driver, Protocol::default(), None);
-    /// ```
-    pub fn open(
-        vendor_id: u16,
-        product_id: u16,
-        timeout: Option<Duration>,
-        options: Option<&[UsbOption]>,
-    ) -> Result<Self> {
-        let context = if let Some(options) = options {
-            Context::with_options(options).map_err(|e| PrinterError::Io(e.to_string()))?
-        } else {
-            Context::new().map_err(|e| PrinterError::Io(e.to_string()))?
-        };
-        let devices = context.devices().map_err(|e| PrinterError::Io(e.to_string()))?;
+    fn close(&self) -> Result<()> {
+        self.driver.close()
+    }
 
-        for device in devices.iter() {
-            let device_descriptor = device
-                .device_descriptor()
-                .map_err(|e| PrinterError::Io(e.to_string()))?;
+    fn capabilities(&self) -> DriverCapabilities {
+        self.driver.capabilities()
+    }
+}
 
-            if device_descriptor.vendor_id() == vendor_id && device_descriptor.product_id() == product_id {
-                let config_descriptor = device
-                    .active_config_descriptor()
-                    .map_err(|e| PrinterError::Io(e.to_string()))?;
+// ================ Tee driver ================
 
-                let (output_endpoint, input_endpoint, interface_number) = config_descriptor
-                    .interfaces()
-                    .flat_map(|interface| interface.descriptors())
-                    .flat_map(|descriptor| {
-                        let interface_number = descriptor.interface_number();
-
-                        // Find input and output endpoints
-                        let mut input_endpoint = None;
-                        let mut output_endpoint = None;
-                        for endpoint in descriptor.endpoint_descriptors() {
-                            if endpoint.transfer_type() == TransferType::Bulk && endpoint.direction() == Direction::In {
-                                input_endpoint = Some(endpoint.address());
-                            } else if endpoint.transfer_type() == TransferType::Bulk
-                                && endpoint.direction() == Direction::Out
-                            {
-                                output_endpoint = Some(endpoint.address());
-                            }
-                        }
+/// Driver writing the data to two drivers at once
+///
+/// The data is read from the first driver only. It can be used to send the print data to a printer and to a file (or
+/// the console) for debugging.
+///
+/// # Example
+///
+/// ```no_run
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::driver::*;
+/// use std::path::Path;
+///
+/// let printer_driver = NetworkDriver::open("192.168.1.248", 9100, None).unwrap();
+/// let file_driver = FileDriver::open(Path::new("./ticket.bin")).unwrap();
+/// let driver = TeeDriver::new(printer_driver, file_driver);
+/// let mut printer = Printer::new(driver, Protocol::default(), None);
+/// ```
+#[derive(Clone)]
+pub struct TeeDriver<A: Driver, B: Driver> {
+    primary: A,
+    secondary: B,
+}
 
-                        match (output_endpoint, input_endpoint) {
-                            (Some(output_endpoint), Some(input_endpoint)) => {
-                                Some((output_endpoint, input_endpoint, interface_number))
-                            }
-                            _ => None,
-                        }
-                    })
-                    .next()
-                    .ok_or_else(|| {
-                        PrinterError::Io("no suitable endpoints or interface number found for USB device".to_string())
-                    })?;
-
-                return match device.open() {
-                    Ok(device_handle) => {
-                        #[cfg(not(target_os = "windows"))]
-                        match device_handle.kernel_driver_active(interface_number) {
-                            Ok(active) => {
-                                if active && let Err(e) = device_handle.detach_kernel_driver(interface_number) {
-                                    return Err(PrinterError::Io(e.to_string()));
-                                }
-                            }
-                            Err(e) => return Err(PrinterError::Io(e.to_string())),
-                        }
+impl<A: Driver, B: Driver> TeeDriver<A, B> {
+    /// Create a driver writing to `primary` and `secondary`, and reading from `primary`
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
 
-                        // Claims device interface
-                        device_handle
-                            .claim_interface(interface_number)
-                            .map_err(|e| PrinterError::Io(e.to_string()))?;
-
-                        Ok(Self {
-                            vendor_id,
-                            product_id,
-                            output_endpoint,
-                            input_endpoint,
-                            device: Arc::new(Mutex::new(device_handle)),
-                            timeout: timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)),
-                        })
-                    }
-                    Err(e) => Err(PrinterError::Io(e.to_string())),
-                };
-            }
-        }
+    /// Get the primary driver
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
 
-        Err(PrinterError::Io("USB device not found".to_string()))
+    /// Get the secondary driver
+    pub fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    /// Release the two drivers
+    pub fn into_inner(self) -> (A, B) {
+        (self.primary, self.secondary)
     }
 }
 
-#[cfg(feature = "usb")]
-impl Driver for UsbDriver {
+impl<A: Driver, B: Driver> Driver for TeeDriver<A, B> {
     fn name(&self) -> String {
-        format!(
-            "USB (VID: {}, PID: {}, output endpoint: {}, input endpoint: {})",
-            self.vendor_id, self.product_id, self.output_endpoint, self.input_endpoint
-        )
+        format!("tee ({}, {})", self.primary.name(), self.secondary.name())
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        self.device
-            .lock()?
-            .write_bulk(self.output_endpoint, data, self.timeout)
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-        Ok(())
+        self.primary.write(data)?;
+        self.secondary.write(data)
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        self.device
-            .lock()?
-            .read_bulk(self.input_endpoint, buf, self.timeout)
-            .map_err(|e| PrinterError::Io(e.to_string()))
+        self.primary.read(buf)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        self.primary.read_timeout(buf, timeout)
+    }
+
+    fn available(&self) -> Result<usize> {
+        self.primary.available()
     }
 
     fn flush(&self) -> Result<()> {
-        Ok(())
+        self.primary.flush()?;
+        self.secondary.flush()
+    }
+
+    fn close(&self) -> Result<()> {
+        self.primary.close()?;
+        self.secondary.close()
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        self.primary.capabilities()
     }
 }
 
-/// Driver for USB printer
-#[cfg(feature = "native_usb")]
+// ================ Recording driver ================
+
+/// Recorded data direction
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecordDirection {
+    /// Data written to the printer (`W`)
+    Write,
+    /// Data read from the printer (`R`)
+    Read,
+}
+
+/// Encode bytes in lowercase hexadecimal
+#[cfg(feature = "std")]
+fn encode_hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Decode hexadecimal bytes
+#[cfg(feature = "std")]
+fn decode_hex(data: &str) -> Option<Vec<u8>> {
+    if !data.is_ascii() || !data.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&data[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Driver recording the data written to and read from another driver in a file
+///
+/// The recording is a text file with one line per write or read:
+///
+/// ```text
+/// # escpos recording: network (192.168.1.248:9100)
+/// 0.000012 W 1b40
+/// 0.000153 W 10040100
+/// 0.021730 R 12
+/// ```
+///
+/// - the first field is the time in seconds since the driver was opened (microsecond precision)
+/// - the second field is the direction: `W` for the data written to the printer, `R` for the data read from it
+/// - the third field is the data in lowercase hexadecimal
+///
+/// The lines starting with `#` and the empty lines are comments. The writes are recorded before being sent to the
+/// driver (the failed writes are also recorded) and the empty reads are not recorded.
+///
+/// A recording can be played back with [`ReplayDriver`].
+#[cfg(feature = "std")]
 #[derive(Clone)]
-pub struct NativeUsbDriver {
-    vendor_id: u16,
-    product_id: u16,
-    output_endpoint: u8,
-    input_endpoint: u8,
-    device: Arc<Mutex<nusb::Interface>>,
+pub struct RecordingDriver<D: Driver> {
+    driver: D,
+    path: String,
+    file: Arc<Mutex<File>>,
+    start: Instant,
 }
 
-#[cfg(feature = "native_usb")]
-impl NativeUsbDriver {
-    /// Open a new USB connection
+#[cfg(feature = "std")]
+impl<D: Driver> RecordingDriver<D> {
+    /// Open the recording driver (the recording file is created or truncated)
     ///
     /// # Example
     ///
@@ -420,141 +734,147 @@ impl NativeUsbDriver {
     /// use escpos::printer::Printer;
     /// use escpos::utils::*;
     /// use escpos::driver::*;
+    /// use std::path::Path;
     ///
-    /// let driver = NativeUsbDriver::open(0x0525, 0xa700).unwrap();
+    /// let driver = NetworkDriver::open("192.168.1.248", 9100, None).unwrap();
+    /// let driver = RecordingDriver::open(driver, Path::new("./printer.rec")).unwrap();
     /// let mut printer = Printer::new(driver, Protocol::default(), None);
     /// ```
-    pub fn open(vendor_id: u16, product_id: u16) -> Result<Self> {
-        let device_info = nusb::list_devices()
-            .wait()
-            .map_err(|e| PrinterError::Io(e.to_string()))?
-            .find(|dev| dev.vendor_id() == vendor_id && dev.product_id() == product_id)
-            .ok_or(PrinterError::Io("USB device not found".to_string()))?;
-        let device = device_info.open().wait().map_err(|e| PrinterError::Io(e.to_string()))?;
-
-        // Get endpoints
-        let configuration = device
-            .active_configuration()
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-
-        let (output_endpoint, input_endpoint) = match configuration.interface_alt_settings().next() {
-            Some(settings) => {
-                let endpoints = settings.endpoints();
-                let (mut output, mut input) = (None, None);
-
-                for endpoint in endpoints {
-                    if endpoint.transfer_type() == nusb::transfer::Bulk::TYPE
-                        && endpoint.direction() == nusb::transfer::Direction::Out
-                    {
-                        output = Some(endpoint.address())
-                    } else if endpoint.transfer_type() == nusb::transfer::Bulk::TYPE
-                        && endpoint.direction() == nusb::transfer::Direction::In
-                    {
-                        input = Some(endpoint.address())
-                    }
-                }
-
-                match (output, input) {
-                    (Some(output), Some(input)) => Some((output, input)),
-                    _ => None,
-                }
-            }
-            None => None,
-        }
-        .ok_or(PrinterError::Io(
-            "no suitable input or output endpoints found for USB device".to_string(),
-        ))?;
-
-        // Get interface number
-        let interface_number = device_info
-            .interfaces()
-            .map(|interface| interface.interface_number())
-            .next()
-            .ok_or_else(|| PrinterError::Io("no suitable interface number found for USB device".to_string()))?;
-
-        let interface = device
-            .detach_and_claim_interface(interface_number)
-            .wait()
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
+    pub fn open(driver: D, path: &Path) -> Result<Self> {
+        let mut file = File::create(path)?;
+        writeln!(file, "# escpos recording: {}", driver.name())?;
 
         Ok(Self {
-            vendor_id,
-            product_id,
-            output_endpoint,
-            input_endpoint,
-            device: Arc::new(Mutex::new(interface)),
+            driver,
+            path: path.to_string_lossy().to_string(),
+            file: Arc::new(Mutex::new(file)),
+            start: Instant::now(),
         })
     }
+
+    /// Get the wrapped driver
+    pub fn driver(&self) -> &D {
+        &self.driver
+    }
+
+    /// Add a line to the recording
+    fn record(&self, direction: RecordDirection, data: &[u8]) -> Result<()> {
+        let direction = match direction {
+            RecordDirection::Write => 'W',
+            RecordDirection::Read => 'R',
+        };
+        writeln!(
+            self.file.lock()?,
+            "{:.6} {direction} {}",
+            self.start.elapsed().as_secs_f64(),
+            encode_hex(data)
+        )?;
+        Ok(())
+    }
 }
 
-#[cfg(feature = "native_usb")]
-impl Driver for NativeUsbDriver {
+#[cfg(feature = "std")]
+impl<D: Driver> Driver for RecordingDriver<D> {
     fn name(&self) -> String {
-        format!(
-            "USB (VID: {}, PID: {}, output endpoint: {}, input endpoint: {})",
-            self.vendor_id, self.product_id, self.output_endpoint, self.input_endpoint
-        )
+        format!("{} (recorded to {})", self.driver.name(), self.path)
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        let endpoint = self
-            .device
-            .lock()?
-            .endpoint::<nusb::transfer::Bulk, nusb::transfer::Out>(self.output_endpoint)
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-
-        let max_size = endpoint.max_packet_size();
-
-        let mut writer = endpoint
-            .writer(max_size)
-            .with_write_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
-
-        writer.write_all(data).map_err(|e| PrinterError::Io(e.to_string()))?;
-        writer.flush().map_err(|e| PrinterError::Io(e.to_string()))?;
-        Ok(())
+        if !data.is_empty() {
+            self.record(RecordDirection::Write, data)?;
+        }
+        self.driver.write(data)
     }
 
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        let endpoint = self
-            .device
-            .lock()?
-            .endpoint::<nusb::transfer::Bulk, nusb::transfer::In>(self.input_endpoint)
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
+        let size = self.driver.read(buf)?;
+        if size > 0 {
+            self.record(RecordDirection::Read, &buf[..size])?;
+        }
+        Ok(size)
+    }
 
-        let max_size = endpoint.max_packet_size();
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        let size = self.driver.read_timeout(buf, timeout)?;
+        if size > 0 {
+            self.record(RecordDirection::Read, &buf[..size])?;
+        }
+        Ok(size)
+    }
 
-        let mut reader = endpoint
-            .reader(max_size)
-            .with_read_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
+    fn available(&self) -> Result<usize> {
+        self.driver.available()
+    }
 
-        let mut pkt_reader = reader.until_short_packet();
-        let size = pkt_reader
-            .read_to_end(&mut buf.to_vec())
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-        pkt_reader.consume_end().map_err(|e| PrinterError::Io(e.to_string()))?;
+    fn flush(&self) -> Result<()> {
+        self.driver.flush()?;
+        Ok(self.file.lock()?.flush()?)
+    }
 
-        Ok(size)
+    fn close(&self) -> Result<()> {
+        self.driver.close()?;
+        Ok(self.file.lock()?.flush()?)
     }
 
-    fn flush(&self) -> Result<()> {
-        Ok(())
+    fn capabilities(&self) -> DriverCapabilities {
+        self.driver.capabilities()
     }
 }
 
-// ================ HidApi driver ================
+// ================ Replay driver ================
 
-/// Driver for USB printer
-#[cfg(feature = "hidapi")]
+/// Replay progress
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct ReplayState {
+    /// Index of the next recorded event
+    event: usize,
+    /// Bytes of the current recorded write already written
+    offset: usize,
+    /// Recorded bytes available for the reads
+    pending: VecDeque<u8>,
+}
+
+/// Driver playing back a recording of [`RecordingDriver`]
+///
+/// The recorded reads are returned once the recorded writes preceding them have been written (the writes don't need to
+/// be split as in the recording), so a status round-trip can be replayed without a printer. The reads return `0` when
+/// no recorded data is available.
+///
+/// By default, the written data is not compared to the recording. In strict mode, an error is returned when the data
+/// differs from the recording or when more data than recorded is written.
+///
+/// # Example
+///
+/// ```rust
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::driver::*;
+///
+/// let recording = "0.000100 W 10040100\n0.015000 R 12\n";
+/// let driver = ReplayDriver::from_recording(recording).unwrap().strict(true);
+/// Printer::new(driver.clone(), Protocol::default(), None)
+///     .real_time_status(RealTimeStatusRequest::Printer)
+///     .unwrap()
+///     .send_status()
+///     .unwrap();
+///
+/// let mut buf = [0; 1];
+/// driver.read(&mut buf).unwrap();
+/// let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::Printer, buf[0]).unwrap();
+/// assert_eq!(status.get(&RealTimeStatusResponse::Online), Some(&true));
+/// ```
+#[cfg(feature = "std")]
 #[derive(Clone)]
-pub struct HidApiDriver {
-    vendor_id: u16,
-    product_id: u16,
-    device: Arc<Mutex<HidDevice>>,
+pub struct ReplayDriver {
+    events: Arc<Vec<(RecordDirection, Vec<u8>)>>,
+    state: Arc<Mutex<ReplayState>>,
+    strict: bool,
 }
 
-#[cfg(feature = "hidapi")]
-impl HidApiDriver {
-    /// Open a new USB connection
+#[cfg(feature = "std")]
+impl ReplayDriver {
+    /// Open a recording file
     ///
     /// # Example
     ///
@@ -562,63 +882,2455 @@ impl HidApiDriver {
     /// use escpos::printer::Printer;
     /// use escpos::utils::*;
     /// use escpos::driver::*;
+    /// use std::path::Path;
     ///
-    /// let driver = HidApiDriver::open(0x0525, 0xa700).unwrap();
+    /// let driver = ReplayDriver::open(Path::new("./printer.rec")).unwrap();
     /// let mut printer = Printer::new(driver, Protocol::default(), None);
     /// ```
-    pub fn open(vendor_id: u16, product_id: u16) -> Result<Self> {
-        let api = HidApi::new().map_err(|e| PrinterError::Io(e.to_string()))?;
-        let device = api
-            .open(vendor_id, product_id)
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-
-        Ok(Self {
-            vendor_id,
-            product_id,
-            device: Arc::new(Mutex::new(device)),
-        })
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::from_recording(&fs::read_to_string(path)?)
     }
-}
 
-#[cfg(feature = "hidapi")]
-impl Driver for HidApiDriver {
-    fn name(&self) -> String {
-        format!("HidApi (VID: {}, PID: {})", self.vendor_id, self.product_id)
+    /// Create the driver from the content of a recording
+    pub fn from_recording(recording: &str) -> Result<Self> {
+        let mut events = Vec::new();
+        for (number, line) in recording.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let event = match fields.as_slice() {
+                [time, direction, data] if time.parse::<f64>().is_ok() => {
+                    let direction = match *direction {
+                        "W" => Some(RecordDirection::Write),
+                        "R" => Some(RecordDirection::Read),
+                        _ => None,
+                    };
+                    direction.zip(decode_hex(data))
+                }
+                _ => None,
+            };
+            let event =
+                event.ok_or_else(|| PrinterError::Input(format!("invalid recording line {}: {line}", number + 1)))?;
+            events.push(event);
+        }
+
+        let driver = Self {
+            events: Arc::new(events),
+            state: Arc::new(Mutex::new(ReplayState::default())),
+            strict: false,
+        };
+        driver.release_reads(&mut *driver.state.lock()?);
+        Ok(driver)
     }
 
-    fn write(&self, data: &[u8]) -> Result<()> {
-        self.device
-            .lock()?
-            .write(data)
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-        Ok(())
+    /// Compare the written data to the recording
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
-    fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        self.device
-            .lock()?
-            .read_timeout(buf, i32::try_from(DEFAULT_TIMEOUT_SECONDS * 1_000)?)
-            .map_err(|e| PrinterError::Io(e.to_string()))
+    /// Check if all the recorded writes and reads have been played back
+    pub fn is_finished(&self) -> Result<bool> {
+        let state = self.state.lock()?;
+        Ok(state.event >= self.events.len() && state.pending.is_empty())
     }
 
-    fn flush(&self) -> Result<()> {
+    /// Make the recorded reads following the current position available
+    fn release_reads(&self, state: &mut ReplayState) {
+        while let Some((RecordDirection::Read, data)) = self.events.get(state.event) {
+            state.pending.extend(data);
+            state.event += 1;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Driver for ReplayDriver {
+    fn name(&self) -> String {
+        "replay".to_owned()
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        let mut state = self.state.lock()?;
+        let mut data = data;
+        while !data.is_empty() {
+            let Some((_, expected)) = self.events.get(state.event) else {
+                if self.strict {
+                    return Err(PrinterError::Io(
+                        "replay: write after the end of the recording".to_owned(),
+                    ));
+                }
+                break;
+            };
+
+            let size = (expected.len() - state.offset).min(data.len());
+            if self.strict && expected[state.offset..state.offset + size] != data[..size] {
+                return Err(PrinterError::Io(format!(
+                    "replay: written data differs from the recording (write {})",
+                    state.event + 1
+                )));
+            }
+            data = &data[size..];
+            state.offset += size;
+
+            if state.offset == expected.len() {
+                state.event += 1;
+                state.offset = 0;
+                self.release_reads(&mut state);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let mut state = self.state.lock()?;
+        let size = buf.len().min(state.pending.len());
+        for (byte, data) in buf.iter_mut().zip(state.pending.drain(..size)) {
+            *byte = data;
+        }
+        Ok(size)
+    }
+
+    fn available(&self) -> Result<usize> {
+        Ok(self.state.lock()?.pending.len())
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            available: true,
+            ..DriverCapabilities::default()
+        }
+    }
+}
+
+// ================ Simulated driver ================
+
+/// Printer information returned by [`SimulatedDriver`] to the `GS I` requests
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulatedPrinterInfo {
+    /// Printer model ID (`GS I 1`)
+    pub model_id: u8,
+    /// Type ID (`GS I 2`)
+    pub type_id: u8,
+    /// Version ID (`GS I 3`)
+    pub version_id: u8,
+    /// Firmware version (`GS I 65`)
+    pub firmware_version: String,
+    /// Manufacturer name (`GS I 66`)
+    pub manufacturer: String,
+    /// Model name (`GS I 67`)
+    pub model: String,
+    /// Serial number (`GS I 68`)
+    pub serial_number: String,
+    /// Font of language (`GS I 69`)
+    pub font: String,
+}
+
+#[cfg(feature = "std")]
+impl Default for SimulatedPrinterInfo {
+    fn default() -> Self {
+        Self {
+            model_id: 0x20,
+            type_id: 0x02,
+            version_id: 0x40,
+            firmware_version: "1.00".to_owned(),
+            manufacturer: "ESC/POS".to_owned(),
+            model: "Simulated printer".to_owned(),
+            serial_number: "0000000000".to_owned(),
+            font: "PC437".to_owned(),
+        }
+    }
+}
+
+/// Simulated printer state
+#[cfg(feature = "std")]
+struct SimulatedState {
+    status: HashMap<RealTimeStatusResponse, bool>,
+    info: SimulatedPrinterInfo,
+    /// Data accepted by the printer
+    written: Vec<u8>,
+    /// Incomplete request at the end of the last write
+    input: Vec<u8>,
+    /// Number of data bytes of the current command (bit image, graphics, 2D code or barcode) not received yet
+    data_size: usize,
+    /// Responses to the requests, not read yet
+    responses: VecDeque<u8>,
+    /// Number of bytes which can be written before a write failure
+    write_limit: Option<usize>,
+    read_timeout: bool,
+    connected: bool,
+}
+
+#[cfg(feature = "std")]
+impl SimulatedState {
+    /// Append the written data and answer the requests
+    ///
+    /// The real-time requests (`DLE EOT`) are processed wherever they are, like a printer does, even in the data of
+    /// the other commands. The other requests (`GS r` and `GS I`) are only processed at a command boundary: the data
+    /// of the length-prefixed commands (`GS v 0`, `ESC *`, `GS ( ...`, `GS 8 L` and `GS k` function B) is skipped.
+    fn receive(&mut self, data: &[u8]) {
+        self.written.extend_from_slice(data);
+        self.input.extend_from_slice(data);
+
+        let mut position = 0;
+        while position < self.input.len() {
+            let Some(request_size) = self.answer_real_time_request(position) else {
+                break;
+            };
+            if self.data_size > 0 {
+                self.data_size -= 1;
+                position += 1;
+                continue;
+            }
+            if request_size > 0 {
+                position += request_size;
+                continue;
+            }
+
+            let length = |low: u8, high: u8| usize::from(u16::from_le_bytes([low, high]));
+            let size = match self.input[position..] {
+                [GS, b'r', n, ..] => {
+                    self.answer_transmit_status(n);
+                    3
+                }
+                [GS, b'I', n, ..] => {
+                    self.answer_printer_id(n);
+                    3
+                }
+                [GS, b'v', b'0', _, x_low, x_high, y_low, y_high, ..] => {
+                    self.data_size = length(x_low, x_high) * length(y_low, y_high);
+                    8
+                }
+                [ESC, b'*', m, n_low, n_high, ..] => {
+                    self.data_size = length(n_low, n_high) * if m >= 32 { 3 } else { 1 };
+                    5
+                }
+                [GS, b'(', _, p_low, p_high, ..] => {
+                    self.data_size = length(p_low, p_high);
+                    5
+                }
+                [GS, b'8', b'L', p1, p2, p3, p4, ..] => {
+                    self.data_size = u32::from_le_bytes([p1, p2, p3, p4]) as usize;
+                    7
+                }
+                [GS, b'k', 65..=255, n, ..] => {
+                    self.data_size = usize::from(n);
+                    4
+                }
+                // Incomplete commands
+                [GS] | [ESC] | [GS, b'r' | b'I' | b'k'] | [GS, b'k', 65..=255] | [GS, b'8'] => break,
+                [GS, b'v', b'0', ..] | [GS, b'v'] | [ESC, b'*', ..] | [GS, b'(', ..] | [GS, b'8', b'L', ..] => break,
+                _ => 1,
+            };
+            position += size;
+        }
+        self.input.drain(..position.min(self.input.len()));
+    }
+
+    /// Answer the `DLE EOT` request at the position of the input and return its size
+    /// (`0` if there is no request, `None` if the request is incomplete)
+    fn answer_real_time_request(&mut self, position: usize) -> Option<usize> {
+        match self.input[position..] {
+            [DLE, EOT, n, ref rest @ ..] => match n {
+                7 | 8 | 18 => {
+                    let a = *rest.first()?;
+                    self.answer_real_time_status(n, a);
+                    Some(4)
+                }
+                _ => {
+                    self.answer_real_time_status(n, 0);
+                    Some(3)
+                }
+            },
+            [DLE] | [DLE, EOT] => None,
+            _ => Some(0),
+        }
+    }
+
+    /// Answer `DLE EOT n a` (the unknown requests are ignored)
+    fn answer_real_time_status(&mut self, n: u8, a: u8) {
+        if let Ok(request) = RealTimeStatusRequest::try_from((n, a)) {
+            self.responses
+                .push_back(RealTimeStatusResponse::encode(request, &self.status));
+        }
+    }
+
+    /// Answer `GS r n`: paper sensor (1) or drawer kick-out connector (2) status
+    fn answer_transmit_status(&mut self, n: u8) {
+        let status = |response| self.status.get(&response) == Some(&true);
+        let response = match n {
+            1 | 49 => {
+                let near_end = if status(RealTimeStatusResponse::RollPaperNearEndSensorPaperAdequate) {
+                    0
+                } else {
+                    0b0000_0011
+                };
+                let end = if status(RealTimeStatusResponse::RollPaperEndSensorPaperPresent) {
+                    0
+                } else {
+                    0b0000_1100
+                };
+                near_end | end
+            }
+            2 | 50 => u8::from(!status(RealTimeStatusResponse::DrawerKickOutConnectorPin3Low)),
+            4 | 52 => 0,
+            _ => return,
+        };
+        self.responses.push_back(response);
+    }
+
+    /// Answer `GS I n`: 1 byte IDs or `_` + information + `NUL`
+    fn answer_printer_id(&mut self, n: u8) {
+        let info = &self.info;
+        let text = match n {
+            1 | 49 => return self.responses.push_back(info.model_id),
+            2 | 50 => return self.responses.push_back(info.type_id),
+            3 | 51 => return self.responses.push_back(info.version_id),
+            65 => &info.firmware_version,
+            66 => &info.manufacturer,
+            67 => &info.model,
+            68 => &info.serial_number,
+            69 => &info.font,
+            _ => return,
+        };
+        let mut response = vec![b'_'];
+        response.extend_from_slice(text.as_bytes());
+        response.push(NUL);
+        self.responses.extend(response);
+    }
+
+    /// Update the online status from the offline causes
+    fn update_online(&mut self) {
+        let offline = self.status.get(&RealTimeStatusResponse::CoverClosed) == Some(&false)
+            || self.status.get(&RealTimeStatusResponse::PrintingStopsDueToPaperEnd) == Some(&true)
+            || self.status.get(&RealTimeStatusResponse::ErrorOccurred) == Some(&true);
+        self.status.insert(RealTimeStatusResponse::Online, !offline);
+    }
+
+    /// Check the connection
+    fn check_connected(&self) -> Result<()> {
+        if self.connected {
+            Ok(())
+        } else {
+            Err(io::Error::from(io::ErrorKind::NotConnected).into())
+        }
+    }
+}
+
+/// Driver simulating a printer, to test the status requests and the error handling without a printer
+///
+/// The simulated printer answers the real-time status requests (`DLE EOT`), the transmit status requests (`GS r`) and
+/// the printer ID requests (`GS I`) found in the written data, with the configured status
+/// ([`RealTimeStatusResponse`]) and information ([`SimulatedPrinterInfo`]). The status is ready by default (online,
+/// cover closed, paper present and no error). Like a printer, the real-time requests are processed anywhere in the
+/// data, while the other requests are only processed between commands: they are not searched in the data of the bit
+/// images, graphics, 2D codes and barcodes.
+///
+/// The failures are scripted at runtime: a write failure after a number of bytes, read timeouts or a disconnection.
+/// The driver can be cloned to keep a handle on the simulated printer after passing it to a
+/// [`Printer`](crate::printer::Printer).
+///
+/// # Example
+///
+/// ```rust
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use escpos::driver::*;
+///
+/// let driver = SimulatedDriver::new();
+/// driver.set_paper_end(true).unwrap();
+///
+/// Printer::new(driver.clone(), Protocol::default(), None)
+///     .real_time_status(RealTimeStatusRequest::RollPaperSensor)
+///     .unwrap()
+///     .send_status()
+///     .unwrap();
+///
+/// let mut buf = [0; 1];
+/// driver.read(&mut buf).unwrap();
+/// let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::RollPaperSensor, buf[0]).unwrap();
+/// assert_eq!(status.get(&RealTimeStatusResponse::RollPaperEndSensorPaperPresent), Some(&false));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct SimulatedDriver {
+    state: Arc<Mutex<SimulatedState>>,
+}
+
+#[cfg(feature = "std")]
+impl Default for SimulatedDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl SimulatedDriver {
+    /// Create a simulated printer, ready to print
+    pub fn new() -> Self {
+        Self::with_info(SimulatedPrinterInfo::default())
+    }
+
+    /// Create a simulated printer with custom information, ready to print
+    pub fn with_info(info: SimulatedPrinterInfo) -> Self {
+        let status = [
+            RealTimeStatusRequest::Printer,
+            RealTimeStatusRequest::OfflineCause,
+            RealTimeStatusRequest::ErrorCause,
+            RealTimeStatusRequest::RollPaperSensor,
+            RealTimeStatusRequest::InkA,
+            RealTimeStatusRequest::Peeler,
+            RealTimeStatusRequest::Interface,
+            RealTimeStatusRequest::DMD,
+        ]
+        .into_iter()
+        .flat_map(|request| RealTimeStatusResponse::parse(request, 0b0001_0010).unwrap_or_default())
+        .collect();
+
+        Self {
+            state: Arc::new(Mutex::new(SimulatedState {
+                status,
+                info,
+                written: Vec::new(),
+                input: Vec::new(),
+                data_size: 0,
+                responses: VecDeque::new(),
+                write_limit: None,
+                read_timeout: false,
+                connected: true,
+            })),
+        }
+    }
+
+    /// Set a status returned to the status requests
+    pub fn set_status(&self, status: RealTimeStatusResponse, value: bool) -> Result<()> {
+        self.state.lock()?.status.insert(status, value);
+        Ok(())
+    }
+
+    /// Get a status returned to the status requests
+    pub fn status(&self, status: RealTimeStatusResponse) -> Result<bool> {
+        Ok(self.state.lock()?.status.get(&status) == Some(&true))
+    }
+
+    /// Simulate the end of the paper roll (the printer goes offline)
+    pub fn set_paper_end(&self, paper_end: bool) -> Result<()> {
+        let mut state = self.state.lock()?;
+        state.status.extend([
+            (RealTimeStatusResponse::RollPaperNearEndSensorPaperAdequate, !paper_end),
+            (RealTimeStatusResponse::RollPaperEndSensorPaperPresent, !paper_end),
+            (RealTimeStatusResponse::PrintingStopsDueToPaperEnd, paper_end),
+        ]);
+        state.update_online();
+        Ok(())
+    }
+
+    /// Simulate an open cover (the printer goes offline)
+    pub fn set_cover_open(&self, cover_open: bool) -> Result<()> {
+        let mut state = self.state.lock()?;
+        state.status.insert(RealTimeStatusResponse::CoverClosed, !cover_open);
+        state.update_online();
+        Ok(())
+    }
+
+    /// Make the writes fail once `bytes` more bytes have been written (`None` to disable the failure)
+    ///
+    /// The bytes written before the failure are accepted by the printer.
+    pub fn fail_writes_after(&self, bytes: Option<usize>) -> Result<()> {
+        self.state.lock()?.write_limit = bytes;
+        Ok(())
+    }
+
+    /// Make the reads time out
+    pub fn set_read_timeout(&self, read_timeout: bool) -> Result<()> {
+        self.state.lock()?.read_timeout = read_timeout;
+        Ok(())
+    }
+
+    /// Simulate a disconnection: all the operations fail until [`SimulatedDriver::reconnect`] is called
+    pub fn disconnect(&self) -> Result<()> {
+        let mut state = self.state.lock()?;
+        state.connected = false;
+        state.input.clear();
+        state.responses.clear();
+        Ok(())
+    }
+
+    /// Reconnect the printer after [`SimulatedDriver::disconnect`]
+    pub fn reconnect(&self) -> Result<()> {
+        self.state.lock()?.connected = true;
+        Ok(())
+    }
+
+    /// Get the data accepted by the printer
+    pub fn written(&self) -> Result<Vec<u8>> {
+        Ok(self.state.lock()?.written.clone())
+    }
+
+    /// Clear the data accepted by the printer
+    pub fn clear_written(&self) -> Result<()> {
+        self.state.lock()?.written.clear();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Driver for SimulatedDriver {
+    fn name(&self) -> String {
+        "simulated".to_owned()
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        let mut state = self.state.lock()?;
+        state.check_connected()?;
+
+        match state.write_limit {
+            Some(limit) if data.len() > limit => {
+                state.receive(&data[..limit]);
+                state.write_limit = Some(0);
+                Err(PrinterError::Io("simulated write failure".to_owned()))
+            }
+            limit => {
+                state.write_limit = limit.map(|limit| limit - data.len());
+                state.receive(data);
+                Ok(())
+            }
+        }
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let mut state = self.state.lock()?;
+        state.check_connected()?;
+        if state.read_timeout {
+            return Err(io::Error::from(io::ErrorKind::TimedOut).into());
+        }
+
+        let size = buf.len().min(state.responses.len());
+        for (byte, data) in buf.iter_mut().zip(state.responses.drain(..size)) {
+            *byte = data;
+        }
+        Ok(size)
+    }
+
+    fn available(&self) -> Result<usize> {
+        let state = self.state.lock()?;
+        state.check_connected()?;
+        Ok(state.responses.len())
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.state.lock()?.check_connected()
+    }
+
+    /// Close the connection, like [`SimulatedDriver::disconnect`]
+    fn close(&self) -> Result<()> {
+        self.disconnect()
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            available: true,
+            ..DriverCapabilities::default()
+        }
+    }
+}
+
+// ================ Network driver ================
+
+/// Default delay in milliseconds before the first reconnection attempt
+#[cfg(feature = "std")]
+const DEFAULT_RECONNECT_DELAY_MILLISECONDS: u64 = 500;
+
+/// Default max delay in seconds between two reconnection attempts
+#[cfg(feature = "std")]
+const DEFAULT_RECONNECT_MAX_DELAY_SECONDS: u64 = 5;
+
+/// Default number of reconnection attempts
+#[cfg(feature = "std")]
+const DEFAULT_RECONNECT_ATTEMPTS: u32 = 3;
+
+/// Network driver options
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkOptions {
+    connect_timeout: Option<Duration>,
+    timeout: Duration,
+    reconnect_attempts: u32,
+    reconnect_delay: Duration,
+    reconnect_max_delay: Duration,
+    #[cfg(feature = "tcp_keepalive")]
+    keepalive: Option<Duration>,
+    nodelay: bool,
+    lazy: bool,
+}
+
+#[cfg(feature = "std")]
+impl Default for NetworkOptions {
+    /// Default network options
+    ///
+    /// No connection timeout, 5s read / write timeout, 3 reconnection attempts (500ms delay doubled up to 5s),
+    /// no TCP keepalive, Nagle algorithm enabled and connection when the driver is opened.
+    fn default() -> Self {
+        Self {
+            connect_timeout: None,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+            reconnect_attempts: DEFAULT_RECONNECT_ATTEMPTS,
+            reconnect_delay: Duration::from_millis(DEFAULT_RECONNECT_DELAY_MILLISECONDS),
+            reconnect_max_delay: Duration::from_secs(DEFAULT_RECONNECT_MAX_DELAY_SECONDS),
+            #[cfg(feature = "tcp_keepalive")]
+            keepalive: None,
+            nodelay: false,
+            lazy: false,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl NetworkOptions {
+    /// Set the connection timeout (`None` to use the system timeout)
+    pub fn connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the read and write timeout (must not be zero)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the number of reconnection attempts when the connection is lost (`0` to disable the reconnection)
+    pub fn reconnect_attempts(mut self, attempts: u32) -> Self {
+        self.reconnect_attempts = attempts;
+        self
+    }
+
+    /// Set the delay before the second connection attempt, doubled after each failed attempt
+    pub fn reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Set the max delay between two connection attempts
+    pub fn reconnect_max_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_max_delay = delay;
+        self
+    }
+
+    #[cfg(feature = "tcp_keepalive")]
+    /// Enable TCP keepalive with the idle time before the first probe (`None` to disable it)
+    ///
+    /// A dead connection (printer switched off, network down) is then detected without writing to the printer.
+    pub fn keepalive(mut self, time: Option<Duration>) -> Self {
+        self.keepalive = time;
+        self
+    }
+
+    /// Disable the Nagle algorithm (`TCP_NODELAY`) to send small commands immediately
+    pub fn nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = nodelay;
+        self
+    }
+
+    /// Connect on the first write instead of when the driver is opened
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+}
+
+/// Driver for network printer
+///
+/// The connection is re-established when it is lost (printer rebooted, network down, connection closed by the
+/// printer): a write on a closed connection reconnects and sends the data again, with the number of attempts and the
+/// backoff delay of [`NetworkOptions`]. The data is only sent again when none of it has been sent: if the connection
+/// is lost in the middle of a write, the error gives the number of bytes sent and the caller decides what to resend
+/// (the printer may already have printed the first part).
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct NetworkDriver {
+    host: String,
+    port: u16,
+    stream: Arc<Mutex<Option<TcpStream>>>,
+    options: NetworkOptions,
+}
+
+#[cfg(feature = "std")]
+impl NetworkDriver {
+    /// Open the network driver
+    ///
+    /// The host can be an IP address or a hostname.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    /// use std::time::Duration;
+    ///
+    /// let driver = NetworkDriver::open("192.168.1.248", 9100, Some(Duration::from_secs(1))).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(host: &str, port: u16, timeout: Option<Duration>) -> Result<Self> {
+        let options = NetworkOptions::default()
+            .connect_timeout(timeout)
+            .timeout(timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)));
+
+        Self::open_with_options(host, port, options)
+    }
+
+    /// Open the network driver using custom options
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    /// use std::time::Duration;
+    ///
+    /// let options = NetworkOptions::default()
+    ///     .connect_timeout(Some(Duration::from_secs(2)))
+    ///     .reconnect_attempts(5)
+    ///     .nodelay(true)
+    ///     .lazy(true);
+    /// let driver = NetworkDriver::open_with_options("printer.local", 9100, options).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_with_options(host: &str, port: u16, options: NetworkOptions) -> Result<Self> {
+        let driver = Self {
+            host: host.to_string(),
+            port,
+            stream: Arc::new(Mutex::new(None)),
+            options,
+        };
+        if !driver.options.lazy {
+            *driver.stream.lock()? = Some(driver.connect_stream()?);
+        }
+
+        Ok(driver)
+    }
+
+    /// Check the connection health
+    ///
+    /// Returns `false` if the driver is not connected yet (lazy connection) or if the connection has been closed by
+    /// the printer or has failed. A lost connection is re-established on the next write.
+    pub fn is_connected(&self) -> bool {
+        match self.stream.lock() {
+            Ok(mut stream) => {
+                if stream.as_ref().is_some_and(|current| !is_alive(current)) {
+                    *stream = None;
+                }
+                stream.is_some()
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Connect to the printer if the driver is not connected
+    pub fn connect(&self) -> Result<()> {
+        let mut stream = self.stream.lock()?;
+        if !stream.as_ref().is_some_and(is_alive) {
+            *stream = Some(self.reconnect_stream()?);
+        }
+
+        Ok(())
+    }
+
+    /// Close the connection (the driver connects again on the next write)
+    pub fn disconnect(&self) -> Result<()> {
+        self.stream.lock()?.take();
+
+        Ok(())
+    }
+
+    /// Read data from the stream, which is released if the connection is lost
+    fn read_stream(stream: &mut Option<TcpStream>, buf: &mut [u8]) -> Result<usize> {
+        let Some(current) = stream.as_mut() else {
+            return Err(PrinterError::Io("network printer not connected".to_owned()));
+        };
+        match current.read(buf) {
+            Ok(0) if !buf.is_empty() => {
+                *stream = None;
+                Ok(0)
+            }
+            Ok(size) => Ok(size),
+            Err(err) => {
+                if is_disconnected(&err) {
+                    *stream = None;
+                }
+                Err(err.into())
+            }
+        }
+    }
+
+    /// Connect to the first address of the host which accepts the connection
+    fn connect_stream(&self) -> Result<TcpStream> {
+        let mut error = None;
+        for addr in (self.host.as_str(), self.port).to_socket_addrs()? {
+            let stream = match self.options.connect_timeout {
+                Some(timeout) => TcpStream::connect_timeout(&addr, timeout),
+                None => TcpStream::connect(addr),
+            };
+            match stream {
+                Ok(stream) => return self.configure_stream(stream),
+                Err(err) => error = Some(err),
+            }
+        }
+
+        Err(match error {
+            Some(err) => err.into(),
+            None => PrinterError::Io(format!("no address found for {}:{}", self.host, self.port)),
+        })
+    }
+
+    /// Connect with the reconnection attempts and the backoff delay
+    fn reconnect_stream(&self) -> Result<TcpStream> {
+        let mut delay = self.options.reconnect_delay;
+        let mut attempts = 0;
+        loop {
+            match self.connect_stream() {
+                Ok(stream) => return Ok(stream),
+                Err(_) if attempts < self.options.reconnect_attempts => {
+                    thread::sleep(delay);
+                    delay = delay.saturating_mul(2).min(self.options.reconnect_max_delay);
+                    attempts += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Set the timeouts and the TCP options
+    fn configure_stream(&self, stream: TcpStream) -> Result<TcpStream> {
+        stream.set_read_timeout(Some(self.options.timeout))?;
+        stream.set_write_timeout(Some(self.options.timeout))?;
+        stream.set_nodelay(self.options.nodelay)?;
+        #[cfg(feature = "tcp_keepalive")]
+        if let Some(time) = self.options.keepalive {
+            SockRef::from(&stream).set_tcp_keepalive(&TcpKeepalive::new().with_time(time))?;
+        }
+
+        Ok(stream)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Driver for NetworkDriver {
+    fn name(&self) -> String {
+        format!("network ({}:{})", self.host, self.port)
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        let mut stream = self.stream.lock()?;
+        let mut reconnected = false;
+        loop {
+            let mut current = match stream.take().filter(is_alive) {
+                Some(current) => current,
+                None => {
+                    reconnected = true;
+                    self.reconnect_stream()?
+                }
+            };
+            let mut written = 0;
+            match write_stream(&mut current, data, &mut written) {
+                Ok(()) => {
+                    *stream = Some(current);
+                    return Ok(());
+                }
+                Err(err) if written > 0 => {
+                    if !is_disconnected(&err) {
+                        *stream = Some(current);
+                    }
+                    return Err(PrinterError::Io(format!(
+                        "network write failed after {written} of {} bytes: {err}",
+                        data.len()
+                    )));
+                }
+                Err(err) if is_disconnected(&err) => {
+                    if reconnected || self.options.reconnect_attempts == 0 {
+                        return Err(err.into());
+                    }
+                }
+                Err(err) => {
+                    *stream = Some(current);
+                    return Err(err.into());
+                }
+            }
+        }
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        let mut stream = self.stream.lock()?;
+        Self::read_stream(&mut stream, buf)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        let mut stream = self.stream.lock()?;
+        if let Some(current) = stream.as_ref() {
+            // A zero timeout is not allowed by the socket
+            current.set_read_timeout(Some(timeout.max(Duration::from_millis(1))))?;
+        }
+        let result = Self::read_stream(&mut stream, buf);
+        if let Some(current) = stream.as_ref() {
+            current.set_read_timeout(Some(self.options.timeout))?;
+        }
+        result
+    }
+
+    /// Get the number of bytes received (1024 bytes at most)
+    fn available(&self) -> Result<usize> {
+        let stream = self.stream.lock()?;
+        let Some(current) = stream.as_ref() else {
+            return Ok(0);
+        };
+
+        current.set_nonblocking(true)?;
+        let result = current.peek(&mut [0; 1024]);
+        current.set_nonblocking(false)?;
+        match result {
+            Ok(size) => Ok(size),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(0),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn flush(&self) -> Result<()> {
+        match self.stream.lock()?.as_mut() {
+            Some(stream) => Ok(stream.flush()?),
+            None => Ok(()),
+        }
+    }
+
+    /// Flush the data and close the connection (it is opened again by the next write)
+    fn close(&self) -> Result<()> {
+        let mut stream = self.stream.lock()?;
+        if let Some(current) = stream.as_mut() {
+            current.flush()?;
+        }
+        *stream = None;
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            bidirectional: true,
+            read_timeout: true,
+            available: true,
+        }
+    }
+}
+
+/// Write all the data to a TCP stream, `written` is the number of bytes sent if an error occurs
+#[cfg(feature = "std")]
+fn write_stream(stream: &mut TcpStream, data: &[u8], written: &mut usize) -> io::Result<()> {
+    while *written < data.len() {
+        match stream.write(&data[*written..]) {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(size) => *written += size,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(())
+}
+
+/// Check without blocking that a TCP connection has not been closed by the peer or has not failed
+#[cfg(feature = "std")]
+fn is_alive(stream: &TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return false;
+    }
+    let alive = match stream.peek(&mut [0; 1]) {
+        Ok(size) => size > 0,
+        Err(err) => err.kind() == io::ErrorKind::WouldBlock,
+    };
+
+    stream.set_nonblocking(false).is_ok() && alive
+}
+
+/// Check if an I/O error means that the connection is lost
+#[cfg(feature = "std")]
+fn is_disconnected(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::BrokenPipe
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+// ================ File driver ================
+
+/// Driver for USB printer using file
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct FileDriver {
+    path: String,
+    file: Arc<Mutex<File>>,
+}
+
+#[cfg(feature = "std")]
+impl FileDriver {
+    /// Open the file driver
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("./foo/bar.txt");
+    /// let driver = FileDriver::open(&path).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::options().read(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_string_lossy().to_string(),
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    /// Open the file driver using custom options
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    /// use std::fs::OpenOptions;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("./foo/bar.txt");
+    /// let mut options = OpenOptions::new();
+    /// options.write(true).create(true).truncate(true);
+    /// let driver = FileDriver::open_with_options(&path, &options).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_with_options(path: &Path, options: &OpenOptions) -> Result<Self> {
+        let file = options.open(path)?;
+        Ok(Self {
+            path: path.to_string_lossy().to_string(),
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Driver for FileDriver {
+    fn name(&self) -> String {
+        format!("file ({})", self.path)
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.file.lock()?.write_all(data)?;
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        Ok(self.file.lock()?.read(buf)?)
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(self.file.lock()?.flush()?)
+    }
+}
+
+// ================ USB drivers ================
+
+/// USB printer class code
+#[cfg(any(feature = "usb", feature = "native_usb"))]
+const USB_PRINTER_CLASS: u8 = 0x07;
+
+/// Information about a USB printer found by `UsbDriver::list` or `NativeUsbDriver::list`
+#[cfg(any(feature = "usb", feature = "native_usb"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsbDeviceInfo {
+    /// USB Vendor ID
+    pub vendor_id: u16,
+    /// USB Product ID
+    pub product_id: u16,
+    /// Manufacturer string (when available)
+    pub manufacturer: Option<String>,
+    /// Product string (when available)
+    pub product: Option<String>,
+    /// Serial number (when available)
+    pub serial_number: Option<String>,
+    /// Bus identifier (bus number with `UsbDriver`, platform bus ID with `NativeUsbDriver`)
+    pub bus_id: String,
+    /// Device address on the bus
+    pub address: u8,
+    /// Number of the interface with the bulk endpoints (when found)
+    pub interface_number: Option<u8>,
+    /// Bulk OUT endpoint address (when found)
+    pub output_endpoint: Option<u8>,
+    /// Bulk IN endpoint address (when found)
+    pub input_endpoint: Option<u8>,
+}
+
+/// Driver for USB printer
+#[cfg(feature = "usb")]
+#[derive(Clone)]
+pub struct UsbDriver {
+    vendor_id: u16,
+    product_id: u16,
+    output_endpoint: u8,
+    input_endpoint: u8,
+    device: Arc<Mutex<DeviceHandle<Context>>>,
+    timeout: Duration,
+}
+
+#[cfg(feature = "usb")]
+impl UsbDriver {
+    /// List the USB printers (devices with a printer class interface)
+    ///
+    /// The manufacturer, product and serial number strings are only available if the device can be opened.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::UsbDriver;
+    ///
+    /// for info in UsbDriver::list(None).unwrap() {
+    ///     println!("{:04x}:{:04x} {:?} (serial: {:?})", info.vendor_id, info.product_id, info.product, info.serial_number);
+    /// }
+    /// ```
+    pub fn list(options: Option<&[UsbOption]>) -> Result<Vec<UsbDeviceInfo>> {
+        let context = usb_context(options)?;
+        let devices = context.devices().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        let mut printers = Vec::new();
+        for device in devices.iter() {
+            let (Ok(device_descriptor), Ok(config_descriptor)) =
+                (device.device_descriptor(), device.active_config_descriptor())
+            else {
+                continue;
+            };
+            let is_printer = device_descriptor.class_code() == USB_PRINTER_CLASS
+                || config_descriptor
+                    .interfaces()
+                    .flat_map(|interface| interface.descriptors())
+                    .any(|descriptor| descriptor.class_code() == USB_PRINTER_CLASS);
+            if !is_printer {
+                continue;
+            }
+
+            let endpoints = usb_bulk_endpoints(&config_descriptor);
+            let handle = device.open().ok();
+            let read_string = |read: fn(&DeviceHandle<Context>, &DeviceDescriptor) -> rusb::Result<String>| {
+                handle.as_ref().and_then(|handle| read(handle, &device_descriptor).ok())
+            };
+
+            printers.push(UsbDeviceInfo {
+                vendor_id: device_descriptor.vendor_id(),
+                product_id: device_descriptor.product_id(),
+                manufacturer: read_string(DeviceHandle::read_manufacturer_string_ascii),
+                product: read_string(DeviceHandle::read_product_string_ascii),
+                serial_number: read_string(DeviceHandle::read_serial_number_string_ascii),
+                bus_id: device.bus_number().to_string(),
+                address: device.address(),
+                interface_number: endpoints.map(|(_, _, interface_number)| interface_number),
+                output_endpoint: endpoints.map(|(output_endpoint, _, _)| output_endpoint),
+                input_endpoint: endpoints.map(|(_, input_endpoint, _)| input_endpoint),
+            });
+        }
+
+        Ok(printers)
+    }
+
+    /// Open a new USB connection
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = UsbDriver::open(0x0525, 0xa700, None, None).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(
+        vendor_id: u16,
+        product_id: u16,
+        timeout: Option<Duration>,
+        options: Option<&[UsbOption]>,
+    ) -> Result<Self> {
+        let context = usb_context(options)?;
+        let devices = context.devices().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        for device in devices.iter() {
+            let device_descriptor = device
+                .device_descriptor()
+                .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+            if device_descriptor.vendor_id() == vendor_id && device_descriptor.product_id() == product_id {
+                return Self::open_device(&device, timeout);
+            }
+        }
+
+        Err(PrinterError::Io("USB device not found".to_string()))
+    }
+
+    /// Open a new USB connection to the device with the given serial number
+    ///
+    /// Useful to drive several printers with the same vendor and product IDs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = UsbDriver::open_by_serial(0x0525, 0xa700, "A1B2C3", None, None).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_by_serial(
+        vendor_id: u16,
+        product_id: u16,
+        serial_number: &str,
+        timeout: Option<Duration>,
+        options: Option<&[UsbOption]>,
+    ) -> Result<Self> {
+        let context = usb_context(options)?;
+        let devices = context.devices().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        for device in devices.iter() {
+            let device_descriptor = device
+                .device_descriptor()
+                .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+            if device_descriptor.vendor_id() == vendor_id
+                && device_descriptor.product_id() == product_id
+                && device
+                    .open()
+                    .and_then(|handle| handle.read_serial_number_string_ascii(&device_descriptor))
+                    .is_ok_and(|serial| serial == serial_number)
+            {
+                return Self::open_device(&device, timeout);
+            }
+        }
+
+        Err(PrinterError::Io("USB device not found".to_string()))
+    }
+
+    /// Open a new USB connection to the device at the given bus number and address
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = UsbDriver::open_by_bus_address(1, 4, None, None).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_by_bus_address(
+        bus_number: u8,
+        address: u8,
+        timeout: Option<Duration>,
+        options: Option<&[UsbOption]>,
+    ) -> Result<Self> {
+        let context = usb_context(options)?;
+        let devices = context.devices().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        match devices
+            .iter()
+            .find(|device| device.bus_number() == bus_number && device.address() == address)
+        {
+            Some(device) => Self::open_device(&device, timeout),
+            None => Err(PrinterError::Io("USB device not found".to_string())),
+        }
+    }
+
+    /// Open a device and claim the interface with the bulk endpoints
+    fn open_device(device: &Device<Context>, timeout: Option<Duration>) -> Result<Self> {
+        let device_descriptor = device
+            .device_descriptor()
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+        let config_descriptor = device
+            .active_config_descriptor()
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        let (output_endpoint, input_endpoint, interface_number) =
+            usb_bulk_endpoints(&config_descriptor).ok_or_else(|| {
+                PrinterError::Io("no suitable endpoints or interface number found for USB device".to_string())
+            })?;
+
+        match device.open() {
+            Ok(device_handle) => {
+                #[cfg(not(target_os = "windows"))]
+                match device_handle.kernel_driver_active(interface_number) {
+                    Ok(active) => {
+                        if active && let Err(e) = device_handle.detach_kernel_driver(interface_number) {
+                            return Err(PrinterError::Io(e.to_string()));
+                        }
+                    }
+                    Err(e) => return Err(PrinterError::Io(e.to_string())),
+                }
+
+                // Claims device interface
+                device_handle
+                    .claim_interface(interface_number)
+                    .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+                Ok(Self {
+                    vendor_id: device_descriptor.vendor_id(),
+                    product_id: device_descriptor.product_id(),
+                    output_endpoint,
+                    input_endpoint,
+                    device: Arc::new(Mutex::new(device_handle)),
+                    timeout: timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)),
+                })
+            }
+            Err(e) => Err(PrinterError::Io(e.to_string())),
+        }
+    }
+}
+
+/// Create a libusb context
+#[cfg(feature = "usb")]
+fn usb_context(options: Option<&[UsbOption]>) -> Result<Context> {
+    if let Some(options) = options {
+        Context::with_options(options).map_err(|e| PrinterError::Io(e.to_string()))
+    } else {
+        Context::new().map_err(|e| PrinterError::Io(e.to_string()))
+    }
+}
+
+/// Find the first interface with bulk output and input endpoints
+///
+/// Returns the output endpoint, the input endpoint and the interface number.
+#[cfg(feature = "usb")]
+fn usb_bulk_endpoints(config_descriptor: &ConfigDescriptor) -> Option<(u8, u8, u8)> {
+    config_descriptor
+        .interfaces()
+        .flat_map(|interface| interface.descriptors())
+        .flat_map(|descriptor| {
+            let interface_number = descriptor.interface_number();
+
+            // Find input and output endpoints
+            let mut input_endpoint = None;
+            let mut output_endpoint = None;
+            for endpoint in descriptor.endpoint_descriptors() {
+                if endpoint.transfer_type() == TransferType::Bulk && endpoint.direction() == Direction::In {
+                    input_endpoint = Some(endpoint.address());
+                } else if endpoint.transfer_type() == TransferType::Bulk && endpoint.direction() == Direction::Out {
+                    output_endpoint = Some(endpoint.address());
+                }
+            }
+
+            match (output_endpoint, input_endpoint) {
+                (Some(output_endpoint), Some(input_endpoint)) => {
+                    Some((output_endpoint, input_endpoint, interface_number))
+                }
+                _ => None,
+            }
+        })
+        .next()
+}
+
+#[cfg(feature = "usb")]
+impl Driver for UsbDriver {
+    fn name(&self) -> String {
+        format!(
+            "USB (VID: {}, PID: {}, output endpoint: {}, input endpoint: {})",
+            self.vendor_id, self.product_id, self.output_endpoint, self.input_endpoint
+        )
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.device
+            .lock()?
+            .write_bulk(self.output_endpoint, data, self.timeout)
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.read_timeout(buf, self.timeout)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        self.device
+            .lock()?
+            .read_bulk(self.input_endpoint, buf, timeout)
+            .map_err(|e| PrinterError::Io(e.to_string()))
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            read_timeout: true,
+            ..DriverCapabilities::default()
+        }
+    }
+}
+
+/// Driver for USB printer
+#[cfg(feature = "native_usb")]
+#[derive(Clone)]
+pub struct NativeUsbDriver {
+    vendor_id: u16,
+    product_id: u16,
+    output_endpoint: u8,
+    input_endpoint: u8,
+    device: Arc<Mutex<nusb::Interface>>,
+    /// Bytes of the last response which did not fit in the read buffer
+    pending: Arc<Mutex<Vec<u8>>>,
+}
+
+#[cfg(feature = "native_usb")]
+impl NativeUsbDriver {
+    /// List the USB printers (devices with a printer class interface)
+    ///
+    /// The endpoints are only available if the device can be opened.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::NativeUsbDriver;
+    ///
+    /// for info in NativeUsbDriver::list().unwrap() {
+    ///     println!("{:04x}:{:04x} {:?} (serial: {:?})", info.vendor_id, info.product_id, info.product, info.serial_number);
+    /// }
+    /// ```
+    pub fn list() -> Result<Vec<UsbDeviceInfo>> {
+        Ok(nusb::list_devices()
+            .wait()
+            .map_err(|e| PrinterError::Io(e.to_string()))?
+            .filter(|device_info| {
+                device_info.class() == USB_PRINTER_CLASS
+                    || device_info
+                        .interfaces()
+                        .any(|interface| interface.class() == USB_PRINTER_CLASS)
+            })
+            .map(|device_info| {
+                let endpoints = device_info
+                    .open()
+                    .wait()
+                    .ok()
+                    .and_then(|device| native_usb_bulk_endpoints(&device));
+
+                UsbDeviceInfo {
+                    vendor_id: device_info.vendor_id(),
+                    product_id: device_info.product_id(),
+                    manufacturer: device_info.manufacturer_string().map(ToOwned::to_owned),
+                    product: device_info.product_string().map(ToOwned::to_owned),
+                    serial_number: device_info.serial_number().map(ToOwned::to_owned),
+                    bus_id: device_info.bus_id().to_owned(),
+                    address: device_info.device_address(),
+                    interface_number: endpoints.map(|(_, _, interface_number)| interface_number),
+                    output_endpoint: endpoints.map(|(output_endpoint, _, _)| output_endpoint),
+                    input_endpoint: endpoints.map(|(_, input_endpoint, _)| input_endpoint),
+                }
+            })
+            .collect())
+    }
+
+    /// Open a new USB connection
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = NativeUsbDriver::open(0x0525, 0xa700).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(vendor_id: u16, product_id: u16) -> Result<Self> {
+        Self::open_device(|dev| dev.vendor_id() == vendor_id && dev.product_id() == product_id)
+    }
+
+    /// Open a new USB connection to the device with the given serial number
+    ///
+    /// Useful to drive several printers with the same vendor and product IDs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = NativeUsbDriver::open_by_serial(0x0525, 0xa700, "A1B2C3").unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_by_serial(vendor_id: u16, product_id: u16, serial_number: &str) -> Result<Self> {
+        Self::open_device(|dev| {
+            dev.vendor_id() == vendor_id && dev.product_id() == product_id && dev.serial_number() == Some(serial_number)
+        })
+    }
+
+    /// Open a new USB connection to the device at the given bus ID and address
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = NativeUsbDriver::open_by_bus_address("1", 4).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_by_bus_address(bus_id: &str, address: u8) -> Result<Self> {
+        Self::open_device(|dev| dev.bus_id() == bus_id && dev.device_address() == address)
+    }
+
+    /// Open the first device matching the predicate and claim the interface with the bulk endpoints
+    fn open_device(predicate: impl Fn(&nusb::DeviceInfo) -> bool) -> Result<Self> {
+        let device_info = nusb::list_devices()
+            .wait()
+            .map_err(|e| PrinterError::Io(e.to_string()))?
+            .find(|dev| predicate(dev))
+            .ok_or(PrinterError::Io("USB device not found".to_string()))?;
+        let device = device_info.open().wait().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        let (output_endpoint, input_endpoint, interface_number) = native_usb_bulk_endpoints(&device).ok_or(
+            PrinterError::Io("no suitable input or output endpoints found for USB device".to_string()),
+        )?;
+
+        let interface = device
+            .detach_and_claim_interface(interface_number)
+            .wait()
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        Ok(Self {
+            vendor_id: device_info.vendor_id(),
+            product_id: device_info.product_id(),
+            output_endpoint,
+            input_endpoint,
+            device: Arc::new(Mutex::new(interface)),
+            pending: Arc::new(Mutex::new(Vec::new())),
+        })
+    }
+}
+
+/// Find the first interface with bulk output and input endpoints in the active configuration
+///
+/// Returns the output endpoint, the input endpoint and the interface number.
+#[cfg(feature = "native_usb")]
+fn native_usb_bulk_endpoints(device: &nusb::Device) -> Option<(u8, u8, u8)> {
+    let configuration = device.active_configuration().ok()?;
+
+    configuration.interface_alt_settings().find_map(|settings| {
+        let (mut output, mut input) = (None, None);
+
+        for endpoint in settings.endpoints() {
+            if endpoint.transfer_type() == nusb::transfer::Bulk::TYPE
+                && endpoint.direction() == nusb::transfer::Direction::Out
+            {
+                output = Some(endpoint.address())
+            } else if endpoint.transfer_type() == nusb::transfer::Bulk::TYPE
+                && endpoint.direction() == nusb::transfer::Direction::In
+            {
+                input = Some(endpoint.address())
+            }
+        }
+
+        match (output, input) {
+            (Some(output), Some(input)) => Some((output, input, settings.interface_number())),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(feature = "native_usb")]
+impl Driver for NativeUsbDriver {
+    fn name(&self) -> String {
+        format!(
+            "USB (VID: {}, PID: {}, output endpoint: {}, input endpoint: {})",
+            self.vendor_id, self.product_id, self.output_endpoint, self.input_endpoint
+        )
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        let endpoint = self
+            .device
+            .lock()?
+            .endpoint::<nusb::transfer::Bulk, nusb::transfer::Out>(self.output_endpoint)
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        let max_size = endpoint.max_packet_size();
+
+        let mut writer = endpoint
+            .writer(max_size)
+            .with_write_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
+
+        writer.write_all(data).map_err(|e| PrinterError::Io(e.to_string()))?;
+        writer.flush().map_err(|e| PrinterError::Io(e.to_string()))?;
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.read_timeout(buf, Duration::from_secs(DEFAULT_TIMEOUT_SECONDS))
+    }
+
+    /// Read a response (until a short packet) into the buffer
+    ///
+    /// The bytes which do not fit in the buffer are returned by the next reads.
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        let mut pending = self.pending.lock()?;
+        if pending.is_empty() {
+            let endpoint = self
+                .device
+                .lock()?
+                .endpoint::<nusb::transfer::Bulk, nusb::transfer::In>(self.input_endpoint)
+                .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+            let max_size = endpoint.max_packet_size();
+
+            let mut reader = endpoint.reader(max_size).with_read_timeout(timeout);
+
+            let mut pkt_reader = reader.until_short_packet();
+            pkt_reader
+                .read_to_end(&mut pending)
+                .map_err(|e| PrinterError::Io(e.to_string()))?;
+            pkt_reader.consume_end().map_err(|e| PrinterError::Io(e.to_string()))?;
+        }
+
+        let size = buf.len().min(pending.len());
+        buf[..size].copy_from_slice(&pending[..size]);
+        pending.drain(..size);
+
+        Ok(size)
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            read_timeout: true,
+            ..DriverCapabilities::default()
+        }
+    }
+}
+
+// ================ HidApi driver ================
+
+/// First vendor-defined HID usage page (`0xFF00` - `0xFFFF`), used by the HID printers
+#[cfg(feature = "hidapi")]
+const HID_VENDOR_DEFINED_USAGE_PAGE: u16 = 0xFF00;
+
+/// Information about a HID device found by `HidApiDriver::list`
+#[cfg(feature = "hidapi")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HidApiDeviceInfo {
+    /// Platform device path (can be passed to `HidApiDriver::open_path`)
+    pub path: String,
+    /// USB Vendor ID
+    pub vendor_id: u16,
+    /// USB Product ID
+    pub product_id: u16,
+    /// Manufacturer string (when available)
+    pub manufacturer: Option<String>,
+    /// Product string (when available)
+    pub product: Option<String>,
+    /// Serial number (when available)
+    pub serial_number: Option<String>,
+    /// Interface number (`-1` when unknown)
+    pub interface_number: i32,
+    /// Top-level usage page (`0` when unknown)
+    pub usage_page: u16,
+    /// Top-level usage (`0` when unknown)
+    pub usage: u16,
+}
+
+#[cfg(feature = "hidapi")]
+impl HidApiDeviceInfo {
+    /// Can the device be a printer?
+    ///
+    /// HID printers have no standard usage page: they use a vendor-defined one. The devices with a standard usage
+    /// page (keyboards, mice, game controllers, barcode scanners…) are excluded. The devices whose usage page is
+    /// unknown (`0`, not reported by some backends) are kept.
+    pub fn is_printer_candidate(&self) -> bool {
+        self.usage_page == 0 || self.usage_page >= HID_VENDOR_DEFINED_USAGE_PAGE
+    }
+}
+
+/// Driver for USB printer
+#[cfg(feature = "hidapi")]
+#[derive(Clone)]
+pub struct HidApiDriver {
+    vendor_id: u16,
+    product_id: u16,
+    device: Arc<Mutex<HidDevice>>,
+}
+
+#[cfg(feature = "hidapi")]
+impl HidApiDriver {
+    /// List the HID devices which can be printers (see [`HidApiDeviceInfo::is_printer_candidate`])
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::HidApiDriver;
+    ///
+    /// for info in HidApiDriver::list().unwrap() {
+    ///     println!("{:04x}:{:04x} {:?} ({})", info.vendor_id, info.product_id, info.product, info.path);
+    /// }
+    /// ```
+    pub fn list() -> Result<Vec<HidApiDeviceInfo>> {
+        Self::list_filter(HidApiDeviceInfo::is_printer_candidate)
+    }
+
+    /// List the HID devices accepted by a filter
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::HidApiDriver;
+    ///
+    /// // All the HID devices of a vendor
+    /// let devices = HidApiDriver::list_filter(|info| info.vendor_id == 0x04b8).unwrap();
+    /// ```
+    pub fn list_filter(filter: impl Fn(&HidApiDeviceInfo) -> bool) -> Result<Vec<HidApiDeviceInfo>> {
+        let api = HidApi::new().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        Ok(api
+            .device_list()
+            .map(|info| HidApiDeviceInfo {
+                path: info.path().to_string_lossy().to_string(),
+                vendor_id: info.vendor_id(),
+                product_id: info.product_id(),
+                manufacturer: info.manufacturer_string().map(ToOwned::to_owned),
+                product: info.product_string().map(ToOwned::to_owned),
+                serial_number: info.serial_number().map(ToOwned::to_owned),
+                interface_number: info.interface_number(),
+                usage_page: info.usage_page(),
+                usage: info.usage(),
+            })
+            .filter(|info| filter(info))
+            .collect())
+    }
+
+    /// Open a new USB connection
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = HidApiDriver::open(0x0525, 0xa700).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(vendor_id: u16, product_id: u16) -> Result<Self> {
+        let api = HidApi::new().map_err(|e| PrinterError::Io(e.to_string()))?;
+        let device = api
+            .open(vendor_id, product_id)
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        Ok(Self {
+            vendor_id,
+            product_id,
+            device: Arc::new(Mutex::new(device)),
+        })
+    }
+
+    /// Open a new USB connection to the device with the given serial number
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = HidApiDriver::open_by_serial(0x0525, 0xa700, "A1B2C3").unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_by_serial(vendor_id: u16, product_id: u16, serial_number: &str) -> Result<Self> {
+        let api = HidApi::new().map_err(|e| PrinterError::Io(e.to_string()))?;
+        let device = api
+            .open_serial(vendor_id, product_id, serial_number)
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        Ok(Self {
+            vendor_id,
+            product_id,
+            device: Arc::new(Mutex::new(device)),
+        })
+    }
+
+    /// Open a new USB connection to the device with the given platform path (see `HidApiDriver::list`)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// let driver = HidApiDriver::open_path("/dev/hidraw0").unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_path(path: &str) -> Result<Self> {
+        let api = HidApi::new().map_err(|e| PrinterError::Io(e.to_string()))?;
+        let path = CString::new(path).map_err(|e| PrinterError::Input(e.to_string()))?;
+        let device = api.open_path(&path).map_err(|e| PrinterError::Io(e.to_string()))?;
+        let info = device.get_device_info().map_err(|e| PrinterError::Io(e.to_string()))?;
+
+        Ok(Self {
+            vendor_id: info.vendor_id(),
+            product_id: info.product_id(),
+            device: Arc::new(Mutex::new(device)),
+        })
+    }
+}
+
+#[cfg(feature = "hidapi")]
+impl Driver for HidApiDriver {
+    fn name(&self) -> String {
+        format!("HidApi (VID: {}, PID: {})", self.vendor_id, self.product_id)
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.device
+            .lock()?
+            .write(data)
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.read_timeout(buf, Duration::from_secs(DEFAULT_TIMEOUT_SECONDS))
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        self.device
+            .lock()?
+            .read_timeout(buf, i32::try_from(timeout.as_millis())?)
+            .map_err(|e| PrinterError::Io(e.to_string()))
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            read_timeout: true,
+            ..DriverCapabilities::default()
+        }
+    }
+}
+
+// ================ Serial port driver ================
+
+/// XON character (resume transmission)
+#[cfg(feature = "serial_port")]
+const XON: u8 = 0x11;
+
+/// XOFF character (pause transmission)
+#[cfg(feature = "serial_port")]
+const XOFF: u8 = 0x13;
+
+/// Default size of the chunks written between two flow control checks
+#[cfg(feature = "serial_port")]
+const DEFAULT_SERIAL_CHUNK_SIZE: usize = 128;
+
+/// Delay between two flow control checks while the printer is busy
+#[cfg(feature = "serial_port")]
+const SERIAL_BUSY_POLL_MILLISECONDS: u64 = 10;
+
+/// Serial port flow control
+#[cfg(feature = "serial_port")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SerialFlowControl {
+    /// No flow control
+    #[default]
+    None,
+    /// Hardware RTS/CTS flow control, handled by the operating system
+    RtsCts,
+    /// Software XON/XOFF flow control, handled by the operating system
+    XonXoff,
+    /// DTR/DSR flow control: the data is written by chunks when the printer asserts DSR (not busy)
+    DtrDsr,
+    /// Software XON/XOFF pacing handled by the driver, for the ports and adapters without flow control support: the
+    /// data is written by chunks and the writing pauses when the printer sends XOFF until it sends XON
+    ///
+    /// The XON / XOFF characters are only handled while writing. The data read afterwards is a response and is
+    /// returned as is, since binary responses (symbol sizes, NV graphics capacity…) can contain `0x11` and `0x13`.
+    XonXoffPacing,
+}
+
+/// Serial port options
+///
+/// The default configuration is 9600 bauds 8N1 without flow control.
+#[cfg(feature = "serial_port")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialPortOptions {
+    baud_rate: u32,
+    data_bits: DataBits,
+    parity: Parity,
+    stop_bits: StopBits,
+    flow_control: SerialFlowControl,
+    dtr: Option<bool>,
+    rts: Option<bool>,
+    timeout: Duration,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "serial_port")]
+impl Default for SerialPortOptions {
+    fn default() -> Self {
+        Self::new(9_600)
+    }
+}
+
+#[cfg(feature = "serial_port")]
+impl SerialPortOptions {
+    /// Create serial port options (8N1, no flow control, 5s timeout)
+    pub fn new(baud_rate: u32) -> Self {
+        Self {
+            baud_rate,
+            data_bits: DataBits::Eight,
+            parity: Parity::None,
+            stop_bits: StopBits::One,
+            flow_control: SerialFlowControl::None,
+            dtr: None,
+            rts: None,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+            chunk_size: DEFAULT_SERIAL_CHUNK_SIZE,
+        }
+    }
+
+    /// Set the baud rate
+    pub fn baud_rate(mut self, baud_rate: u32) -> Self {
+        self.baud_rate = baud_rate;
+        self
+    }
+
+    /// Set the number of data bits
+    pub fn data_bits(mut self, data_bits: DataBits) -> Self {
+        self.data_bits = data_bits;
+        self
+    }
+
+    /// Set the parity
+    pub fn parity(mut self, parity: Parity) -> Self {
+        self.parity = parity;
+        self
+    }
+
+    /// Set the number of stop bits
+    pub fn stop_bits(mut self, stop_bits: StopBits) -> Self {
+        self.stop_bits = stop_bits;
+        self
+    }
+
+    /// Set the flow control
+    pub fn flow_control(mut self, flow_control: SerialFlowControl) -> Self {
+        self.flow_control = flow_control;
+        self
+    }
+
+    /// Set the DTR (Data Terminal Ready) state when the port is opened
+    ///
+    /// DTR is asserted by default with the DTR/DSR flow control.
+    pub fn dtr(mut self, dtr: bool) -> Self {
+        self.dtr = Some(dtr);
+        self
+    }
+
+    /// Set the RTS (Request To Send) state when the port is opened
+    pub fn rts(mut self, rts: bool) -> Self {
+        self.rts = Some(rts);
+        self
+    }
+
+    /// Set the read and write timeout, also used as the max time to wait while the printer is busy
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the size of the chunks written between two flow control checks (DTR/DSR and XON/XOFF pacing)
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+}
+
+/// Kind of serial port
+#[cfg(feature = "serial_port")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerialPortKind {
+    /// USB serial adapter
+    Usb,
+    /// PCI serial port
+    Pci,
+    /// Bluetooth serial port
+    Bluetooth,
+    /// Unknown (built-in RS-232 port, pseudo-terminal, etc.)
+    Unknown,
+}
+
+/// Information about a serial port found by `SerialPortDriver::list`
+#[cfg(feature = "serial_port")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialPortDeviceInfo {
+    /// Port path (can be passed to `SerialPortDriver::open`)
+    pub path: String,
+    /// Kind of port
+    pub kind: SerialPortKind,
+    /// USB Vendor ID (USB serial adapters)
+    pub vendor_id: Option<u16>,
+    /// USB Product ID (USB serial adapters)
+    pub product_id: Option<u16>,
+    /// Manufacturer string (USB serial adapters, when available)
+    pub manufacturer: Option<String>,
+    /// Product string (USB serial adapters, when available)
+    pub product: Option<String>,
+    /// Serial number (USB serial adapters, when available)
+    pub serial_number: Option<String>,
+}
+
+/// Flow control state of the serial port driver
+#[cfg(feature = "serial_port")]
+#[derive(Debug, Default)]
+struct SerialFlowState {
+    /// XOFF received from the printer
+    paused: bool,
+    /// Data received while checking the flow control, returned by the next reads
+    pending: Vec<u8>,
+}
+
+/// Driver for Serial printer
+#[cfg(feature = "serial_port")]
+#[derive(Clone)]
+pub struct SerialPortDriver {
+    path: String,
+    port: Arc<Mutex<Box<dyn SerialPort>>>,
+    flow_state: Arc<Mutex<SerialFlowState>>,
+    options: SerialPortOptions,
+}
+
+#[cfg(feature = "serial_port")]
+impl SerialPortDriver {
+    /// List the serial ports
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::SerialPortDriver;
+    ///
+    /// for info in SerialPortDriver::list().unwrap() {
+    ///     println!("{} ({:?}, {:?})", info.path, info.kind, info.product);
+    /// }
+    /// ```
+    pub fn list() -> Result<Vec<SerialPortDeviceInfo>> {
+        Ok(serialport::available_ports()
+            .map_err(|e| PrinterError::Io(e.to_string()))?
+            .into_iter()
+            .map(|port| {
+                let mut info = SerialPortDeviceInfo {
+                    path: port.port_name,
+                    kind: SerialPortKind::Unknown,
+                    vendor_id: None,
+                    product_id: None,
+                    manufacturer: None,
+                    product: None,
+                    serial_number: None,
+                };
+                match port.port_type {
+                    SerialPortType::UsbPort(usb) => {
+                        info.kind = SerialPortKind::Usb;
+                        info.vendor_id = Some(usb.vid);
+                        info.product_id = Some(usb.pid);
+                        info.manufacturer = usb.manufacturer;
+                        info.product = usb.product;
+                        info.serial_number = usb.serial_number;
+                    }
+                    SerialPortType::PciPort => info.kind = SerialPortKind::Pci,
+                    SerialPortType::BluetoothPort => info.kind = SerialPortKind::Bluetooth,
+                    SerialPortType::Unknown => (),
+                }
+                info
+            })
+            .collect())
+    }
+
+    /// Open a new Serial port connection
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    /// use std::time::Duration;
+    ///
+    /// let driver = SerialPortDriver::open("/dev/ttyUSB0", 115_200, Some(Duration::from_secs(5))).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(path: &str, baud_rate: u32, timeout: Option<Duration>) -> Result<Self> {
+        let options =
+            SerialPortOptions::new(baud_rate).timeout(timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)));
+
+        Self::open_with_options(path, options)
+    }
+
+    /// Open a new Serial port connection using custom options
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    ///
+    /// // 19200 bauds 7E1 with RTS/CTS flow control
+    /// let options = SerialPortOptions::new(19_200)
+    ///     .data_bits(DataBits::Seven)
+    ///     .parity(Parity::Even)
+    ///     .flow_control(SerialFlowControl::RtsCts);
+    /// let driver = SerialPortDriver::open_with_options("/dev/ttyS0", options).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open_with_options(path: &str, options: SerialPortOptions) -> Result<Self> {
+        let flow_control = match options.flow_control {
+            SerialFlowControl::RtsCts => FlowControl::Hardware,
+            SerialFlowControl::XonXoff => FlowControl::Software,
+            _ => FlowControl::None,
+        };
+        let mut builder = serialport::new(path, options.baud_rate)
+            .data_bits(options.data_bits)
+            .parity(options.parity)
+            .stop_bits(options.stop_bits)
+            .flow_control(flow_control)
+            .timeout(options.timeout);
+        match (options.dtr, options.flow_control) {
+            (Some(dtr), _) => builder = builder.dtr_on_open(dtr),
+            (None, SerialFlowControl::DtrDsr) => builder = builder.dtr_on_open(true),
+            _ => (),
+        }
+        let mut port = builder.open().map_err(|e| PrinterError::Io(e.to_string()))?;
+        if let Some(rts) = options.rts {
+            port.write_request_to_send(rts)
+                .map_err(|e| PrinterError::Io(e.to_string()))?;
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            port: Arc::new(Mutex::new(port)),
+            flow_state: Arc::new(Mutex::new(SerialFlowState::default())),
+            options,
+        })
+    }
+
+    /// Wait until the printer is ready to receive data (DSR asserted or XON received)
+    fn wait_ready(&self, port: &mut Box<dyn SerialPort>, flow_state: &mut SerialFlowState) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            let ready = match self.options.flow_control {
+                SerialFlowControl::DtrDsr => port
+                    .read_data_set_ready()
+                    .map_err(|e| PrinterError::Io(e.to_string()))?,
+                SerialFlowControl::XonXoffPacing => {
+                    Self::receive_flow_control(port, flow_state)?;
+                    !flow_state.paused
+                }
+                _ => true,
+            };
+            if ready {
+                return Ok(());
+            }
+            if start.elapsed() >= self.options.timeout {
+                return Err(PrinterError::Io(format!("serial printer busy ({})", self.path)));
+            }
+            thread::sleep(Duration::from_millis(SERIAL_BUSY_POLL_MILLISECONDS));
+        }
+    }
+
+    /// Read the available data without blocking, handling the XON / XOFF characters and keeping the other data
+    fn receive_flow_control(port: &mut Box<dyn SerialPort>, flow_state: &mut SerialFlowState) -> Result<()> {
+        let available = port.bytes_to_read().map_err(|e| PrinterError::Io(e.to_string()))?;
+        if available > 0 {
+            let mut buf = vec![0; usize::try_from(available)?];
+            let size = port.read(&mut buf)?;
+            let data = Self::filter_flow_control(&buf[..size], flow_state);
+            flow_state.pending.extend(data);
+        }
+
+        Ok(())
+    }
+
+    /// Remove the XON / XOFF characters from received data and update the pause state
+    fn filter_flow_control(data: &[u8], flow_state: &mut SerialFlowState) -> Vec<u8> {
+        data.iter()
+            .filter(|&&byte| match byte {
+                XON => {
+                    flow_state.paused = false;
+                    false
+                }
+                XOFF => {
+                    flow_state.paused = true;
+                    false
+                }
+                _ => true,
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(feature = "serial_port")]
+impl Driver for SerialPortDriver {
+    fn name(&self) -> String {
+        format!("Serial port ({})", self.path)
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        let mut port = self.port.lock()?;
+        match self.options.flow_control {
+            SerialFlowControl::DtrDsr | SerialFlowControl::XonXoffPacing => {
+                let mut flow_state = self.flow_state.lock()?;
+                for chunk in data.chunks(self.options.chunk_size) {
+                    self.wait_ready(&mut port, &mut flow_state)?;
+                    port.write_all(chunk)?;
+                }
+            }
+            _ => port.write_all(data)?,
+        }
+
         Ok(())
     }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.read_port(&mut *self.port.lock()?, buf)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        let mut port = self.port.lock()?;
+        port.set_timeout(timeout).map_err(|e| PrinterError::Io(e.to_string()))?;
+        let result = self.read_port(&mut port, buf);
+        port.set_timeout(self.options.timeout)
+            .map_err(|e| PrinterError::Io(e.to_string()))?;
+        result
+    }
+
+    fn available(&self) -> Result<usize> {
+        let port = self.port.lock()?;
+        let pending = self.flow_state.lock()?.pending.len();
+        let available = port.bytes_to_read().map_err(|e| PrinterError::Io(e.to_string()))?;
+        Ok(pending + usize::try_from(available)?)
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(self.port.lock()?.flush()?)
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            bidirectional: true,
+            read_timeout: true,
+            available: true,
+        }
+    }
+}
+
+#[cfg(feature = "serial_port")]
+impl SerialPortDriver {
+    /// Read data from the locked port, the data received while waiting for XON being returned first
+    ///
+    /// The data read from the port is not filtered: it is the response to a request, which can contain the XON and
+    /// XOFF bytes.
+    fn read_port(&self, port: &mut Box<dyn SerialPort>, buf: &mut [u8]) -> Result<usize> {
+        let mut flow_state = self.flow_state.lock()?;
+        if !flow_state.pending.is_empty() {
+            let size = buf.len().min(flow_state.pending.len());
+            buf[..size].copy_from_slice(&flow_state.pending[..size]);
+            flow_state.pending.drain(..size);
+            return Ok(size);
+        }
+
+        Ok(port.read(buf)?)
+    }
+}
+
+// ================ Linux USB line printer driver ================
+
+/// Directory of the Linux `usblp` devices
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+const LP_DEVICES_DIRECTORY: &str = "/dev/usb";
+
+/// Max size of the IEEE 1284 device ID (with its 2-byte length)
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+const LP_DEVICE_ID_SIZE: usize = 1024;
+
+/// `LPIOC_GET_DEVICE_ID(len)` ioctl of the `usblp` kernel driver
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+const LPIOC_GET_DEVICE_ID: libc::Ioctl = libc::_IOR::<[u8; LP_DEVICE_ID_SIZE]>(b'P' as u32, 1);
+
+/// IEEE 1284 device ID of a printer
+///
+/// The device ID is a list of `KEY:value;` fields, for example
+/// `MFG:EPSON;CMD:ESC/POS;MDL:TM-T20II;CLS:PRINTER;DES:EPSON TM-T20II;`.
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LpDeviceId {
+    /// Manufacturer (`MFG` or `MANUFACTURER` field)
+    pub manufacturer: Option<String>,
+    /// Model (`MDL` or `MODEL` field)
+    pub model: Option<String>,
+    /// Supported command sets (`CMD` or `COMMAND SET` field)
+    pub command_set: Vec<String>,
+    /// Device class (`CLS` or `CLASS` field)
+    pub class: Option<String>,
+    /// Description (`DES` or `DESCRIPTION` field)
+    pub description: Option<String>,
+    /// Serial number (`SN` or `SERN` field)
+    pub serial_number: Option<String>,
+    /// Raw device ID
+    pub raw: String,
+}
+
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+impl LpDeviceId {
+    /// Parse an IEEE 1284 device ID
+    ///
+    /// ```
+    /// use escpos::driver::LpDeviceId;
+    ///
+    /// let id = LpDeviceId::parse("MFG:EPSON;CMD:ESC/POS,ZPL;MDL:TM-T20II;CLS:PRINTER;");
+    /// assert_eq!(id.manufacturer.as_deref(), Some("EPSON"));
+    /// assert_eq!(id.model.as_deref(), Some("TM-T20II"));
+    /// assert_eq!(id.command_set, ["ESC/POS", "ZPL"]);
+    /// ```
+    pub fn parse(device_id: &str) -> Self {
+        let mut id = Self {
+            raw: device_id.to_owned(),
+            ..Self::default()
+        };
+
+        for field in device_id.split(';') {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().to_owned();
+            match key.trim().to_ascii_uppercase().as_str() {
+                "MFG" | "MANUFACTURER" => id.manufacturer = Some(value),
+                "MDL" | "MODEL" => id.model = Some(value),
+                "CMD" | "COMMAND SET" => {
+                    id.command_set = value
+                        .split(',')
+                        .map(|command| command.trim().to_owned())
+                        .filter(|command| !command.is_empty())
+                        .collect()
+                }
+                "CLS" | "CLASS" => id.class = Some(value),
+                "DES" | "DESCRIPTION" => id.description = Some(value),
+                "SN" | "SERN" => id.serial_number = Some(value),
+                _ => (),
+            }
+        }
+
+        id
+    }
+
+    /// Check if the printer supports a command set (`ESC/POS`, `ESCPOS`, etc.), ignoring the case
+    pub fn supports(&self, command_set: &str) -> bool {
+        self.command_set
+            .iter()
+            .any(|command| command.eq_ignore_ascii_case(command_set))
+    }
 }
 
-// ================ Serial port driver ================
+/// Information about a Linux USB line printer found by `LpDriver::list`
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LpDeviceInfo {
+    /// Device path (can be passed to `LpDriver::open`)
+    pub path: PathBuf,
+    /// IEEE 1284 device ID (`None` if the device can not be opened, for example when it is already in use)
+    pub device_id: Option<LpDeviceId>,
+}
 
-/// Driver for Serial printer
-#[cfg(feature = "serial_port")]
+/// Driver for USB printer using the Linux `usblp` kernel driver (`/dev/usb/lpN`)
+///
+/// Unlike [`FileDriver`], the driver reads the IEEE 1284 device ID of the printer and waits for the status responses
+/// with a timeout instead of blocking.
+#[cfg(all(feature = "usblp", target_os = "linux"))]
 #[derive(Clone)]
-pub struct SerialPortDriver {
+pub struct LpDriver {
     path: String,
-    port: Arc<Mutex<Box<dyn SerialPort>>>,
+    file: Arc<Mutex<File>>,
+    timeout: Duration,
 }
 
-#[cfg(feature = "serial_port")]
-impl SerialPortDriver {
-    /// Open a new Serial port connection
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+impl LpDriver {
+    /// List the USB line printers (`/dev/usb/lp*`) with their IEEE 1284 device ID
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::LpDriver;
+    ///
+    /// for info in LpDriver::list().unwrap() {
+    ///     let model = info.device_id.and_then(|id| id.model);
+    ///     println!("{} ({:?})", info.path.display(), model);
+    /// }
+    /// ```
+    pub fn list() -> Result<Vec<LpDeviceInfo>> {
+        let entries = match fs::read_dir(LP_DEVICES_DIRECTORY) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut devices = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("lp"))
+            {
+                continue;
+            }
+
+            let device_id = File::open(&path).ok().and_then(|file| lp_device_id(&file).ok());
+            devices.push(LpDeviceInfo { path, device_id });
+        }
+        devices.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(devices)
+    }
+
+    /// Open the USB line printer driver
+    ///
+    /// The timeout (5s by default) is the max time to wait for data when reading.
     ///
     /// # Example
     ///
@@ -626,46 +3338,757 @@ impl SerialPortDriver {
     /// use escpos::printer::Printer;
     /// use escpos::utils::*;
     /// use escpos::driver::*;
+    /// use std::path::Path;
     /// use std::time::Duration;
     ///
-    /// let driver = SerialPortDriver::open("/dev/ttyUSB0", 115_200, Some(Duration::from_secs(5))).unwrap();
+    /// let driver = LpDriver::open(Path::new("/dev/usb/lp0"), Some(Duration::from_millis(500))).unwrap();
     /// let mut printer = Printer::new(driver, Protocol::default(), None);
     /// ```
-    pub fn open(path: &str, baud_rate: u32, timeout: Option<Duration>) -> Result<Self> {
-        let mut port = serialport::new(path, baud_rate);
-        if let Some(timeout) = timeout {
-            port = port.timeout(timeout);
+    pub fn open(path: &Path, timeout: Option<Duration>) -> Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+
+        Ok(Self {
+            path: path.to_string_lossy().to_string(),
+            file: Arc::new(Mutex::new(file)),
+            timeout: timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)),
+        })
+    }
+
+    /// Get the IEEE 1284 device ID of the printer (manufacturer, model, command set, etc.)
+    pub fn device_id(&self) -> Result<LpDeviceId> {
+        lp_device_id(&*self.file.lock()?)
+    }
+}
+
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+impl Driver for LpDriver {
+    fn name(&self) -> String {
+        format!("USB line printer ({})", self.path)
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.file.lock()?.write_all(data)?;
+        Ok(())
+    }
+
+    /// Read data, or return `0` if no data is received before the timeout
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.read_timeout(buf, self.timeout)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        poll_read(&mut *self.file.lock()?, buf, timeout)
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(self.file.lock()?.flush()?)
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            read_timeout: true,
+            ..DriverCapabilities::default()
+        }
+    }
+}
+
+/// Read the IEEE 1284 device ID with the `LPIOC_GET_DEVICE_ID` ioctl
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+fn lp_device_id(file: &File) -> Result<LpDeviceId> {
+    let mut buf = [0u8; LP_DEVICE_ID_SIZE];
+    // The kernel writes at most the buffer size encoded in the ioctl number
+    if unsafe { libc::ioctl(file.as_raw_fd(), LPIOC_GET_DEVICE_ID, buf.as_mut_ptr()) } < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    Ok(parse_lp_device_id(&buf))
+}
+
+/// Parse the buffer of the `LPIOC_GET_DEVICE_ID` ioctl
+///
+/// The first 2 bytes are the big-endian length of the device ID, including these 2 bytes.
+#[cfg(all(feature = "usblp", target_os = "linux"))]
+fn parse_lp_device_id(buf: &[u8]) -> LpDeviceId {
+    let length = match buf {
+        [high, low, ..] => usize::from(u16::from_be_bytes([*high, *low])).clamp(2, buf.len()),
+        _ => return LpDeviceId::parse(""),
+    };
+    LpDeviceId::parse(&String::from_utf8_lossy(&buf[2..length]))
+}
+
+/// Wait for data with a timeout and read it, or return `0` if no data is received before the timeout
+#[cfg(all(any(feature = "usblp", feature = "bluetooth"), target_os = "linux"))]
+fn poll_read(file: &mut File, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+    let mut poll_fd = libc::pollfd {
+        fd: file.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+
+    match unsafe { libc::poll(&mut poll_fd, 1, timeout) } {
+        -1 => Err(io::Error::last_os_error().into()),
+        0 => Ok(0),
+        _ => Ok(file.read(buf)?),
+    }
+}
+
+// ================ Bluetooth driver ================
+
+/// RFCOMM protocol of the Linux Bluetooth sockets
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+const BTPROTO_RFCOMM: libc::c_int = 3;
+
+/// Default RFCOMM channel of the Serial Port Profile
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+const DEFAULT_RFCOMM_CHANNEL: u8 = 1;
+
+/// Directory of the BlueZ storage
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+const BLUEZ_STORAGE_DIRECTORY: &str = "/var/lib/bluetooth";
+
+/// Linux RFCOMM socket address (`struct sockaddr_rc`)
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+#[repr(C)]
+struct SockaddrRc {
+    rc_family: libc::sa_family_t,
+    rc_bdaddr: [u8; 6],
+    rc_channel: u8,
+}
+
+/// Information about a paired Bluetooth device found by `BluetoothDriver::list_paired`
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BluetoothDeviceInfo {
+    /// Device address (`AA:BB:CC:DD:EE:FF`, can be passed to `BluetoothDriver::open`)
+    pub address: String,
+    /// Device name (when available)
+    pub name: Option<String>,
+    /// Address of the local adapter the device is paired with
+    pub adapter: String,
+    /// Class of device (when available)
+    pub class: Option<u32>,
+}
+
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+impl BluetoothDeviceInfo {
+    /// Check if the class of device is a printer (imaging major class with the printer bit)
+    pub fn is_printer(&self) -> bool {
+        self.class
+            .is_some_and(|class| (class >> 8) & 0x1F == 0x06 && class & 0x80 != 0)
+    }
+}
+
+/// Driver for Bluetooth Classic printer using the Serial Port Profile (RFCOMM socket, Linux only)
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+#[derive(Clone)]
+pub struct BluetoothDriver {
+    address: String,
+    channel: u8,
+    socket: Arc<Mutex<File>>,
+    timeout: Duration,
+}
+
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+impl BluetoothDriver {
+    /// List the Bluetooth devices paired with the local adapters
+    ///
+    /// The devices are read from the BlueZ storage (`/var/lib/bluetooth`), which is usually only readable by root.
+    /// An empty list is returned if BlueZ is not installed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::driver::BluetoothDriver;
+    ///
+    /// for info in BluetoothDriver::list_paired().unwrap() {
+    ///     println!("{} {:?} (printer: {})", info.address, info.name, info.is_printer());
+    /// }
+    /// ```
+    pub fn list_paired() -> Result<Vec<BluetoothDeviceInfo>> {
+        bluez_paired_devices(Path::new(BLUEZ_STORAGE_DIRECTORY))
+    }
+
+    /// Open a Bluetooth connection to the RFCOMM channel of a device (channel 1 by default)
+    ///
+    /// The timeout (5s by default) is used to connect and to wait for data when reading.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use escpos::printer::Printer;
+    /// use escpos::utils::*;
+    /// use escpos::driver::*;
+    /// use std::time::Duration;
+    ///
+    /// let driver = BluetoothDriver::open("66:22:B3:4C:90:1F", None, Some(Duration::from_secs(10))).unwrap();
+    /// let mut printer = Printer::new(driver, Protocol::default(), None);
+    /// ```
+    pub fn open(address: &str, channel: Option<u8>, timeout: Option<Duration>) -> Result<Self> {
+        let channel = channel.unwrap_or(DEFAULT_RFCOMM_CHANNEL);
+        let timeout = timeout.unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
+        let socket = rfcomm_connect(parse_bluetooth_address(address)?, channel, timeout)?;
+
+        Ok(Self::from_socket(address, channel, socket, timeout))
+    }
+
+    /// Create the driver from a connected socket
+    fn from_socket(address: &str, channel: u8, socket: File, timeout: Duration) -> Self {
+        Self {
+            address: address.to_ascii_uppercase(),
+            channel,
+            socket: Arc::new(Mutex::new(socket)),
+            timeout,
+        }
+    }
+}
+
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+impl Driver for BluetoothDriver {
+    fn name(&self) -> String {
+        format!("Bluetooth ({}, channel {})", self.address, self.channel)
+    }
+
+    fn write(&self, data: &[u8]) -> Result<()> {
+        self.socket.lock()?.write_all(data)?;
+        Ok(())
+    }
+
+    /// Read data, or return `0` if no data is received before the timeout
+    fn read(&self, buf: &mut [u8]) -> Result<usize> {
+        self.read_timeout(buf, self.timeout)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        poll_read(&mut *self.socket.lock()?, buf, timeout)
+    }
+
+    fn available(&self) -> Result<usize> {
+        let socket = self.socket.lock()?;
+        let mut available: libc::c_int = 0;
+        if unsafe { libc::ioctl(socket.as_raw_fd(), libc::FIONREAD, &mut available) } < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(usize::try_from(available)?)
+    }
+
+    fn flush(&self) -> Result<()> {
+        Ok(self.socket.lock()?.flush()?)
+    }
+
+    fn close(&self) -> Result<()> {
+        let mut socket = self.socket.lock()?;
+        socket.flush()?;
+        if unsafe { libc::shutdown(socket.as_raw_fd(), libc::SHUT_RDWR) } < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            bidirectional: true,
+            read_timeout: true,
+            available: true,
+        }
+    }
+}
+
+/// Parse a Bluetooth address (`AA:BB:CC:DD:EE:FF`) to a `bdaddr_t` (bytes in reverse order)
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+fn parse_bluetooth_address(address: &str) -> Result<[u8; 6]> {
+    let error = || PrinterError::Input(format!("invalid Bluetooth address: {address}"));
+
+    let mut bdaddr = [0; 6];
+    let mut parts = address.split(':');
+    for byte in bdaddr.iter_mut().rev() {
+        let part = parts.next().filter(|part| part.len() == 2).ok_or_else(error)?;
+        *byte = u8::from_str_radix(part, 16).map_err(|_| error())?;
+    }
+    if parts.next().is_some() {
+        return Err(error());
+    }
+
+    Ok(bdaddr)
+}
+
+/// Connect a RFCOMM socket with a timeout
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+fn rfcomm_connect(bdaddr: [u8; 6], channel: u8, timeout: Duration) -> Result<File> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_BLUETOOTH,
+            libc::SOCK_STREAM | libc::SOCK_CLOEXEC | libc::SOCK_NONBLOCK,
+            BTPROTO_RFCOMM,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    let socket = unsafe { File::from_raw_fd(fd) };
+
+    let addr = SockaddrRc {
+        rc_family: libc::AF_BLUETOOTH as libc::sa_family_t,
+        rc_bdaddr: bdaddr,
+        rc_channel: channel,
+    };
+    let result = unsafe {
+        libc::connect(
+            fd,
+            (&addr as *const SockaddrRc).cast(),
+            mem::size_of::<SockaddrRc>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        let error = io::Error::last_os_error();
+        if error.raw_os_error() != Some(libc::EINPROGRESS) {
+            return Err(error.into());
+        }
+
+        // Wait for the connection and get its result
+        let mut poll_fd = libc::pollfd {
+            fd,
+            events: libc::POLLOUT,
+            revents: 0,
+        };
+        let timeout = i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX);
+        match unsafe { libc::poll(&mut poll_fd, 1, timeout) } {
+            -1 => return Err(io::Error::last_os_error().into()),
+            0 => return Err(io::Error::from(io::ErrorKind::TimedOut).into()),
+            _ => (),
+        }
+        let mut error: libc::c_int = 0;
+        let mut length = mem::size_of::<libc::c_int>() as libc::socklen_t;
+        if unsafe {
+            libc::getsockopt(
+                fd,
+                libc::SOL_SOCKET,
+                libc::SO_ERROR,
+                (&mut error as *mut libc::c_int).cast(),
+                &mut length,
+            )
+        } < 0
+        {
+            return Err(io::Error::last_os_error().into());
+        }
+        if error != 0 {
+            return Err(io::Error::from_raw_os_error(error).into());
+        }
+    }
+
+    // Back to blocking mode for the writes
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    if flags < 0 || unsafe { libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) } < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+
+    Ok(socket)
+}
+
+/// Read the paired devices from the BlueZ storage (`<adapter>/<device>/info` files with a `[LinkKey]` section)
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+fn bluez_paired_devices(storage: &Path) -> Result<Vec<BluetoothDeviceInfo>> {
+    let adapters = match fs::read_dir(storage) {
+        Ok(adapters) => adapters,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut devices = Vec::new();
+    for adapter in adapters {
+        let adapter = adapter?.path();
+        let Some(adapter_address) = adapter
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| parse_bluetooth_address(name).is_ok())
+        else {
+            continue;
+        };
+
+        for device in fs::read_dir(&adapter)? {
+            let device = device?.path();
+            let Some(address) = device
+                .file_name()
+                .and_then(|name| name.to_str())
+                .filter(|name| parse_bluetooth_address(name).is_ok())
+            else {
+                continue;
+            };
+            let Ok(info) = fs::read_to_string(device.join("info")) else {
+                continue;
+            };
+
+            let (mut section, mut name, mut class, mut paired) = ("", None, None, false);
+            for line in info.lines().map(str::trim) {
+                if let Some(header) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                    section = header;
+                    paired |= section == "LinkKey";
+                } else if section == "General"
+                    && let Some((key, value)) = line.split_once('=')
+                {
+                    match key {
+                        "Name" | "Alias" if name.is_none() || key == "Alias" => name = Some(value.to_owned()),
+                        "Class" => class = u32::from_str_radix(value.trim_start_matches("0x"), 16).ok(),
+                        _ => (),
+                    }
+                }
+            }
+
+            if paired {
+                devices.push(BluetoothDeviceInfo {
+                    address: address.to_owned(),
+                    name,
+                    adapter: adapter_address.to_owned(),
+                    class,
+                });
+            }
+        }
+    }
+    devices.sort_by(|a, b| a.address.cmp(&b.address));
+
+    Ok(devices)
+}
+
+// ================ BLE driver ================
+
+/// Size of the ATT header of a write (opcode and handle), not available for the data
+#[cfg(feature = "ble")]
+const ATT_WRITE_HEADER_SIZE: usize = 3;
+
+/// Bluetooth SIG base UUID (`00000000-0000-1000-8000-00805f9b34fb`)
+#[cfg(feature = "ble")]
+const BLE_BASE_UUID: u128 = 0x0000_0000_0000_1000_8000_0080_5F9B_34FB;
+
+/// BLE UUID of a GATT service or characteristic
+#[cfg(feature = "ble")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BleUuid(pub u128);
+
+#[cfg(feature = "ble")]
+impl BleUuid {
+    /// Create a UUID from a 16-bit short UUID
+    ///
+    /// ```
+    /// use escpos::driver::BleUuid;
+    ///
+    /// assert_eq!(BleUuid::from_u16(0x18F0).to_string(), "000018f0-0000-1000-8000-00805f9b34fb");
+    /// ```
+    pub const fn from_u16(uuid: u16) -> Self {
+        Self(BLE_BASE_UUID | ((uuid as u128) << 96))
+    }
+
+    /// Parse a UUID (`49535343-fe7d-4ae5-8fa9-9fafd205e455`) or a 16-bit short UUID (`ff02`)
+    ///
+    /// ```
+    /// use escpos::driver::BleUuid;
+    ///
+    /// assert_eq!(BleUuid::parse("FF02").unwrap(), BleUuid::from_u16(0xFF02));
+    /// assert!(BleUuid::parse("49535343-fe7d-4ae5-8fa9-9fafd205e455").is_ok());
+    /// assert!(BleUuid::parse("49535343fe7d").is_err());
+    /// ```
+    pub fn parse(uuid: &str) -> Result<Self> {
+        let error = || PrinterError::Input(format!("invalid BLE UUID: {uuid}"));
+
+        if uuid.len() == 4 {
+            return u16::from_str_radix(uuid, 16).map(Self::from_u16).map_err(|_| error());
+        }
+        let groups: Vec<&str> = uuid.split('-').collect();
+        if groups.iter().map(|group| group.len()).ne([8, 4, 4, 4, 12]) {
+            return Err(error());
+        }
+
+        u128::from_str_radix(&groups.concat(), 16)
+            .map(Self)
+            .map_err(|_| error())
+    }
+}
+
+#[cfg(feature = "ble")]
+impl fmt::Display for BleUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let uuid = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            uuid >> 96,
+            (uuid >> 80) & 0xFFFF,
+            (uuid >> 64) & 0xFFFF,
+            (uuid >> 48) & 0xFFFF,
+            uuid & 0xFFFF_FFFF_FFFF
+        )
+    }
+}
+
+/// BLE GATT write type
+#[cfg(feature = "ble")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BleWriteType {
+    /// Write request, acknowledged by the printer (slower, but the printer can not be overflowed)
+    #[default]
+    WithResponse,
+    /// Write command, not acknowledged (faster, use `BleOptions::chunk_delay` if the printer loses data)
+    WithoutResponse,
+}
+
+/// GATT transport used by the BLE driver
+///
+/// Implement this trait with the BLE library of your platform (`btleplug`, `bluer`, `esp32-nimble`, etc.): the
+/// connection to the printer, the service discovery and the MTU negotiation are done by the transport.
+#[cfg(feature = "ble")]
+pub trait GattTransport {
+    /// Negotiated ATT MTU
+    fn mtu(&self) -> Result<usize>;
+
+    /// Write a value to a characteristic
+    fn write(&self, service: BleUuid, characteristic: BleUuid, data: &[u8], write_type: BleWriteType) -> Result<()>;
+
+    /// Subscribe to the notifications of a characteristic
+    fn subscribe(&self, service: BleUuid, characteristic: BleUuid) -> Result<()>;
+
+    /// Wait for the next notification of the subscribed characteristic (`None` if no notification is received before
+    /// the timeout)
+    fn notification(&self, timeout: Duration) -> Result<Option<Vec<u8>>>;
+}
+
+/// BLE driver options
+///
+/// The default service and characteristics (`18f0`, write `2af1` and notify `2af0`) are the ones of most of the
+/// cheap 58 mm thermal printers.
+#[cfg(feature = "ble")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BleOptions {
+    service: BleUuid,
+    write_characteristic: BleUuid,
+    notify_characteristic: Option<BleUuid>,
+    write_type: BleWriteType,
+    chunk_size: Option<usize>,
+    chunk_delay: Duration,
+    timeout: Duration,
+}
+
+#[cfg(feature = "ble")]
+impl Default for BleOptions {
+    fn default() -> Self {
+        Self {
+            service: BleUuid::from_u16(0x18F0),
+            write_characteristic: BleUuid::from_u16(0x2AF1),
+            notify_characteristic: Some(BleUuid::from_u16(0x2AF0)),
+            write_type: BleWriteType::default(),
+            chunk_size: None,
+            chunk_delay: Duration::ZERO,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+        }
+    }
+}
+
+#[cfg(feature = "ble")]
+impl BleOptions {
+    /// Set the GATT service
+    pub fn service(mut self, service: BleUuid) -> Self {
+        self.service = service;
+        self
+    }
+
+    /// Set the characteristic to write the data
+    pub fn write_characteristic(mut self, characteristic: BleUuid) -> Self {
+        self.write_characteristic = characteristic;
+        self
+    }
+
+    /// Set the characteristic notifying the printer responses (`None` if the printer does not send responses)
+    pub fn notify_characteristic(mut self, characteristic: Option<BleUuid>) -> Self {
+        self.notify_characteristic = characteristic;
+        self
+    }
+
+    /// Set the write type
+    pub fn write_type(mut self, write_type: BleWriteType) -> Self {
+        self.write_type = write_type;
+        self
+    }
+
+    /// Set the size of the written chunks (MTU - 3 by default)
+    pub fn chunk_size(mut self, chunk_size: Option<usize>) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Set the delay between two written chunks (none by default)
+    pub fn chunk_delay(mut self, delay: Duration) -> Self {
+        self.chunk_delay = delay;
+        self
+    }
+
+    /// Set the max time to wait for a notification when reading
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Driver for Bluetooth Low Energy printer using a GATT characteristic
+///
+/// The data is split in chunks fitting the MTU and written to the write characteristic, and the printer responses
+/// are received with the notifications of the notify characteristic.
+///
+/// # Example
+///
+/// ```no_run
+/// use escpos::driver::*;
+/// use escpos::errors::Result;
+/// use escpos::printer::Printer;
+/// use escpos::utils::*;
+/// use std::time::Duration;
+///
+/// // Connection to the printer with the BLE library of the platform
+/// struct Transport;
+///
+/// impl GattTransport for Transport {
+///     fn mtu(&self) -> Result<usize> {
+///         Ok(185)
+///     }
+///
+///     fn write(&self, _service: BleUuid, _characteristic: BleUuid, _data: &[u8], _write_type: BleWriteType) -> Result<()> {
+///         Ok(())
+///     }
+///
+///     fn subscribe(&self, _service: BleUuid, _characteristic: BleUuid) -> Result<()> {
+///         Ok(())
+///     }
+///
+///     fn notification(&self, _timeout: Duration) -> Result<Option<Vec<u8>>> {
+///         Ok(None)
+///     }
+/// }
+///
+/// let options = BleOptions::default()
+///     .service(BleUuid::from_u16(0xFF00))
+///     .write_characteristic(BleUuid::from_u16(0xFF02))
+///     .notify_characteristic(Some(BleUuid::from_u16(0xFF01)))
+///     .write_type(BleWriteType::WithoutResponse);
+/// let driver = BleDriver::open(Transport, options).unwrap();
+/// let mut printer = Printer::new(driver, Protocol::default(), None);
+/// ```
+#[cfg(feature = "ble")]
+pub struct BleDriver<T: GattTransport> {
+    transport: Arc<T>,
+    options: BleOptions,
+    chunk_size: usize,
+    notifications: Arc<Mutex<VecDeque<u8>>>,
+}
+
+#[cfg(feature = "ble")]
+impl<T: GattTransport> Clone for BleDriver<T> {
+    fn clone(&self) -> Self {
+        Self {
+            transport: self.transport.clone(),
+            options: self.options.clone(),
+            chunk_size: self.chunk_size,
+            notifications: self.notifications.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "ble")]
+impl<T: GattTransport> BleDriver<T> {
+    /// Open the BLE driver on a connected GATT transport and subscribe to the notifications
+    pub fn open(transport: T, options: BleOptions) -> Result<Self> {
+        let chunk_size = match options.chunk_size {
+            Some(chunk_size) => chunk_size,
+            None => transport.mtu()?.saturating_sub(ATT_WRITE_HEADER_SIZE),
+        };
+        if chunk_size == 0 {
+            return Err(PrinterError::Input("invalid BLE chunk size: 0".to_owned()));
+        }
+        if let Some(characteristic) = options.notify_characteristic {
+            transport.subscribe(options.service, characteristic)?;
         }
-        let port = port.open().map_err(|e| PrinterError::Io(e.to_string()))?;
 
         Ok(Self {
-            path: path.to_string(),
-            port: Arc::new(Mutex::new(port)),
+            transport: Arc::new(transport),
+            options,
+            chunk_size,
+            notifications: Arc::new(Mutex::new(VecDeque::new())),
         })
     }
+
+    /// Get the GATT transport
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Get the size of the written chunks
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
 }
 
-#[cfg(feature = "serial_port")]
-impl Driver for SerialPortDriver {
+#[cfg(feature = "ble")]
+impl<T: GattTransport> Driver for BleDriver<T> {
     fn name(&self) -> String {
-        format!("Serial port ({})", self.path)
+        format!(
+            "BLE (service: {}, characteristic: {})",
+            self.options.service, self.options.write_characteristic
+        )
     }
 
     fn write(&self, data: &[u8]) -> Result<()> {
-        self.port.lock()?.write_all(data)?;
+        for (i, chunk) in data.chunks(self.chunk_size).enumerate() {
+            if i > 0 && !self.options.chunk_delay.is_zero() {
+                thread::sleep(self.options.chunk_delay);
+            }
+            self.transport.write(
+                self.options.service,
+                self.options.write_characteristic,
+                chunk,
+                self.options.write_type,
+            )?;
+        }
 
         Ok(())
     }
 
+    /// Read the notified data, or return `0` if no notification is received before the timeout
     fn read(&self, buf: &mut [u8]) -> Result<usize> {
-        let mut port = self.port.lock()?;
-        port.set_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS))
-            .map_err(|e| PrinterError::Io(e.to_string()))?;
-        Ok(port.read(buf)?)
+        self.read_timeout(buf, self.options.timeout)
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], timeout: Duration) -> Result<usize> {
+        if self.options.notify_characteristic.is_none() {
+            return Err(PrinterError::Io("no BLE notify characteristic".to_owned()));
+        }
+
+        let mut notifications = self.notifications.lock()?;
+        if notifications.is_empty() {
+            match self.transport.notification(timeout)? {
+                Some(data) => notifications.extend(data),
+                None => return Ok(0),
+            }
+        }
+
+        let size = buf.len().min(notifications.len());
+        for (byte, notified) in buf.iter_mut().zip(notifications.drain(..size)) {
+            *byte = notified;
+        }
+
+        Ok(size)
+    }
+
+    /// Get the number of notified bytes not read yet
+    fn available(&self) -> Result<usize> {
+        Ok(self.notifications.lock()?.len())
     }
 
     fn flush(&self) -> Result<()> {
-        Ok(self.port.lock()?.flush()?)
+        Ok(())
+    }
+
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            bidirectional: self.options.notify_characteristic.is_some(),
+            read_timeout: true,
+            available: true,
+        }
     }
 }
 
@@ -1055,6 +4478,1022 @@ fn parse_vid_pid(path: &str) -> (Option<u16>, Option<u16>) {
     (parse_hex_after(path, "vid_"), parse_hex_after(path, "pid_"))
 }
 
+#[cfg(test)]
+mod driver_mut_tests {
+    use super::*;
+    use crate::printer::Printer;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    /// Driver owning its data, without interior mutability
+    #[derive(Default)]
+    struct MemoryDriver {
+        data: Vec<u8>,
+        responses: Vec<u8>,
+        closed: bool,
+    }
+
+    impl DriverMut for MemoryDriver {
+        fn name(&self) -> String {
+            "memory".to_owned()
+        }
+
+        fn write(&mut self, data: &[u8]) -> Result<()> {
+            self.data.extend_from_slice(data);
+            Ok(())
+        }
+
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let size = buf.len().min(self.responses.len());
+            buf[..size].copy_from_slice(&self.responses[..size]);
+            self.responses.drain(..size);
+            Ok(size)
+        }
+
+        fn available(&mut self) -> Result<usize> {
+            Ok(self.responses.len())
+        }
+
+        fn flush(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn close(&mut self) -> Result<()> {
+            self.closed = true;
+            Ok(())
+        }
+
+        fn capabilities(&self) -> DriverCapabilities {
+            DriverCapabilities {
+                available: true,
+                ..DriverCapabilities::default()
+            }
+        }
+    }
+
+    /// Read the available data of a driver
+    fn read_available(driver: &mut impl DriverMut) -> Result<usize> {
+        let mut buf = [0; 8];
+        let available = driver.available()?;
+        driver.read_timeout(&mut buf[..available], Duration::from_millis(10))
+    }
+
+    #[test]
+    fn test_shared_driver() {
+        let driver = SharedDriver::new(MemoryDriver {
+            responses: vec![0x12],
+            ..MemoryDriver::default()
+        });
+        Printer::new(driver.clone(), Protocol::default(), None)
+            .init()
+            .unwrap()
+            .print()
+            .unwrap();
+        assert_eq!(driver.lock().unwrap().data, [0x1B, 0x40]);
+        assert_eq!(driver.name(), "memory");
+        assert!(driver.capabilities().available);
+
+        assert_eq!(driver.available().unwrap(), 1);
+        let mut buf = [0; 2];
+        assert_eq!(driver.read_timeout(&mut buf, Duration::from_millis(10)).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+
+        driver.close().unwrap();
+        assert!(driver.lock().unwrap().closed);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_driver_poisoned() {
+        let driver = SharedDriver::new(MemoryDriver::default());
+        let shared = driver.clone();
+        std::thread::spawn(move || {
+            let _guard = shared.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join()
+        .unwrap_err();
+
+        assert!(driver.write(&[1]).is_err());
+        assert_eq!(driver.name(), "memory");
+        assert!(driver.capabilities().available);
+    }
+
+    #[test]
+    fn test_driver_as_driver_mut() {
+        let driver = SharedDriver::new(MemoryDriver {
+            responses: vec![1, 2, 3],
+            ..MemoryDriver::default()
+        });
+        assert_eq!(read_available(&mut &driver).unwrap(), 3);
+        assert_eq!(DriverMut::name(&&driver), "memory");
+
+        #[cfg(feature = "std")]
+        {
+            let console = ConsoleDriver::open(false);
+            assert!(!DriverMut::capabilities(&&console).bidirectional);
+            assert_eq!(read_available(&mut &console).unwrap(), 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod chunked_tests {
+    use super::*;
+    use alloc::collections::VecDeque;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+
+    /// Driver recording the writes and returning queued responses
+    #[derive(Default)]
+    pub(super) struct MockDriver {
+        pub(super) writes: RefCell<Vec<Vec<u8>>>,
+        pub(super) responses: RefCell<VecDeque<u8>>,
+    }
+
+    impl Driver for MockDriver {
+        fn name(&self) -> String {
+            "mock".to_owned()
+        }
+
+        fn write(&self, data: &[u8]) -> Result<()> {
+            self.writes.borrow_mut().push(data.to_vec());
+            Ok(())
+        }
+
+        fn read(&self, buf: &mut [u8]) -> Result<usize> {
+            match (buf.first_mut(), self.responses.borrow_mut().pop_front()) {
+                (Some(byte), Some(response)) => {
+                    *byte = response;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+
+        fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn no_sleep(_duration: Duration) {}
+
+    #[test]
+    fn test_chunked_driver_write() {
+        let driver = ChunkedDriver::new(MockDriver::default(), 4);
+        driver.write(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).unwrap();
+        assert_eq!(
+            *driver.driver().writes.borrow(),
+            vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10]]
+        );
+        assert_eq!(driver.name(), "mock (chunks of 4 bytes)");
+
+        let driver = ChunkedDriver::new(MockDriver::default(), 0);
+        driver.write(&[1, 2]).unwrap();
+        assert_eq!(driver.into_inner().writes.into_inner(), vec![vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn test_chunked_driver_status_pacing() {
+        let mock = MockDriver::default();
+        // Offline (0x1A), then online (0x12)
+        mock.responses.borrow_mut().extend([0x1A, 0x12]);
+        let driver = ChunkedDriver::new(mock, 2)
+            .pacing(ChunkPacing::Status {
+                poll: Duration::from_millis(10),
+                attempts: 3,
+            })
+            .sleep(no_sleep);
+        driver.write(&[1, 2, 3]).unwrap();
+
+        let status = Protocol::default().real_time_status(RealTimeStatusRequest::Printer);
+        assert_eq!(
+            *driver.driver().writes.borrow(),
+            vec![status.clone(), status, vec![1, 2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn test_chunked_driver_without_sleep() {
+        // No sleep function, as in `no_std` mode
+        let driver = |pacing| ChunkedDriver {
+            sleep: None,
+            ..ChunkedDriver::new(MockDriver::default(), 2).pacing(pacing)
+        };
+
+        let delay = driver(ChunkPacing::Delay(Duration::from_millis(10)));
+        assert!(matches!(delay.write(&[1, 2, 3]), Err(PrinterError::Input(_))));
+        delay.write(&[1, 2]).unwrap();
+        driver(ChunkPacing::Delay(Duration::ZERO)).write(&[1, 2, 3]).unwrap();
+    }
+
+    #[test]
+    fn test_chunked_driver_status_pacing_errors() {
+        let pacing = ChunkPacing::Status {
+            poll: Duration::from_millis(10),
+            attempts: 1,
+        };
+
+        let mock = MockDriver::default();
+        mock.responses.borrow_mut().extend([0x1A, 0x1A, 0x12]);
+        let driver = ChunkedDriver::new(mock, 2).pacing(pacing).sleep(no_sleep);
+        assert!(matches!(driver.write(&[1, 2, 3]), Err(PrinterError::Io(_))));
+
+        let driver = ChunkedDriver::new(MockDriver::default(), 2).pacing(pacing);
+        assert!(matches!(
+            driver.write(&[1, 2, 3]),
+            Err(PrinterError::InvalidResponse(_))
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod middleware_tests {
+    use super::chunked_tests::MockDriver;
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("escpos-{name}-{}.rec", std::process::id()))
+    }
+
+    #[test]
+    fn test_tee_driver() {
+        let mock = MockDriver::default();
+        mock.responses.borrow_mut().push_back(0x12);
+        let driver = TeeDriver::new(mock, MockDriver::default());
+        driver.write(&[1, 2]).unwrap();
+        driver.write(&[3]).unwrap();
+
+        let mut buf = [0; 2];
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+        assert_eq!(driver.name(), "tee (mock, mock)");
+
+        let (primary, secondary) = driver.into_inner();
+        assert_eq!(primary.writes.into_inner(), vec![vec![1, 2], vec![3]]);
+        assert_eq!(secondary.writes.into_inner(), vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(encode_hex(&[0x1b, 0x40, 0x0a]), "1b400a");
+        assert_eq!(decode_hex("1b400A"), Some(vec![0x1b, 0x40, 0x0a]));
+        assert_eq!(decode_hex(""), Some(vec![]));
+        assert_eq!(decode_hex("1b4"), None);
+        assert_eq!(decode_hex("1g"), None);
+    }
+
+    #[test]
+    fn test_recording_driver() {
+        let path = temp_path("recording");
+        let mock = MockDriver::default();
+        mock.responses.borrow_mut().push_back(0x12);
+        let driver = RecordingDriver::open(mock, &path).unwrap();
+        driver.write(&[0x1b, 0x40]).unwrap();
+        driver.write(&[0x10, 0x04, 0x01]).unwrap();
+        let mut buf = [0; 1];
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(driver.read(&mut buf).unwrap(), 0);
+        driver.flush().unwrap();
+
+        let recording = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let lines = recording.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "# escpos recording: mock");
+        let events = lines[1..]
+            .iter()
+            .map(|line| line.split_whitespace().skip(1).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![vec!["W", "1b40"], vec!["W", "100401"], vec!["R", "12"]]);
+
+        // The recording can be played back
+        let replay = ReplayDriver::from_recording(&recording).unwrap().strict(true);
+        replay.write(&[0x1b, 0x40, 0x10, 0x04]).unwrap();
+        assert_eq!(replay.read(&mut buf).unwrap(), 0);
+        replay.write(&[0x01]).unwrap();
+        assert_eq!(replay.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+        assert!(replay.is_finished().unwrap());
+    }
+
+    #[test]
+    fn test_replay_driver() {
+        let recording = "# comment\n\n0.1 R 0102\n0.2 W 1b40\n0.3 R 03\n";
+        let driver = ReplayDriver::from_recording(recording).unwrap();
+        let mut buf = [0; 4];
+        assert_eq!(driver.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf[..2], [1, 2]);
+        driver.write(&[0xff, 0xff, 0xff]).unwrap();
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 3);
+        assert!(driver.is_finished().unwrap());
+
+        let driver = ReplayDriver::from_recording(recording).unwrap().strict(true);
+        assert!(driver.write(&[0x1b, 0x41]).is_err());
+        let driver = ReplayDriver::from_recording(recording).unwrap().strict(true);
+        assert!(driver.write(&[0x1b, 0x40, 0x00]).is_err());
+
+        assert!(ReplayDriver::from_recording("0.1 X 00").is_err());
+        assert!(ReplayDriver::from_recording("W 00").is_err());
+        assert!(ReplayDriver::from_recording("0.1 W 0").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod simulated_tests {
+    use super::*;
+    use crate::domain::{BitImageSize, RasterBitImage};
+    use crate::printer::Printer;
+    use crate::printer_options::PrinterOptions;
+
+    fn read_all(driver: &SimulatedDriver) -> Vec<u8> {
+        let mut buf = [0; 64];
+        let size = driver.read(&mut buf).unwrap();
+        buf[..size].to_vec()
+    }
+
+    #[test]
+    fn test_simulated_driver_real_time_status() {
+        let driver = SimulatedDriver::new();
+        let protocol = Protocol::default();
+        driver
+            .write(&protocol.real_time_status(RealTimeStatusRequest::Printer))
+            .unwrap();
+        assert_eq!(read_all(&driver), [0x12]);
+
+        driver.set_cover_open(true).unwrap();
+        assert!(!driver.status(RealTimeStatusResponse::Online).unwrap());
+        // Request split in two writes
+        let request = protocol.real_time_status(RealTimeStatusRequest::OfflineCause);
+        driver.write(&[b'a', request[0]]).unwrap();
+        assert_eq!(read_all(&driver), []);
+        driver.write(&request[1..]).unwrap();
+        let response = read_all(&driver);
+        let status = RealTimeStatusResponse::parse(RealTimeStatusRequest::OfflineCause, response[0]).unwrap();
+        assert!(!status[&RealTimeStatusResponse::CoverClosed]);
+
+        driver.set_cover_open(false).unwrap();
+        driver.set_status(RealTimeStatusResponse::InkEndDetected, true).unwrap();
+        driver
+            .write(&protocol.real_time_status(RealTimeStatusRequest::Printer))
+            .unwrap();
+        driver
+            .write(&protocol.real_time_status(RealTimeStatusRequest::InkA))
+            .unwrap();
+        assert_eq!(read_all(&driver), [0x12, 0x1A]);
+        assert_eq!(driver.written().unwrap().len(), 17);
+    }
+
+    #[test]
+    fn test_simulated_driver_transmit_status_and_printer_id() {
+        let driver = SimulatedDriver::new();
+        driver.write(&[GS, b'r', 1, GS, b'r', 2]).unwrap();
+        assert_eq!(read_all(&driver), [0x00, 0x00]);
+
+        driver.set_paper_end(true).unwrap();
+        driver.write(&[GS, b'r', 49]).unwrap();
+        assert_eq!(read_all(&driver), [0x0F]);
+        assert!(!driver.status(RealTimeStatusResponse::Online).unwrap());
+
+        driver.write(&[GS, b'I', 1, GS, b'I', 67]).unwrap();
+        assert_eq!(read_all(&driver), b"\x20_Simulated printer\x00");
+    }
+
+    #[test]
+    fn test_simulated_driver_command_data() {
+        // The requests in the data of a command are not answered, except the real-time requests
+        let driver = SimulatedDriver::new();
+        driver.write(&[GS, b'v', b'0', 0, 4, 0]).unwrap();
+        driver.write(&[1, 0, GS, b'r', 1, DLE]).unwrap();
+        assert_eq!(read_all(&driver), []);
+        driver.write(&[EOT, 1, GS, b'r', 1]).unwrap();
+        assert_eq!(read_all(&driver), [0x12, 0x00]);
+
+        driver.write(&[ESC, b'*', 33, 1, 0, GS, b'I', 1, GS, b'I', 2]).unwrap();
+        assert_eq!(read_all(&driver), [0x02]);
+        driver.write(&[GS, b'k', 73, 3, GS, b'r', 1]).unwrap();
+        driver.write(&[GS, b'(', b'k', 3, 0, GS, b'r', 1]).unwrap();
+        driver.write(&[GS, b'8', b'L', 3, 0, 0, 0, GS, b'r', 1]).unwrap();
+        assert_eq!(read_all(&driver), []);
+
+        // Raster image containing `GS r 1`, printed in bands synchronized with `GS r 1`
+        let mut options = PrinterOptions::default();
+        options.bit_image_band_height(Some(1));
+        options.bit_image_band_sync(true);
+        let raster = RasterBitImage::new(24, 2, vec![GS, b'r', 1, GS, b'r', 1], BitImageSize::Normal).unwrap();
+        let mut printer = Printer::new(driver.clone(), Protocol::default(), Some(options));
+        printer.raster_bit_image(&raster).unwrap().print().unwrap();
+        assert_eq!(driver.available().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_simulated_driver_failures() {
+        let driver = SimulatedDriver::new();
+        driver.fail_writes_after(Some(5)).unwrap();
+        driver.write(&[1, 2, 3]).unwrap();
+        assert!(driver.write(&[4, 5, 6]).is_err());
+        assert!(driver.write(&[7]).is_err());
+        assert_eq!(driver.written().unwrap(), [1, 2, 3, 4, 5]);
+        driver.fail_writes_after(None).unwrap();
+        driver.write(&[7]).unwrap();
+
+        driver.write(&[DLE, EOT, 1]).unwrap();
+        driver.set_read_timeout(true).unwrap();
+        assert!(driver.read(&mut [0; 1]).is_err());
+        driver.set_read_timeout(false).unwrap();
+        assert_eq!(read_all(&driver), [0x12]);
+
+        driver.write(&[GS, b'I', 2]).unwrap();
+        assert_eq!(driver.available().unwrap(), 1);
+
+        driver.close().unwrap();
+        assert!(driver.write(&[1]).is_err());
+        assert!(driver.read(&mut [0; 1]).is_err());
+        assert!(driver.flush().is_err());
+        driver.reconnect().unwrap();
+        driver.write(&[1]).unwrap();
+    }
+
+    #[test]
+    fn test_simulated_driver_with_chunked_driver() {
+        let simulated = SimulatedDriver::new();
+        simulated.set_cover_open(true).unwrap();
+        let driver = ChunkedDriver::new(simulated.clone(), 2)
+            .pacing(ChunkPacing::Status {
+                poll: Duration::ZERO,
+                attempts: 2,
+            })
+            .sleep(|_| {});
+        assert!(driver.write(&[1, 2, 3]).is_err());
+
+        simulated.set_cover_open(false).unwrap();
+        simulated.clear_written().unwrap();
+        driver.write(&[1, 2, 3]).unwrap();
+        assert_eq!(simulated.written().unwrap(), [DLE, EOT, 1, 0, 1, 2, 3]);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod network_tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn options() -> NetworkOptions {
+        NetworkOptions::default()
+            .connect_timeout(Some(Duration::from_secs(1)))
+            .reconnect_delay(Duration::from_millis(10))
+    }
+
+    fn receive(listener: &TcpListener, size: usize) -> (TcpStream, Vec<u8>) {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut data = vec![0; size];
+        stream.read_exact(&mut data).unwrap();
+        (stream, data)
+    }
+
+    #[test]
+    fn test_network_driver_hostname() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let driver = NetworkDriver::open("localhost", port, Some(Duration::from_secs(1))).unwrap();
+        driver.write(b"hello").unwrap();
+        let (_stream, data) = receive(&listener, 5);
+        assert_eq!(data, b"hello");
+        assert!(driver.is_connected());
+    }
+
+    #[test]
+    fn test_network_driver_lazy() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let driver = NetworkDriver::open_with_options("127.0.0.1", port, options().lazy(true).nodelay(true)).unwrap();
+        assert!(!driver.is_connected());
+        driver.flush().unwrap();
+        assert!(driver.read(&mut [0; 1]).is_err());
+
+        driver.write(b"lazy").unwrap();
+        let (_stream, data) = receive(&listener, 4);
+        assert_eq!(data, b"lazy");
+        assert!(driver.is_connected());
+    }
+
+    #[test]
+    fn test_network_driver_read_timeout_and_available() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let driver = NetworkDriver::open_with_options("127.0.0.1", port, options()).unwrap();
+        driver.write(b"status").unwrap();
+        let (mut stream, _) = receive(&listener, 6);
+        assert_eq!(driver.available().unwrap(), 0);
+        assert!(driver.read_timeout(&mut [0; 1], Duration::from_millis(10)).is_err());
+        assert!(driver.capabilities().read_timeout);
+
+        stream.write_all(&[0x12, 0x00]).unwrap();
+        let mut attempts = 0;
+        while driver.available().unwrap() < 2 && attempts < 100 {
+            thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        let mut buf = [0; 2];
+        assert_eq!(driver.read_timeout(&mut buf, Duration::from_millis(100)).unwrap(), 2);
+        assert_eq!(buf, [0x12, 0x00]);
+
+        driver.close().unwrap();
+        assert!(!driver.is_connected());
+    }
+
+    #[test]
+    fn test_network_driver_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let driver = NetworkDriver::open_with_options("127.0.0.1", port, options()).unwrap();
+        driver.write(b"first").unwrap();
+        let (stream, data) = receive(&listener, 5);
+        assert_eq!(data, b"first");
+
+        // The printer closes the connection
+        drop(stream);
+        let mut attempts = 0;
+        while driver.is_connected() && attempts < 100 {
+            thread::sleep(Duration::from_millis(10));
+            attempts += 1;
+        }
+        assert!(!driver.is_connected());
+
+        driver.write(b"second").unwrap();
+        let (_stream, data) = receive(&listener, 6);
+        assert_eq!(data, b"second");
+        assert!(driver.is_connected());
+
+        driver.disconnect().unwrap();
+        assert!(!driver.is_connected());
+        driver.connect().unwrap();
+        assert!(driver.is_connected());
+    }
+
+    #[cfg(feature = "tcp_keepalive")]
+    #[test]
+    fn test_network_driver_keepalive() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let options = options().keepalive(Some(Duration::from_secs(10)));
+        let driver = NetworkDriver::open_with_options("127.0.0.1", port, options).unwrap();
+        driver.write(b"data").unwrap();
+        let (_stream, data) = receive(&listener, 4);
+        assert_eq!(data, b"data");
+    }
+
+    #[test]
+    fn test_network_driver_connection_failure() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        assert!(NetworkDriver::open_with_options("127.0.0.1", port, options()).is_err());
+
+        let driver =
+            NetworkDriver::open_with_options("127.0.0.1", port, options().lazy(true).reconnect_attempts(2)).unwrap();
+        assert!(driver.write(b"data").is_err());
+        assert!(driver.connect().is_err());
+    }
+}
+
+#[cfg(all(test, feature = "hidapi"))]
+mod hidapi_tests {
+    use super::*;
+
+    #[test]
+    fn test_hidapi_device_info_is_printer_candidate() {
+        let info = |usage_page| HidApiDeviceInfo {
+            path: "/dev/hidraw0".to_owned(),
+            vendor_id: 0x04b8,
+            product_id: 0x0202,
+            manufacturer: None,
+            product: None,
+            serial_number: None,
+            interface_number: 0,
+            usage_page,
+            usage: 1,
+        };
+
+        assert!(info(0xFF00).is_printer_candidate());
+        assert!(info(0xFFA0).is_printer_candidate());
+        assert!(info(0).is_printer_candidate());
+        // Generic desktop (keyboards, mice), consumer and barcode scanner usage pages
+        assert!(!info(0x01).is_printer_candidate());
+        assert!(!info(0x0C).is_printer_candidate());
+        assert!(!info(0x8C).is_printer_candidate());
+    }
+}
+
+#[cfg(all(test, feature = "serial_port", unix))]
+mod serial_tests {
+    use super::*;
+    use serialport::TTYPort;
+
+    /// Open a pseudo-terminal pair and a driver on the slave side, returning the master side
+    fn open(options: SerialPortOptions) -> (TTYPort, SerialPortDriver) {
+        let (master, slave) = TTYPort::pair().unwrap();
+        let path = slave.name().unwrap();
+        let driver = SerialPortDriver::open_with_options(&path, options).unwrap();
+        (master, driver)
+    }
+
+    #[test]
+    fn test_serial_port_driver_options() {
+        let options = SerialPortOptions::new(19_200)
+            .data_bits(DataBits::Seven)
+            .parity(Parity::Even)
+            .stop_bits(StopBits::One)
+            .timeout(Duration::from_millis(100));
+        let (mut master, driver) = open(options);
+
+        driver.write(b"7E1").unwrap();
+        let mut data = [0; 3];
+        master.read_exact(&mut data).unwrap();
+        assert_eq!(&data, b"7E1");
+
+        master.write_all(&[0x12]).unwrap();
+        let mut buf = [0; 1];
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+    }
+
+    #[test]
+    fn test_serial_port_driver_xon_xoff_pacing() {
+        let options = SerialPortOptions::new(9_600)
+            .flow_control(SerialFlowControl::XonXoffPacing)
+            .chunk_size(2)
+            .timeout(Duration::from_secs(2));
+        let (mut master, driver) = open(options);
+
+        // The printer is busy and sends a status byte
+        master.write_all(&[XOFF, 0x12]).unwrap();
+        let mut resume = master.try_clone_native().unwrap();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            resume.write_all(&[XON]).unwrap();
+        });
+
+        let start = Instant::now();
+        driver.write(b"paced").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        handle.join().unwrap();
+
+        let mut data = [0; 5];
+        master.read_exact(&mut data).unwrap();
+        assert_eq!(&data, b"paced");
+
+        // The status byte received while pacing is kept
+        let mut buf = [0; 4];
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+
+        // A binary response is not filtered
+        master.write_all(&[0x37, XON, XOFF, 0x00]).unwrap();
+        let mut response = Vec::new();
+        while response.len() < 4 {
+            let size = driver.read(&mut buf).unwrap();
+            assert!(size > 0);
+            response.extend_from_slice(&buf[..size]);
+        }
+        assert_eq!(response, [0x37, XON, XOFF, 0x00]);
+        driver.write(b"ok").unwrap();
+    }
+
+    #[test]
+    fn test_serial_port_driver_xoff_timeout() {
+        let options = SerialPortOptions::new(9_600)
+            .flow_control(SerialFlowControl::XonXoffPacing)
+            .timeout(Duration::from_millis(50));
+        let (mut master, driver) = open(options);
+
+        master.write_all(&[XOFF]).unwrap();
+        thread::sleep(Duration::from_millis(10));
+        assert!(driver.write(b"busy").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "usblp", target_os = "linux"))]
+mod lp_tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::os::fd::FromRawFd;
+
+    /// Open a pseudo-terminal in raw mode, returning the master side and the path of the slave side
+    fn pty() -> (File, PathBuf) {
+        unsafe {
+            let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            assert!(master >= 0);
+            assert_eq!(libc::grantpt(master), 0);
+            assert_eq!(libc::unlockpt(master), 0);
+
+            let mut termios = std::mem::zeroed();
+            assert_eq!(libc::tcgetattr(master, &mut termios), 0);
+            libc::cfmakeraw(&mut termios);
+            assert_eq!(libc::tcsetattr(master, libc::TCSANOW, &termios), 0);
+
+            let mut name = [0; 128];
+            assert_eq!(libc::ptsname_r(master, name.as_mut_ptr(), name.len()), 0);
+            let path = PathBuf::from(CStr::from_ptr(name.as_ptr()).to_string_lossy().to_string());
+
+            (File::from_raw_fd(master), path)
+        }
+    }
+
+    #[test]
+    fn test_lp_device_id_parse() {
+        let id = LpDeviceId::parse(
+            "MANUFACTURER:EPSON;COMMAND SET:ESC/POS, ESCPOS;MODEL:TM-T20II;CLASS:PRINTER;DESCRIPTION:EPSON TM-T20II;SN:X1;",
+        );
+        assert_eq!(id.manufacturer.as_deref(), Some("EPSON"));
+        assert_eq!(id.model.as_deref(), Some("TM-T20II"));
+        assert_eq!(id.command_set, ["ESC/POS", "ESCPOS"]);
+        assert_eq!(id.class.as_deref(), Some("PRINTER"));
+        assert_eq!(id.description.as_deref(), Some("EPSON TM-T20II"));
+        assert_eq!(id.serial_number.as_deref(), Some("X1"));
+        assert!(id.supports("esc/pos"));
+        assert!(!id.supports("ZPL"));
+
+        let id = LpDeviceId::parse("invalid;MDL:T88;");
+        assert_eq!(id.manufacturer, None);
+        assert_eq!(id.model.as_deref(), Some("T88"));
+        assert!(id.command_set.is_empty());
+    }
+
+    #[test]
+    fn test_lp_driver() {
+        let (mut master, path) = pty();
+        let driver = LpDriver::open(&path, Some(Duration::from_millis(50))).unwrap();
+
+        driver.write(b"\x1B@").unwrap();
+        driver.flush().unwrap();
+        let mut data = [0; 2];
+        master.read_exact(&mut data).unwrap();
+        assert_eq!(data, [0x1B, b'@']);
+
+        // No status response before the timeout
+        let mut buf = [0; 4];
+        assert_eq!(driver.read(&mut buf).unwrap(), 0);
+
+        master.write_all(&[0x16]).unwrap();
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x16);
+
+        // A pseudo-terminal has no IEEE 1284 device ID
+        assert!(driver.device_id().is_err());
+    }
+
+    #[test]
+    fn test_parse_lp_device_id() {
+        // Buffer of the ioctl: length (2 bytes included), device ID and the rest of the buffer
+        let device_id = b"MFG:EPSON;CMD:ESC/POS;MDL:TM-T20II;CLS:PRINTER;";
+        let mut buf = [0xFF; LP_DEVICE_ID_SIZE];
+        buf[..2].copy_from_slice(&(device_id.len() as u16 + 2).to_be_bytes());
+        buf[2..2 + device_id.len()].copy_from_slice(device_id);
+
+        let id = parse_lp_device_id(&buf);
+        assert_eq!(id.raw.as_bytes(), device_id);
+        assert_eq!(id.manufacturer.as_deref(), Some("EPSON"));
+        assert_eq!(id.model.as_deref(), Some("TM-T20II"));
+        assert_eq!(id.command_set, ["ESC/POS"]);
+        assert_eq!(id.class.as_deref(), Some("PRINTER"));
+
+        // The length is limited to the buffer
+        buf[..2].copy_from_slice(&[0xFF, 0xFF]);
+        assert_eq!(
+            parse_lp_device_id(&buf[..2 + device_id.len()]).model.as_deref(),
+            Some("TM-T20II")
+        );
+        // Empty device ID
+        assert_eq!(parse_lp_device_id(&[0, 0]).manufacturer, None);
+        assert_eq!(parse_lp_device_id(&[]).manufacturer, None);
+    }
+}
+
+#[cfg(all(test, feature = "bluetooth", target_os = "linux"))]
+mod bluetooth_tests {
+    use super::*;
+    use std::os::unix::net::UnixStream;
+
+    #[test]
+    fn test_parse_bluetooth_address() {
+        assert_eq!(
+            parse_bluetooth_address("66:22:b3:4C:90:1F").unwrap(),
+            [0x1F, 0x90, 0x4C, 0xB3, 0x22, 0x66]
+        );
+        assert!(parse_bluetooth_address("66:22:B3:4C:90").is_err());
+        assert!(parse_bluetooth_address("66:22:B3:4C:90:1F:00").is_err());
+        assert!(parse_bluetooth_address("66:22:B3:4C:90:1G").is_err());
+        assert!(parse_bluetooth_address("662:2:B3:4C:90:1F").is_err());
+        assert!(BluetoothDriver::open("66:22:B3:4C:90", None, None).is_err());
+    }
+
+    #[test]
+    fn test_bluetooth_driver_transport() {
+        // A socket pair stands in for the RFCOMM socket
+        let (socket, mut printer) = UnixStream::pair().unwrap();
+        let socket = File::from(std::os::fd::OwnedFd::from(socket));
+        let driver = BluetoothDriver::from_socket("66:22:b3:4c:90:1f", 1, socket, Duration::from_millis(50));
+        assert_eq!(driver.name(), "Bluetooth (66:22:B3:4C:90:1F, channel 1)");
+
+        driver.write(b"\x1B@hello").unwrap();
+        driver.flush().unwrap();
+        let mut data = [0; 7];
+        printer.read_exact(&mut data).unwrap();
+        assert_eq!(&data, b"\x1B@hello");
+
+        let mut buf = [0; 4];
+        assert_eq!(driver.read(&mut buf).unwrap(), 0);
+        printer.write_all(&[0x12]).unwrap();
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x12);
+    }
+
+    #[test]
+    fn test_bluez_paired_devices() {
+        let storage = std::env::temp_dir().join(format!("escpos-bluez-{}", std::process::id()));
+        let adapter = storage.join("00:1A:7D:DA:71:13");
+        let write_info = |address: &str, info: &str| {
+            fs::create_dir_all(adapter.join(address)).unwrap();
+            fs::write(adapter.join(address).join("info"), info).unwrap();
+        };
+        write_info(
+            "66:22:B3:4C:90:1F",
+            "[General]\nName=MTP-II\nClass=0x040680\n\n[LinkKey]\nKey=0123456789ABCDEF0123456789ABCDEF\n",
+        );
+        write_info(
+            "11:22:33:44:55:66",
+            "[General]\nName=Phone\nAlias=My phone\nClass=0x5a020c\n\n[LinkKey]\nKey=00\n",
+        );
+        // Known but not paired
+        write_info("AA:BB:CC:DD:EE:FF", "[General]\nName=Headset\n");
+        fs::create_dir_all(adapter.join("cache")).unwrap();
+
+        let devices = bluez_paired_devices(&storage).unwrap();
+        fs::remove_dir_all(&storage).unwrap();
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].address, "11:22:33:44:55:66");
+        assert_eq!(devices[0].name.as_deref(), Some("My phone"));
+        assert!(!devices[0].is_printer());
+        assert_eq!(devices[1].address, "66:22:B3:4C:90:1F");
+        assert_eq!(devices[1].name.as_deref(), Some("MTP-II"));
+        assert_eq!(devices[1].adapter, "00:1A:7D:DA:71:13");
+        assert_eq!(devices[1].class, Some(0x040680));
+        assert!(devices[1].is_printer());
+
+        assert!(bluez_paired_devices(&storage).unwrap().is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "ble"))]
+mod ble_tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Recorded write: service, characteristic, data and write type
+    type Write = (BleUuid, BleUuid, Vec<u8>, BleWriteType);
+
+    /// GATT transport recording the writes and returning queued notifications
+    #[derive(Default)]
+    struct MockTransport {
+        mtu: usize,
+        writes: RefCell<Vec<Write>>,
+        subscriptions: RefCell<Vec<(BleUuid, BleUuid)>>,
+        notifications: RefCell<VecDeque<Vec<u8>>>,
+    }
+
+    impl GattTransport for MockTransport {
+        fn mtu(&self) -> Result<usize> {
+            Ok(self.mtu)
+        }
+
+        fn write(
+            &self,
+            service: BleUuid,
+            characteristic: BleUuid,
+            data: &[u8],
+            write_type: BleWriteType,
+        ) -> Result<()> {
+            self.writes
+                .borrow_mut()
+                .push((service, characteristic, data.to_vec(), write_type));
+            Ok(())
+        }
+
+        fn subscribe(&self, service: BleUuid, characteristic: BleUuid) -> Result<()> {
+            self.subscriptions.borrow_mut().push((service, characteristic));
+            Ok(())
+        }
+
+        fn notification(&self, _timeout: Duration) -> Result<Option<Vec<u8>>> {
+            Ok(self.notifications.borrow_mut().pop_front())
+        }
+    }
+
+    fn transport(mtu: usize) -> MockTransport {
+        MockTransport {
+            mtu,
+            ..MockTransport::default()
+        }
+    }
+
+    #[test]
+    fn test_ble_uuid() {
+        assert_eq!(
+            BleUuid::parse("49535343-FE7D-4AE5-8FA9-9FAFD205E455")
+                .unwrap()
+                .to_string(),
+            "49535343-fe7d-4ae5-8fa9-9fafd205e455"
+        );
+        assert_eq!(BleUuid::parse("2af1").unwrap(), BleUuid::from_u16(0x2AF1));
+        assert!(BleUuid::parse("2af").is_err());
+        assert!(BleUuid::parse("49535343-fe7d-4ae5-8fa9-9fafd205e45g").is_err());
+        assert!(BleUuid::parse("49535343fe7d-4ae5-8fa9-9fafd205e455-").is_err());
+    }
+
+    #[test]
+    fn test_ble_driver_chunks() {
+        let driver = BleDriver::open(transport(23), BleOptions::default()).unwrap();
+        assert_eq!(driver.chunk_size(), 20);
+        assert_eq!(
+            driver.transport().subscriptions.borrow().as_slice(),
+            [(BleUuid::from_u16(0x18F0), BleUuid::from_u16(0x2AF0))]
+        );
+
+        let data: Vec<u8> = (0..45).collect();
+        driver.write(&data).unwrap();
+        let writes = driver.transport().writes.borrow();
+        assert_eq!(writes.len(), 3);
+        assert_eq!(
+            writes.iter().map(|(_, _, chunk, _)| chunk.len()).collect::<Vec<_>>(),
+            [20, 20, 5]
+        );
+        assert_eq!(
+            writes
+                .iter()
+                .flat_map(|(_, _, chunk, _)| chunk.clone())
+                .collect::<Vec<_>>(),
+            data
+        );
+        assert!(writes.iter().all(|(service, characteristic, _, write_type)| {
+            *service == BleUuid::from_u16(0x18F0)
+                && *characteristic == BleUuid::from_u16(0x2AF1)
+                && *write_type == BleWriteType::WithResponse
+        }));
+    }
+
+    #[test]
+    fn test_ble_driver_options() {
+        let options = BleOptions::default()
+            .service(BleUuid::from_u16(0xFF00))
+            .write_characteristic(BleUuid::from_u16(0xFF02))
+            .notify_characteristic(None)
+            .write_type(BleWriteType::WithoutResponse)
+            .chunk_size(Some(4));
+        let driver = BleDriver::open(transport(185), options).unwrap();
+        assert!(driver.transport().subscriptions.borrow().is_empty());
+        assert_eq!(driver.chunk_size(), 4);
+
+        driver.write(b"\x1B@abcd").unwrap();
+        let writes = driver.transport().writes.borrow();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].1, BleUuid::from_u16(0xFF02));
+        assert_eq!(writes[1].3, BleWriteType::WithoutResponse);
+
+        assert!(driver.read(&mut [0; 1]).is_err());
+        assert!(BleDriver::open(transport(3), BleOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_ble_driver_notifications() {
+        let transport = transport(23);
+        transport
+            .notifications
+            .borrow_mut()
+            .extend([vec![0x12, 0x16, 0x1E], vec![0x14]]);
+        let driver = BleDriver::open(transport, BleOptions::default()).unwrap();
+
+        let mut buf = [0; 2];
+        assert_eq!(driver.available().unwrap(), 0);
+        assert_eq!(driver.read(&mut buf).unwrap(), 2);
+        assert_eq!(buf, [0x12, 0x16]);
+        assert_eq!(driver.available().unwrap(), 1);
+        assert_eq!(driver.read_timeout(&mut buf, Duration::ZERO).unwrap(), 1);
+        assert_eq!(buf[0], 0x1E);
+        assert_eq!(driver.read(&mut buf).unwrap(), 1);
+        assert_eq!(buf[0], 0x14);
+        assert_eq!(driver.read(&mut buf).unwrap(), 0);
+    }
+}
+
 #[cfg(all(test, feature = "usbprint"))]
 mod tests {
     use super::parse_vid_pid;
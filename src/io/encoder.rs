@@ -6,7 +6,7 @@ use alloc::vec::Vec;
 use encoding_rs::{Encoding, UTF_8};
 
 /// Encoder
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Encoder {
     codec: &'static Encoding,
     allow_unencodable: bool,
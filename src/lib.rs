@@ -195,6 +195,9 @@
 //! status…) work in `no_std` because the actual code rendering is done by the printer's firmware
 //! — this crate only serializes the ESC/POS command bytes.
 //!
+//! Images already packed in raster format (1 bit per dot) can be printed without the `graphics` feature
+//! with `RasterBitImage` and `Printer::raster_bit_image`.
+//!
 //! See `examples/no_std_codes.rs` for a minimal end-to-end example with a custom in-memory driver.
 //!
 //! [`Driver`]: crate::driver::Driver
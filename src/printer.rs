@@ -1,14 +1,20 @@
 //! Printer
 
-use super::errors::Result;
+use super::errors::{PrinterError, Result};
 #[cfg(feature = "ui")]
 use crate::domain::ui::line::Line;
 use crate::printer_options::PrinterOptions;
 use crate::{domain::*, driver::Driver, utils::Protocol};
+#[cfg(all(feature = "graphics", feature = "codes_2d"))]
+use alloc::borrow::ToOwned;
 use alloc::vec::Vec;
 use alloc::{format, vec};
 use log::debug;
 
+/// Size of the buffer used to read the printer responses
+#[cfg(any(feature = "graphics", feature = "codes_2d"))]
+const RESPONSE_BUFFER_SIZE: usize = 64;
+
 /// Printer
 ///
 /// Print a document
@@ -109,10 +115,11 @@ impl<D: Driver> Printer<D> {
     ///     assert_eq!(style_state.justify_mode, JustifyMode::default());
     ///     assert_eq!(style_state.underline_mode, UnderlineMode::default());
     ///     assert_eq!(style_state.text_size, (1, 1));
+    ///     assert_eq!(style_state.line_spacing, None);
     ///
     ///     printer.print()?;
     ///
-    ///     // Style state is reset after flushing the buffer
+    ///     // Style state is reset after flushing the buffer (except the line spacing)
     ///     assert_eq!(printer.style_state(), PrinterStyleState::default());
     ///
     ///     Ok(())
@@ -123,15 +130,33 @@ impl<D: Driver> Printer<D> {
     }
 
     /// Reset the printer style state
+    ///
+    /// The line spacing is kept: the printer keeps it until [`init`](Printer::init), [`reset`](Printer::reset)
+    /// or [`reset_line_spacing`](Printer::reset_line_spacing).
     pub fn reset_style_state(&mut self) -> &mut Self {
-        self.style_state = PrinterStyleState::default();
+        self.style_state = PrinterStyleState {
+            line_spacing: self.style_state.line_spacing,
+            ..PrinterStyleState::default()
+        };
         self
     }
 
     /// Flush the buffer, reset the style state and clean the instructions
     fn flush(&mut self) -> Result<&mut Self> {
         for instruction in self.instructions.iter() {
-            self.driver.write(&instruction.flatten_commands())?
+            self.driver.write(&instruction.flatten_commands())?;
+
+            if instruction.wait_response {
+                self.driver.flush()?;
+
+                let mut buffer = [0; 1];
+                if self.driver.read(&mut buffer)? == 0 {
+                    return Err(PrinterError::InvalidResponse(format!(
+                        "no response to \"{}\"",
+                        instruction.name
+                    )));
+                }
+            }
         }
         self.driver.flush()?;
         self.instructions = vec![];
@@ -140,6 +165,36 @@ impl<D: Driver> Printer<D> {
         Ok(self)
     }
 
+    /// Send a request to the printer without using the instructions buffer and read its response
+    ///
+    /// The response must be terminated by `NUL`.
+    #[cfg(any(feature = "graphics", feature = "codes_2d"))]
+    fn request(&mut self, label: &str, cmd: &[u8]) -> Result<Vec<u8>> {
+        if self.options.get_debug_mode().is_some() {
+            debug!(
+                "{:?}",
+                Instruction::new(label, &[cmd.to_vec()], self.options.get_debug_mode())
+            );
+        }
+
+        self.driver.write(cmd)?;
+        self.driver.flush()?;
+
+        let mut response = Vec::new();
+        let mut buffer = [0; RESPONSE_BUFFER_SIZE];
+        while response.last() != Some(&NUL) {
+            let size = self.driver.read(&mut buffer)?;
+            if size == 0 {
+                return Err(PrinterError::InvalidResponse(format!(
+                    "incomplete response to \"{label}\": {response:02X?}"
+                )));
+            }
+            response.extend_from_slice(&buffer[..size]);
+        }
+
+        Ok(response)
+    }
+
     /// Set debug mode
     pub fn debug_mode(&mut self, mode: Option<DebugMode>) -> &mut Self {
         self.options.debug_mode(mode);
@@ -177,6 +232,7 @@ impl<D: Driver> Printer<D> {
     pub fn init(&mut self) -> Result<&mut Self> {
         let cmd = self.protocol.init();
         self.command("initialization", &[cmd])?;
+        self.style_state.line_spacing = None;
 
         // Set page code
         if let Some(page_code) = self.options.get_page_code() {
@@ -190,7 +246,10 @@ impl<D: Driver> Printer<D> {
     /// Hardware reset
     pub fn reset(&mut self) -> Result<&mut Self> {
         let cmd = self.protocol.reset();
-        self.command("reset", &[cmd])
+        self.command("reset", &[cmd])?;
+        self.style_state.line_spacing = None;
+
+        Ok(self)
     }
 
     /// Paper full cut
@@ -308,12 +367,14 @@ impl<D: Driver> Printer<D> {
 
     /// Line spacing
     pub fn line_spacing(&mut self, value: u8) -> Result<&mut Self> {
+        self.style_state.line_spacing = Some(value);
         let cmd = self.protocol.line_spacing(value);
         self.command("line spacing", &[cmd])
     }
 
     /// Reset line spacing
     pub fn reset_line_spacing(&mut self) -> Result<&mut Self> {
+        self.style_state.line_spacing = None;
         let cmd = self.protocol.reset_line_spacing();
         self.command("reset line spacing", &[cmd])
     }
@@ -418,10 +479,32 @@ impl<D: Driver> Printer<D> {
     #[cfg(feature = "barcodes")]
     /// Print barcode
     fn barcode(&mut self, barcode: Barcode) -> Result<&mut Self> {
+        #[cfg(feature = "graphics")]
+        if self.is_code_image(barcode.option.code_rendering()) {
+            return self.barcode_image(barcode);
+        }
+
         let commands = self.protocol.barcode(&barcode.data, barcode.system, barcode.option)?;
         self.command(&format!("print {} barcode", barcode.system), commands.as_slice())
     }
 
+    #[cfg(all(feature = "barcodes", feature = "graphics"))]
+    /// Print barcode rendered as an image, the HRI characters are printed as text
+    fn barcode_image(&mut self, barcode: Barcode) -> Result<&mut Self> {
+        let (raster, hri) = barcode.render()?;
+        let position = barcode.option.position();
+
+        if matches!(position, BarcodePosition::Above | BarcodePosition::Both) {
+            self.writeln(&hri)?;
+        }
+        self.code_image(&format!("print {} barcode image", barcode.system), &raster)?;
+        if matches!(position, BarcodePosition::Below | BarcodePosition::Both) {
+            self.writeln(&hri)?;
+        }
+
+        Ok(self)
+    }
+
     #[cfg(feature = "barcodes")]
     /// Print EAN13 barcode with default option
     pub fn ean13(&mut self, data: &str) -> Result<&mut Self> {
@@ -506,29 +589,204 @@ impl<D: Driver> Printer<D> {
         self.barcode(Barcode::new(BarcodeSystem::ITF, data, option)?)
     }
 
+    #[cfg(feature = "barcodes")]
+    /// Print CODE128 barcode with default option
+    ///
+    /// The code sets A, B and C are selected automatically to get the shortest barcode.
+    pub fn code128(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::CODE128, data, BarcodeOption::default())?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print CODE128 barcode with option
+    ///
+    /// The code sets A, B and C are selected automatically to get the shortest barcode.
+    pub fn code128_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::CODE128, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print CODE93 barcode with default option
+    pub fn code93(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::CODE93, data, BarcodeOption::default())?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print CODE93 barcode with option
+    pub fn code93_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::CODE93, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1-128 barcode with default option
+    ///
+    /// The data is a GS1 element string with the AIs in parentheses (`(01)09501101530003(10)AB-123`).
+    pub fn gs1_128(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::GS1_128, data, BarcodeOption::default())?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1-128 barcode with option
+    ///
+    /// The data is a GS1 element string with the AIs in parentheses (`(01)09501101530003(10)AB-123`).
+    pub fn gs1_128_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::GS1_128, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Omnidirectional barcode with default option
+    ///
+    /// The data is the 13 digits of the GTIN, without the check digit.
+    pub fn gs1_databar_omnidirectional(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(
+            BarcodeSystem::GS1DataBarOmnidirectional,
+            data,
+            BarcodeOption::default(),
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Omnidirectional barcode with option
+    ///
+    /// The data is the 13 digits of the GTIN, without the check digit.
+    pub fn gs1_databar_omnidirectional_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::GS1DataBarOmnidirectional, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Truncated barcode with default option
+    ///
+    /// The data is the 13 digits of the GTIN, without the check digit.
+    pub fn gs1_databar_truncated(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(
+            BarcodeSystem::GS1DataBarTruncated,
+            data,
+            BarcodeOption::default(),
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Truncated barcode with option
+    ///
+    /// The data is the 13 digits of the GTIN, without the check digit.
+    pub fn gs1_databar_truncated_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::GS1DataBarTruncated, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Limited barcode with default option
+    ///
+    /// The data is the 13 digits of the GTIN (starting with `0` or `1`), without the check digit.
+    pub fn gs1_databar_limited(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(
+            BarcodeSystem::GS1DataBarLimited,
+            data,
+            BarcodeOption::default(),
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Limited barcode with option
+    ///
+    /// The data is the 13 digits of the GTIN (starting with `0` or `1`), without the check digit.
+    pub fn gs1_databar_limited_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::GS1DataBarLimited, data, option)?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Expanded barcode with default option
+    ///
+    /// The data is a GS1 element string with the AIs in parentheses (`(01)09501101530003(10)AB-123`).
+    pub fn gs1_databar_expanded(&mut self, data: &str) -> Result<&mut Self> {
+        self.barcode(Barcode::new(
+            BarcodeSystem::GS1DataBarExpanded,
+            data,
+            BarcodeOption::default(),
+        )?)
+    }
+
+    #[cfg(feature = "barcodes")]
+    /// Print GS1 DataBar Expanded barcode with option
+    ///
+    /// The data is a GS1 element string with the AIs in parentheses (`(01)09501101530003(10)AB-123`).
+    pub fn gs1_databar_expanded_option(&mut self, data: &str, option: BarcodeOption) -> Result<&mut Self> {
+        self.barcode(Barcode::new(BarcodeSystem::GS1DataBarExpanded, data, option)?)
+    }
+
     #[cfg(feature = "codes_2d")]
     /// Construct QR code
-    fn qrcode_builder(&mut self, data: &str, option: Option<QRCodeOption>) -> Result<&mut Self> {
-        let qrcode = QRCode::new(data, option)?;
-        let commands = self.protocol.qrcode(&qrcode.data, qrcode.option)?;
+    fn qrcode_builder(&mut self, data: &[u8], option: QRCodeOption) -> Result<&mut Self> {
+        option.check_data(data)?;
+
+        #[cfg(feature = "graphics")]
+        if self.is_code_image(option.code_rendering()) {
+            return self.code_image("print qrcode image", &option.raster(data)?);
+        }
+        if option.is_gs1() {
+            return Err(PrinterError::Input(
+                "GS1 QR codes can only be rendered as images".to_owned(),
+            ));
+        }
+
+        let commands = self.protocol.qrcode(data, option)?;
         self.command("print qrcode", commands.as_slice())
     }
 
     #[cfg(feature = "codes_2d")]
     /// Print QR code with default option
     pub fn qrcode(&mut self, data: &str) -> Result<&mut Self> {
-        self.qrcode_builder(data, None)
+        self.qrcode_option(data, QRCodeOption::default())
     }
 
     #[cfg(feature = "codes_2d")]
     /// Print QR code with option
     pub fn qrcode_option(&mut self, data: &str, option: QRCodeOption) -> Result<&mut Self> {
-        self.qrcode_builder(data, Some(option))
+        let data = option.encoder().encode(data)?;
+        self.qrcode_builder(&data, option)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print QR code with binary data and default option
+    pub fn qrcode_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.qrcode_builder(data, QRCodeOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print QR code with binary data and option
+    pub fn qrcode_bytes_option(&mut self, data: &[u8], option: QRCodeOption) -> Result<&mut Self> {
+        self.qrcode_builder(data, option)
+    }
+
+    #[cfg(all(feature = "codes_2d", feature = "graphics"))]
+    /// Print GS1 QR code (model 2, rendered as an image)
+    pub fn qrcode_gs1(&mut self, elements: &GS1ElementString) -> Result<&mut Self> {
+        let option =
+            QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M).rendering(CodeRendering::Image);
+        self.qrcode_gs1_option(elements, option)
+    }
+
+    #[cfg(all(feature = "codes_2d", feature = "graphics"))]
+    /// Print GS1 QR code with option
+    ///
+    /// The option GS1 mode is enabled. The QR code must be rendered as an image.
+    pub fn qrcode_gs1_option(&mut self, elements: &GS1ElementString, option: QRCodeOption) -> Result<&mut Self> {
+        self.qrcode_option(&elements.to_gs_separated(), option.gs1(true))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Print QR code with a [payload](QRCodePayload) (Wi-Fi, vCard, EPC payment, URL) and the option which fits it
+    pub fn qrcode_payload(&mut self, payload: &impl QRCodePayload) -> Result<&mut Self> {
+        let data = payload.data()?;
+        let option = payload.option(&data)?;
+        self.qrcode_option(&data, option)
     }
 
     #[cfg(feature = "codes_2d")]
     /// Construct 2D GS1 DataBar with custom option
     pub fn gs1_databar_2d_option(&mut self, data: &str, option: GS1DataBar2DOption) -> Result<&mut Self> {
+        #[cfg(feature = "graphics")]
+        self.check_code_printer_rendering("2D GS1 DataBar")?;
+
         let code = GS1DataBar2D::new(data, option)?;
         let commands = self.protocol.gs1_databar_2d(&code.data, code.option)?;
         self.command("print 2D GS1 DataBar", commands.as_slice())
@@ -540,74 +798,334 @@ impl<D: Driver> Printer<D> {
         self.gs1_databar_2d_option(data, GS1DataBar2DOption::default())
     }
 
+    #[cfg(feature = "codes_2d")]
+    /// Construct 2D GS1 DataBar of a GS1 element string
+    ///
+    /// See [`GS1ElementString::gs1_databar_2d_data`] for the elements of each type.
+    pub fn gs1_databar_2d_gs1(&mut self, elements: &GS1ElementString, option: GS1DataBar2DOption) -> Result<&mut Self> {
+        let data = elements.gs1_databar_2d_data(option.code_type())?;
+        self.gs1_databar_2d_option(&data, option)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct PDF417
+    fn pdf417_builder(&mut self, data: &[u8], option: Pdf417Option) -> Result<&mut Self> {
+        option.check_data(data)?;
+
+        #[cfg(feature = "graphics")]
+        if self.is_code_image(option.code_rendering()) {
+            return self.code_image("print PDF417 image", &option.raster(data)?);
+        }
+
+        let commands = self.protocol.pdf417(data, option)?;
+        self.command("print PDF417", commands.as_slice())
+    }
+
     #[cfg(feature = "codes_2d")]
     /// PDF417
     pub fn pdf417_option(&mut self, data: &str, option: Pdf417Option) -> Result<&mut Self> {
-        let code = Pdf417::new(data, option);
-        let commands = self.protocol.pdf417(&code.data, code.option)?;
-        self.command("print PDF417", commands.as_slice())
+        let data = option.encoder().encode(data)?;
+        self.pdf417_builder(&data, option)
     }
 
     #[cfg(feature = "codes_2d")]
     /// PDF417
     pub fn pdf417(&mut self, data: &str) -> Result<&mut Self> {
-        let code = Pdf417::new(data, Pdf417Option::default());
-        self.pdf417_option(data, code.option)
+        self.pdf417_option(data, Pdf417Option::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// PDF417 with binary data
+    pub fn pdf417_bytes_option(&mut self, data: &[u8], option: Pdf417Option) -> Result<&mut Self> {
+        self.pdf417_builder(data, option)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// PDF417 with binary data
+    pub fn pdf417_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.pdf417_bytes_option(data, Pdf417Option::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct MaxiCode
+    fn maxi_code_builder(&mut self, data: &[u8], mode: MaxiCodeMode) -> Result<&mut Self> {
+        #[cfg(feature = "graphics")]
+        self.check_code_printer_rendering("MaxiCode")?;
+
+        mode.check_data(data)?;
+        let commands = self.protocol.maxi_code(data, mode)?;
+        self.command("print MaxiCode", commands.as_slice())
     }
 
     #[cfg(feature = "codes_2d")]
     /// MaxiCode
     pub fn maxi_code_option(&mut self, data: &str, mode: MaxiCodeMode) -> Result<&mut Self> {
-        let code = MaxiCode::new(data, mode);
-        let commands = self.protocol.maxi_code(&code.data, code.mode)?;
-        self.command("print MaxiCode", commands.as_slice())
+        self.maxi_code_builder(data.as_bytes(), mode)
     }
 
     #[cfg(feature = "codes_2d")]
     /// MaxiCode
     pub fn maxi_code(&mut self, data: &str) -> Result<&mut Self> {
-        let code = MaxiCode::new(data, MaxiCodeMode::default());
-        self.maxi_code_option(data, code.mode)
+        self.maxi_code_option(data, MaxiCodeMode::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// MaxiCode with binary data
+    pub fn maxi_code_bytes_option(&mut self, data: &[u8], mode: MaxiCodeMode) -> Result<&mut Self> {
+        self.maxi_code_builder(data, mode)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// MaxiCode with binary data
+    pub fn maxi_code_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.maxi_code_bytes_option(data, MaxiCodeMode::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct DataMatrix
+    fn data_matrix_builder(&mut self, data: &[u8], option: DataMatrixOption) -> Result<&mut Self> {
+        option.check_data(data)?;
+
+        #[cfg(feature = "graphics")]
+        if self.is_code_image(option.code_rendering()) {
+            return self.code_image("print DataMatrix image", &option.raster(data)?);
+        }
+        if option.is_gs1() {
+            return Err(PrinterError::Input(
+                "GS1 DataMatrix can only be rendered as images".to_owned(),
+            ));
+        }
+
+        let commands = self.protocol.data_matrix(data, option)?;
+        self.command("print DataMatrix", commands.as_slice())
     }
 
     #[cfg(feature = "codes_2d")]
     /// DataMatrix
     pub fn data_matrix_option(&mut self, data: &str, option: DataMatrixOption) -> Result<&mut Self> {
-        let code = DataMatrix::new(data, option);
-        let commands = self.protocol.data_matrix(&code.data, code.option)?;
-        self.command("print DataMatrix", commands.as_slice())
+        let data = option.encoder().encode(data)?;
+        self.data_matrix_builder(&data, option)
     }
 
     #[cfg(feature = "codes_2d")]
     /// DataMatrix
     pub fn data_matrix(&mut self, data: &str) -> Result<&mut Self> {
-        let code = DataMatrix::new(data, DataMatrixOption::default());
-        self.data_matrix_option(data, code.option)
+        self.data_matrix_option(data, DataMatrixOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix with binary data
+    pub fn data_matrix_bytes_option(&mut self, data: &[u8], option: DataMatrixOption) -> Result<&mut Self> {
+        self.data_matrix_builder(data, option)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// DataMatrix with binary data
+    pub fn data_matrix_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.data_matrix_bytes_option(data, DataMatrixOption::default())
+    }
+
+    #[cfg(all(feature = "codes_2d", feature = "graphics"))]
+    /// GS1 DataMatrix (rendered as an image)
+    pub fn data_matrix_gs1(&mut self, elements: &GS1ElementString) -> Result<&mut Self> {
+        self.data_matrix_gs1_option(elements, DataMatrixOption::default().rendering(CodeRendering::Image))
+    }
+
+    #[cfg(all(feature = "codes_2d", feature = "graphics"))]
+    /// GS1 DataMatrix with option
+    ///
+    /// The option GS1 mode is enabled. The DataMatrix must be rendered as an image.
+    pub fn data_matrix_gs1_option(
+        &mut self,
+        elements: &GS1ElementString,
+        option: DataMatrixOption,
+    ) -> Result<&mut Self> {
+        self.data_matrix_option(&elements.to_gs_separated(), option.gs1(true))
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Construct Aztec code
+    fn aztec_builder(&mut self, data: &[u8], option: AztecOption) -> Result<&mut Self> {
+        option.check_data(data)?;
+
+        #[cfg(feature = "graphics")]
+        if self.is_code_image(option.code_rendering()) {
+            return self.code_image("print Aztec image", &option.raster(data)?);
+        }
+
+        let commands = self.protocol.aztec(data, option)?;
+        self.command("print Aztec", commands.as_slice())
     }
 
     #[cfg(feature = "codes_2d")]
     /// Aztec code
     pub fn aztec_option(&mut self, data: &str, option: AztecOption) -> Result<&mut Self> {
-        let code = Aztec::new(data, option);
-        let commands = self.protocol.aztec(&code.data, code.option)?;
-        self.command("print Aztec", commands.as_slice())
+        let data = option.encoder().encode(data)?;
+        self.aztec_builder(&data, option)
     }
 
     #[cfg(feature = "codes_2d")]
     /// Aztec code
     pub fn aztec(&mut self, data: &str) -> Result<&mut Self> {
-        let code = Aztec::new(data, AztecOption::default());
-        self.aztec_option(data, code.option)
+        self.aztec_option(data, AztecOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code with binary data
+    pub fn aztec_bytes_option(&mut self, data: &[u8], option: AztecOption) -> Result<&mut Self> {
+        self.aztec_builder(data, option)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Aztec code with binary data
+    pub fn aztec_bytes(&mut self, data: &[u8]) -> Result<&mut Self> {
+        self.aztec_bytes_option(data, AztecOption::default())
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the size of a QR code symbol before printing it
+    ///
+    /// The data is stored in the printer without being printed. The request is sent immediately
+    /// (the instructions waiting to be printed are not sent) and the response is read with the driver.
+    pub fn qrcode_symbol_size(&mut self, qrcode: &QRCode) -> Result<CodeSymbolSize> {
+        let cmd = self
+            .protocol
+            .qrcode_size_info(&qrcode.encoded_data()?, &qrcode.option)?;
+        CodeSymbolSize::parse(&self.request("QR code symbol size", &cmd)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the size of a 2D GS1 DataBar symbol before printing it
+    ///
+    /// See [`Printer::qrcode_symbol_size`].
+    pub fn gs1_databar_2d_symbol_size(&mut self, code: &GS1DataBar2D) -> Result<CodeSymbolSize> {
+        let cmd = self.protocol.gs1_databar_2d_size_info(&code.data, &code.option)?;
+        CodeSymbolSize::parse(&self.request("2D GS1 DataBar symbol size", &cmd)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the size of a PDF417 symbol before printing it
+    ///
+    /// See [`Printer::qrcode_symbol_size`].
+    pub fn pdf417_symbol_size(&mut self, code: &Pdf417) -> Result<CodeSymbolSize> {
+        let cmd = self.protocol.pdf417_size_info(&code.encoded_data()?, &code.option)?;
+        CodeSymbolSize::parse(&self.request("PDF417 symbol size", &cmd)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the size of a MaxiCode symbol before printing it
+    ///
+    /// See [`Printer::qrcode_symbol_size`].
+    pub fn maxi_code_symbol_size(&mut self, code: &MaxiCode) -> Result<CodeSymbolSize> {
+        code.mode.check_data(code.data.as_bytes())?;
+        let cmd = self.protocol.maxi_code_size_info(code.data.as_bytes(), code.mode)?;
+        CodeSymbolSize::parse(&self.request("MaxiCode symbol size", &cmd)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the size of a DataMatrix symbol before printing it
+    ///
+    /// See [`Printer::qrcode_symbol_size`].
+    pub fn data_matrix_symbol_size(&mut self, code: &DataMatrix) -> Result<CodeSymbolSize> {
+        let cmd = self
+            .protocol
+            .data_matrix_size_info(&code.encoded_data()?, &code.option)?;
+        CodeSymbolSize::parse(&self.request("DataMatrix symbol size", &cmd)?)
+    }
+
+    #[cfg(feature = "codes_2d")]
+    /// Get the size of an Aztec code symbol before printing it
+    ///
+    /// See [`Printer::qrcode_symbol_size`].
+    pub fn aztec_symbol_size(&mut self, code: &Aztec) -> Result<CodeSymbolSize> {
+        let cmd = self.protocol.aztec_size_info(&code.encoded_data()?, &code.option)?;
+        CodeSymbolSize::parse(&self.request("Aztec code symbol size", &cmd)?)
+    }
+
+    #[cfg(all(feature = "graphics", any(feature = "barcodes", feature = "codes_2d")))]
+    /// Is the code rendered as an image? (the rendering of the code option, else the one of the printer options)
+    fn is_code_image(&self, rendering: Option<CodeRendering>) -> bool {
+        rendering.unwrap_or(self.options.get_code_rendering()) == CodeRendering::Image
+    }
+
+    #[cfg(all(feature = "graphics", feature = "codes_2d"))]
+    /// Check that a code which can not be rendered as an image is not expected to be
+    fn check_code_printer_rendering(&self, name: &str) -> Result<()> {
+        if self.is_code_image(None) {
+            return Err(PrinterError::Input(format!("{name} can not be rendered as an image")));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(all(feature = "graphics", any(feature = "barcodes", feature = "codes_2d")))]
+    /// Print a code rendered as an image
+    ///
+    /// The image is printed with the bit image mode and band height of the printer options.
+    fn code_image(&mut self, label: &str, raster: &RasterBitImage) -> Result<&mut Self> {
+        let commands = self.protocol.raster_bit_image(
+            raster,
+            self.options.get_bit_image_mode(),
+            self.options.get_bit_image_band_height(),
+            self.style_state.line_spacing,
+        );
+        self.bit_image_bands(label, commands)
+    }
+
+    /// Add the bands of a bit image to the instructions
+    ///
+    /// If the band synchronization is enabled, the printer status is requested after each band but the last one
+    /// and the next band is sent once the response has been received.
+    fn bit_image_bands(&mut self, label: &str, commands: Vec<Command>) -> Result<&mut Self> {
+        if !self.options.get_bit_image_band_sync() || commands.len() < 2 {
+            return self.command(label, &commands);
+        }
+
+        let count = commands.len();
+        for (i, command) in commands.into_iter().enumerate() {
+            self.command(&format!("{label} (band {}/{count})", i + 1), &[command])?;
+
+            if i + 1 < count {
+                let instruction = Instruction::with_response(
+                    "wait for bit image band",
+                    &[self.protocol.transmit_paper_sensor_status()],
+                    self.options.get_debug_mode(),
+                );
+                if self.options.get_debug_mode().is_some() {
+                    debug!("{:?}", instruction.clone());
+                }
+                self.instructions.push(instruction);
+            }
+        }
+
+        Ok(self)
     }
 
     #[cfg(feature = "graphics")]
-    /// Print image
-    pub fn bit_image_option(&mut self, path: &str, option: BitImageOption) -> Result<&mut Self> {
+    /// Construct a bit image
+    ///
+    /// The bit image mode and band height of the printer options are used when the option does not set them.
+    /// `commands` builds the image commands from the resolved option and the line spacing to restore.
+    fn bit_image_builder<F>(&mut self, label: &str, option: BitImageOption, commands: F) -> Result<&mut Self>
+    where
+        F: FnOnce(&Protocol, BitImageOption, Option<u8>) -> Result<Vec<Command>>,
+    {
         let cmd = self.protocol.cancel();
         self.command("cancel data", &[cmd])?;
 
-        let cmd = self.protocol.bit_image(path, option)?;
-        self.command("print bit image", &[cmd])
+        let option = option
+            .default_mode(self.options.get_bit_image_mode())
+            .default_band_height(self.options.get_bit_image_band_height());
+        let commands = commands(&self.protocol, option, self.style_state.line_spacing)?;
+        self.bit_image_bands(label, commands)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image
+    pub fn bit_image_option(&mut self, path: &str, option: BitImageOption) -> Result<&mut Self> {
+        self.bit_image_builder("print bit image", option, |protocol, option, line_spacing| {
+            protocol.bit_image(path, option, line_spacing)
+        })
     }
 
     #[cfg(feature = "graphics")]
@@ -619,11 +1137,11 @@ impl<D: Driver> Printer<D> {
     #[cfg(feature = "graphics")]
     /// Print image
     pub fn bit_image_from_bytes_option(&mut self, bytes: &[u8], option: BitImageOption) -> Result<&mut Self> {
-        let cmd = self.protocol.cancel();
-        self.command("cancel data", &[cmd])?;
-
-        let cmd = self.protocol.bit_image_from_bytes(bytes, option)?;
-        self.command("print bit image from bytes", &[cmd])
+        self.bit_image_builder(
+            "print bit image from bytes",
+            option,
+            |protocol, option, line_spacing| protocol.bit_image_from_bytes(bytes, option, line_spacing),
+        )
     }
 
     #[cfg(feature = "graphics")]
@@ -632,6 +1150,225 @@ impl<D: Driver> Printer<D> {
         self.bit_image_from_bytes_option(bytes, BitImageOption::default())
     }
 
+    #[cfg(feature = "graphics")]
+    /// Print image from `DynamicImage`
+    pub fn bit_image_from_dynamic_image_option(
+        &mut self,
+        img: image::DynamicImage,
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        self.bit_image_builder(
+            "print bit image from dynamic image",
+            option,
+            |protocol, option, line_spacing| protocol.bit_image_from_dynamic_image(img, option, line_spacing),
+        )
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from `DynamicImage`
+    pub fn bit_image_from_dynamic_image(&mut self, img: image::DynamicImage) -> Result<&mut Self> {
+        self.bit_image_from_dynamic_image_option(img, BitImageOption::default())
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from RGBA pixels (4 bytes per pixel, row by row)
+    pub fn bit_image_from_rgba_option(
+        &mut self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        self.bit_image_builder(
+            "print bit image from RGBA pixels",
+            option,
+            |protocol, option, line_spacing| protocol.bit_image_from_rgba(pixels, width, height, option, line_spacing),
+        )
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from RGBA pixels (4 bytes per pixel, row by row)
+    pub fn bit_image_from_rgba(&mut self, pixels: &[u8], width: u32, height: u32) -> Result<&mut Self> {
+        self.bit_image_from_rgba_option(pixels, width, height, BitImageOption::default())
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from luma pixels (1 byte per pixel, row by row, 0 is black)
+    pub fn bit_image_from_luma_option(
+        &mut self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        self.bit_image_builder(
+            "print bit image from luma pixels",
+            option,
+            |protocol, option, line_spacing| protocol.bit_image_from_luma(pixels, width, height, option, line_spacing),
+        )
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print image from luma pixels (1 byte per pixel, row by row, 0 is black)
+    pub fn bit_image_from_luma(&mut self, pixels: &[u8], width: u32, height: u32) -> Result<&mut Self> {
+        self.bit_image_from_luma_option(pixels, width, height, BitImageOption::default())
+    }
+
+    /// Print raster bit image
+    ///
+    /// The image is already packed in raster format (see [`RasterBitImage`]),
+    /// so the `graphics` feature is not required.
+    /// It is printed with the bit image mode and band height of the printer options.
+    pub fn raster_bit_image(&mut self, raster: &RasterBitImage) -> Result<&mut Self> {
+        let cmd = self.protocol.cancel();
+        self.command("cancel data", &[cmd])?;
+
+        let commands = self.protocol.raster_bit_image(
+            raster,
+            self.options.get_bit_image_mode(),
+            self.options.get_bit_image_band_height(),
+            self.style_state.line_spacing,
+        );
+        self.bit_image_bands("print raster bit image", commands)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic with option
+    ///
+    /// The image is stored in the print buffer (`GS ( L` / `GS 8 L`) and then printed.
+    /// It supports multiple tone (4-level gray) and 2-color printing (see [`GraphicOption`]).
+    pub fn graphic_option(&mut self, path: &str, option: GraphicOption) -> Result<&mut Self> {
+        let commands = self.protocol.graphic(path, option)?;
+        self.command("print graphic", &commands)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic
+    pub fn graphic(&mut self, path: &str) -> Result<&mut Self> {
+        self.graphic_option(path, GraphicOption::default())
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic from bytes with option
+    pub fn graphic_from_bytes_option(&mut self, bytes: &[u8], option: GraphicOption) -> Result<&mut Self> {
+        let commands = self.protocol.graphic_from_bytes(bytes, option)?;
+        self.command("print graphic from bytes", &commands)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print graphic from bytes
+    pub fn graphic_from_bytes(&mut self, bytes: &[u8]) -> Result<&mut Self> {
+        self.graphic_from_bytes_option(bytes, GraphicOption::default())
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store an image in the NV graphics memory
+    ///
+    /// The image is stored under the given key code (2 characters, e.g. `"LG"`) and can then be printed
+    /// with [`Printer::nv_graphic`] without being sent again.
+    ///
+    /// The NV memory has a limited number of write cycles: store the image once (e.g. at installation)
+    /// instead of before each print.
+    pub fn nv_graphic_store(&mut self, key_code: &str, path: &str, option: BitImageOption) -> Result<&mut Self> {
+        let cmd = self.protocol.nv_graphic_store(key_code.try_into()?, path, option)?;
+        self.command("store NV graphic", &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Store an image from bytes in the NV graphics memory
+    ///
+    /// See [`Printer::nv_graphic_store`].
+    pub fn nv_graphic_store_from_bytes(
+        &mut self,
+        key_code: &str,
+        bytes: &[u8],
+        option: BitImageOption,
+    ) -> Result<&mut Self> {
+        let cmd = self
+            .protocol
+            .nv_graphic_store_from_bytes(key_code.try_into()?, bytes, option)?;
+        self.command("store NV graphic from bytes", &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print NV graphic with option
+    pub fn nv_graphic_option(
+        &mut self,
+        key_code: &str,
+        width_size: GraphicSize,
+        height_size: GraphicSize,
+    ) -> Result<&mut Self> {
+        let cmd = self
+            .protocol
+            .nv_graphic_print(key_code.try_into()?, width_size, height_size);
+        self.command("print NV graphic", &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Print NV graphic
+    pub fn nv_graphic(&mut self, key_code: &str) -> Result<&mut Self> {
+        self.nv_graphic_option(key_code, GraphicSize::Normal, GraphicSize::Normal)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete NV graphic
+    pub fn nv_graphic_delete(&mut self, key_code: &str) -> Result<&mut Self> {
+        let cmd = self.protocol.nv_graphic_delete(key_code.try_into()?);
+        self.command("delete NV graphic", &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Delete all NV graphics
+    pub fn nv_graphics_delete_all(&mut self) -> Result<&mut Self> {
+        let cmd = self.protocol.nv_graphics_delete_all();
+        self.command("delete all NV graphics", &[cmd])
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the key codes of the graphics stored in the NV graphics memory
+    ///
+    /// The request is sent immediately (the instructions waiting to be printed are not sent)
+    /// and the response is read with the driver.
+    pub fn nv_graphics_key_codes(&mut self) -> Result<Vec<NvGraphicKeyCode>> {
+        let cmd = self.protocol.nv_graphics_key_codes();
+        let mut response = self.request("NV graphics key codes", &cmd)?;
+        let mut key_codes = Vec::new();
+
+        loop {
+            let (mut codes, has_more) = NvGraphicKeyCode::parse_list(&response)?;
+            key_codes.append(&mut codes);
+
+            if !has_more {
+                break;
+            }
+            response = self.request("NV graphics next key codes", &[ACK])?;
+        }
+
+        Ok(key_codes)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the NV graphics memory capacity (in bytes)
+    ///
+    /// The request is sent immediately (the instructions waiting to be printed are not sent)
+    /// and the response is read with the driver.
+    pub fn nv_graphics_capacity(&mut self) -> Result<u32> {
+        let cmd = self.protocol.nv_graphics_capacity();
+        let response = self.request("NV graphics capacity", &cmd)?;
+        nv_graphics::parse_nv_graphics_capacity(&response, nv_graphics::NV_GRAPHICS_CAPACITY_IDENTIFIER)
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the remaining capacity of the NV graphics memory (in bytes)
+    ///
+    /// The request is sent immediately (the instructions waiting to be printed are not sent)
+    /// and the response is read with the driver.
+    pub fn nv_graphics_remaining_capacity(&mut self) -> Result<u32> {
+        let cmd = self.protocol.nv_graphics_remaining_capacity();
+        let response = self.request("NV graphics remaining capacity", &cmd)?;
+        nv_graphics::parse_nv_graphics_capacity(&response, nv_graphics::NV_GRAPHICS_REMAINING_CAPACITY_IDENTIFIER)
+    }
+
     #[cfg(feature = "ui")]
     /// Print line
     pub fn draw_line(&mut self, line: Line) -> Result<&mut Self> {
@@ -640,20 +1377,6 @@ impl<D: Driver> Printer<D> {
             .draw_line(line, self.options.clone(), self.style_state.clone())?;
         self.command("draw line", commands.as_slice())
     }
-
-    // #[cfg(feature = "graphics")]
-    // /// Print image
-    // fn _image(&mut self, path: &str) -> Result<&mut Self> {
-    //     let cmd = self.protocol.graphic_density(GraphicDensity::Low);
-    //     self.command("set graphic density", cmd)?;
-    //
-    //     let cmd = self.protocol.graphic_data(path)?;
-    //     self.command("set graphic data", cmd)?;
-    //
-    //     // Print
-    //     let cmd = self.protocol.graphic_print();
-    //     self.command("print graphic", cmd)
-    // }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -666,6 +1389,8 @@ pub struct PrinterStyleState {
     pub double_strike: bool,
     pub reverse: bool,
     pub flip: bool,
+    /// Line spacing set with `ESC 3` (`None`: default line spacing)
+    pub line_spacing: Option<u8>,
 }
 
 impl Default for PrinterStyleState {
@@ -679,6 +1404,7 @@ impl Default for PrinterStyleState {
             double_strike: false,
             reverse: false,
             flip: false,
+            line_spacing: None,
         }
     }
 }
@@ -689,6 +1415,45 @@ impl PrinterStyleState {}
 mod tests {
     use super::*;
     use crate::driver::ConsoleDriver;
+    use alloc::{collections::VecDeque, string::String};
+    use core::cell::RefCell;
+
+    /// Driver returning predefined responses
+    #[derive(Default)]
+    struct ResponseDriver {
+        written: RefCell<Vec<u8>>,
+        responses: RefCell<VecDeque<Vec<u8>>>,
+    }
+
+    impl ResponseDriver {
+        fn new(responses: &[&[u8]]) -> Self {
+            Self {
+                written: RefCell::new(Vec::new()),
+                responses: RefCell::new(responses.iter().map(|r| r.to_vec()).collect()),
+            }
+        }
+    }
+
+    impl Driver for ResponseDriver {
+        fn name(&self) -> String {
+            "response".into()
+        }
+
+        fn write(&self, data: &[u8]) -> Result<()> {
+            self.written.borrow_mut().extend_from_slice(data);
+            Ok(())
+        }
+
+        fn read(&self, buf: &mut [u8]) -> Result<usize> {
+            let response = self.responses.borrow_mut().pop_front().unwrap_or_default();
+            buf[..response.len()].copy_from_slice(&response);
+            Ok(response.len())
+        }
+
+        fn flush(&self) -> Result<()> {
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_command() {
@@ -706,4 +1471,273 @@ mod tests {
 
         assert_eq!(printer.instructions, expected);
     }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphics_key_codes() {
+        let driver = ResponseDriver::new(&[b"\x37\x72\x41LG", b"A1\x00", b"\x37\x72\x40B2\x00"]);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer.writeln("not sent").unwrap();
+
+        let key_codes = printer.nv_graphics_key_codes().unwrap();
+        assert_eq!(
+            key_codes.iter().map(|kc| kc.to_string()).collect::<Vec<_>>(),
+            vec!["LG", "A1", "B2"]
+        );
+        assert_eq!(
+            printer.driver.written.borrow().as_slice(),
+            &[29, 40, 76, 4, 0, 48, 64, 75, 67, ACK]
+        );
+        assert_eq!(printer.instructions.len(), 2);
+    }
+
+    #[cfg(feature = "graphics")]
+    #[test]
+    fn test_nv_graphics_capacity() {
+        let driver = ResponseDriver::new(&[b"\x37\x30262144\x00", b"\x37\x311024\x00"]);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+
+        assert_eq!(printer.nv_graphics_capacity().unwrap(), 262_144);
+        assert_eq!(printer.nv_graphics_remaining_capacity().unwrap(), 1_024);
+        assert!(printer.nv_graphics_capacity().is_err());
+    }
+
+    /// Printer options with 1 line bands and band synchronization
+    fn options_sync() -> PrinterOptions {
+        let mut options = PrinterOptions::default();
+        options.bit_image_band_height(Some(1));
+        options.bit_image_band_sync(true);
+        options
+    }
+
+    #[test]
+    fn test_raster_bit_image_band_sync() {
+        let driver = ResponseDriver::new(&[&[0x00], &[0x00]]);
+        let mut printer = Printer::new(driver, Protocol::default(), Some(options_sync()));
+
+        let raster = RasterBitImage::new(8, 3, vec![1, 2, 3], BitImageSize::Normal).unwrap();
+        printer.raster_bit_image(&raster).unwrap().print().unwrap();
+
+        assert_eq!(
+            printer.driver.written.borrow().as_slice(),
+            &[
+                24, 29, 118, 48, 0, 1, 0, 1, 0, 1, 29, 114, 1, 29, 118, 48, 0, 1, 0, 1, 0, 2, 29, 114, 1, 29, 118, 48,
+                0, 1, 0, 1, 0, 3
+            ]
+        );
+
+        // No response from the printer
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options_sync()));
+        assert!(printer.raster_bit_image(&raster).unwrap().print().is_err());
+    }
+
+    #[test]
+    fn test_raster_bit_image_column_line_spacing() {
+        let mut options = PrinterOptions::default();
+        options.bit_image_mode(BitImageMode::Column8DoubleDensity);
+        let raster = RasterBitImage::new(8, 3, vec![1, 2, 3], BitImageSize::Normal).unwrap();
+
+        // The line spacing of the user is restored after the image
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        printer
+            .line_spacing(40)
+            .unwrap()
+            .raster_bit_image(&raster)
+            .unwrap()
+            .print()
+            .unwrap();
+        let written = printer.driver.written.borrow();
+        assert!(written.starts_with(&[27, 51, 40, 24, 27, 51, 24, 27, 42]));
+        assert!(written.ends_with(&[10, 27, 51, 40]));
+
+        // The line spacing is kept after printing the buffer
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        printer.line_spacing(40).unwrap().print().unwrap();
+        assert_eq!(printer.style_state().line_spacing, Some(40));
+        printer.raster_bit_image(&raster).unwrap().print().unwrap();
+        let written = printer.driver.written.borrow();
+        assert!(written.starts_with(&[27, 51, 40, 24, 27, 51, 24, 27, 42]));
+        assert!(written.ends_with(&[10, 27, 51, 40]));
+        drop(written);
+        printer.reset().unwrap();
+        assert_eq!(printer.style_state().line_spacing, None);
+
+        // Default line spacing
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options));
+        printer.raster_bit_image(&raster).unwrap().print().unwrap();
+        assert!(printer.driver.written.borrow().ends_with(&[10, 27, 50]));
+    }
+
+    #[cfg(all(feature = "graphics", feature = "barcodes", feature = "codes_2d"))]
+    #[test]
+    fn test_code_rendering_image() {
+        let mut options = PrinterOptions::default();
+        options.code_rendering(CodeRendering::Image);
+        let raster_commands = |raster: RasterBitImage| {
+            Protocol::default()
+                .raster_bit_image(&raster, BitImageMode::Raster, None, None)
+                .concat()
+        };
+
+        // QR code rendered with the printer options
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        let option = || QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::H);
+        printer.qrcode_option("Hello", option()).unwrap().print().unwrap();
+        let qrcode = QRCode::new("Hello", Some(option())).unwrap();
+        assert_eq!(
+            printer.driver.written.borrow().as_slice(),
+            raster_commands(qrcode.raster().unwrap())
+        );
+
+        // Model 1 QR codes (the default model) are rendered as model 2 QR codes
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        printer.qrcode("Hello").unwrap().print().unwrap();
+        let qrcode = QRCode::new("Hello", Some(option())).unwrap();
+        assert_eq!(
+            printer.driver.written.borrow().as_slice(),
+            raster_commands(qrcode.raster().unwrap())
+        );
+
+        // Barcode with HRI characters below
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        printer.ean8("9638507").unwrap().print().unwrap();
+        let barcode = Barcode::new(BarcodeSystem::EAN8, "9638507", BarcodeOption::default()).unwrap();
+        let mut expected = raster_commands(barcode.raster().unwrap());
+        expected.extend_from_slice(b"96385074");
+        expected.extend_from_slice(&[27, 100, 1]);
+        assert_eq!(printer.driver.written.borrow().as_slice(), expected);
+
+        // Rendering of the code option
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        let option = DataMatrixOption::default().rendering(CodeRendering::Printer);
+        printer.data_matrix_option("123456", option).unwrap().print().unwrap();
+        assert!(printer.driver.written.borrow().starts_with(&[29, 40, 107]));
+
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        let option = DataMatrixOption::default().rendering(CodeRendering::Image);
+        printer.data_matrix_option("123456", option).unwrap().print().unwrap();
+        assert!(printer.driver.written.borrow().starts_with(&[29, 118, 48]));
+
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        printer.aztec("Hello").unwrap().print().unwrap();
+        assert!(printer.driver.written.borrow().starts_with(&[29, 118, 48]));
+
+        // PDF417 rendered with the printer options
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options.clone()));
+        printer.pdf417("Hello").unwrap().print().unwrap();
+        let pdf417 = Pdf417::new("Hello", Pdf417Option::default());
+        assert_eq!(
+            printer.driver.written.borrow().as_slice(),
+            raster_commands(pdf417.raster().unwrap())
+        );
+
+        // Codes which can not be rendered as images
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), Some(options));
+        assert!(printer.maxi_code("Hello").is_err());
+    }
+
+    #[cfg(all(feature = "graphics", feature = "codes_2d"))]
+    #[test]
+    fn test_codes_2d_gs1() {
+        let elements = GS1ElementString::new()
+            .gtin("09501101530003")
+            .unwrap()
+            .batch("AB-123")
+            .unwrap();
+
+        // Rendered as images
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        printer.qrcode_gs1(&elements).unwrap().print().unwrap();
+        assert!(printer.driver.written.borrow().starts_with(&[29, 118, 48]));
+
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        printer.data_matrix_gs1(&elements).unwrap().print().unwrap();
+        assert!(printer.driver.written.borrow().starts_with(&[29, 118, 48]));
+
+        // The GS1 mode can not be set with the printer rendering
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M);
+        assert!(printer.qrcode_gs1_option(&elements, option).is_err());
+        assert!(
+            printer
+                .data_matrix_gs1_option(&elements, DataMatrixOption::default())
+                .is_err()
+        );
+        assert!(
+            printer
+                .data_matrix_option("0109501101530003", DataMatrixOption::default().gs1(true))
+                .is_err()
+        );
+
+        // 2D GS1 DataBar
+        let elements = GS1ElementString::new().gtin("09501101530003").unwrap();
+        let option = GS1DataBar2DOption::new(GS1DataBar2DWidth::default(), GS1DataBar2DType::Stacked);
+        printer.gs1_databar_2d_gs1(&elements, option).unwrap().print().unwrap();
+        assert!(
+            printer
+                .driver
+                .written
+                .borrow()
+                .windows(13)
+                .any(|data| data == b"0950110153000")
+        );
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_codes_2d_bytes() {
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        printer.pdf417_bytes(&[0x00, 0xFF]).unwrap().print().unwrap();
+        assert!(
+            printer
+                .driver
+                .written
+                .borrow()
+                .ends_with(&[29, 40, 107, 5, 0, 48, 80, 48, 0x00, 0xFF, 29, 40, 107, 3, 0, 48, 81, 48])
+        );
+
+        // Text encoding
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        let option = AztecOption::default().encoding(crate::utils::Encoder::new(encoding_rs::WINDOWS_1252));
+        printer.aztec_option("é", option).unwrap().print().unwrap();
+        assert!(
+            printer
+                .driver
+                .written
+                .borrow()
+                .ends_with(&[29, 40, 107, 4, 0, 53, 80, 48, 0xE9, 29, 40, 107, 3, 0, 53, 81, 48])
+        );
+
+        // Capacity
+        let mut printer = Printer::new(ResponseDriver::default(), Protocol::default(), None);
+        assert!(printer.qrcode_bytes(&[0xFF; 211]).is_err());
+        assert!(printer.data_matrix_bytes(&[0xFF; 1557]).is_err());
+        assert!(printer.maxi_code(&"a".repeat(94)).is_err());
+    }
+
+    #[cfg(feature = "codes_2d")]
+    #[test]
+    fn test_qrcode_symbol_size() {
+        let driver = ResponseDriver::new(&[b"\x37\x76", b"116\x1F116\x1F0\x00"]);
+        let mut printer = Printer::new(driver, Protocol::default(), None);
+        printer.writeln("not sent").unwrap();
+
+        let option = QRCodeOption::new(QRCodeModel::Model2, 4, QRCodeCorrectionLevel::M);
+        let size = printer
+            .qrcode_symbol_size(&QRCode::new("Hello", Some(option)).unwrap())
+            .unwrap();
+        assert_eq!((size.width(), size.height(), size.fits()), (116, 116, true));
+        assert!(
+            printer
+                .driver
+                .written
+                .borrow()
+                .ends_with(&[29, 40, 107, 3, 0, 49, 82, 48])
+        );
+        assert_eq!(printer.instructions.len(), 2);
+
+        // No response from the printer
+        let code = Pdf417::new("Hello", Pdf417Option::default());
+        assert!(printer.pdf417_symbol_size(&code).is_err());
+    }
 }
@@ -1,6 +1,8 @@
 //! Printer options
 
-use crate::domain::{DebugMode, PageCode};
+#[cfg(feature = "graphics")]
+use crate::domain::CodeRendering;
+use crate::domain::{BitImageMode, DebugMode, PageCode};
 
 /// Printer options
 #[derive(Debug, Clone)]
@@ -13,6 +15,19 @@ pub struct PrinterOptions {
 
     /// Number of characters per line (default: 42)
     characters_per_line: u8,
+
+    /// Default [bit image mode](BitImageMode) (default: raster)
+    bit_image_mode: BitImageMode,
+
+    /// Default bit image band height in lines (default: no banding)
+    bit_image_band_height: Option<u16>,
+
+    /// Wait for a status response between the bit image bands (default: false)
+    bit_image_band_sync: bool,
+
+    #[cfg(feature = "graphics")]
+    /// Default [rendering](CodeRendering) of the barcodes and 2D codes (default: printer)
+    code_rendering: CodeRendering,
 }
 
 impl Default for PrinterOptions {
@@ -20,19 +35,27 @@ impl Default for PrinterOptions {
     ///
     /// ```
     /// use escpos::printer_options::PrinterOptions;
-    /// use escpos::utils::{DebugMode, PageCode, DEFAULT_CHARACTERS_PER_LINE};
+    /// use escpos::utils::{BitImageMode, DebugMode, PageCode, DEFAULT_CHARACTERS_PER_LINE};
     ///
     /// let options = PrinterOptions::default();
     ///
     /// assert_eq!(options.get_page_code(), None);
     /// assert_eq!(options.get_debug_mode(), None);
     /// assert_eq!(options.get_characters_per_line(), DEFAULT_CHARACTERS_PER_LINE);
+    /// assert_eq!(options.get_bit_image_mode(), BitImageMode::Raster);
+    /// assert_eq!(options.get_bit_image_band_height(), None);
+    /// assert!(!options.get_bit_image_band_sync());
     /// ```
     fn default() -> Self {
         Self {
             page_code: None,
             debug_mode: None,
             characters_per_line: 42,
+            bit_image_mode: BitImageMode::default(),
+            bit_image_band_height: None,
+            bit_image_band_sync: false,
+            #[cfg(feature = "graphics")]
+            code_rendering: CodeRendering::default(),
         }
     }
 }
@@ -55,6 +78,11 @@ impl PrinterOptions {
             page_code,
             characters_per_line,
             debug_mode,
+            bit_image_mode: BitImageMode::default(),
+            bit_image_band_height: None,
+            bit_image_band_sync: false,
+            #[cfg(feature = "graphics")]
+            code_rendering: CodeRendering::default(),
         }
     }
 
@@ -116,4 +144,99 @@ impl PrinterOptions {
     pub fn debug_mode(&mut self, debug_mode: Option<DebugMode>) {
         self.debug_mode = debug_mode;
     }
+
+    /// Get the default [bit image mode](BitImageMode)
+    pub fn get_bit_image_mode(&self) -> BitImageMode {
+        self.bit_image_mode
+    }
+
+    /// Set the default [bit image mode](BitImageMode)
+    ///
+    /// It is used when the bit image option does not set a mode, for example to print images
+    /// with `ESC *` on older printers which ignore `GS v 0`.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    /// use escpos::utils::BitImageMode;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.bit_image_mode(BitImageMode::Column24DoubleDensity);
+    ///
+    /// assert_eq!(printer_options.get_bit_image_mode(), BitImageMode::Column24DoubleDensity);
+    /// ```
+    pub fn bit_image_mode(&mut self, bit_image_mode: BitImageMode) {
+        self.bit_image_mode = bit_image_mode;
+    }
+
+    /// Get the default bit image band height (in lines)
+    pub fn get_bit_image_band_height(&self) -> Option<u16> {
+        self.bit_image_band_height
+    }
+
+    /// Set the default bit image band height (in lines)
+    ///
+    /// Tall images are split in bands sent as consecutive commands to avoid overflowing
+    /// the printer receive buffer. It is used when the bit image option does not set a band height.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.bit_image_band_height(Some(256));
+    ///
+    /// assert_eq!(printer_options.get_bit_image_band_height(), Some(256));
+    /// ```
+    pub fn bit_image_band_height(&mut self, bit_image_band_height: Option<u16>) {
+        self.bit_image_band_height = bit_image_band_height.filter(|height| *height > 0);
+    }
+
+    /// Get the bit image band synchronization
+    pub fn get_bit_image_band_sync(&self) -> bool {
+        self.bit_image_band_sync
+    }
+
+    /// Wait for a status response (`GS r 1`) after each bit image band but the last one
+    ///
+    /// The status is only sent by the printer once the previous band has been processed,
+    /// so the next band is not sent while the receive buffer is still full.
+    /// The driver must be able to read the printer responses.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.bit_image_band_sync(true);
+    ///
+    /// assert!(printer_options.get_bit_image_band_sync());
+    /// ```
+    pub fn bit_image_band_sync(&mut self, bit_image_band_sync: bool) {
+        self.bit_image_band_sync = bit_image_band_sync;
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Get the default [rendering](CodeRendering) of the barcodes and 2D codes
+    pub fn get_code_rendering(&self) -> CodeRendering {
+        self.code_rendering
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the default [rendering](CodeRendering) of the barcodes and 2D codes
+    ///
+    /// With [`CodeRendering::Image`], barcodes, QR codes, DataMatrix, Aztec codes and PDF417 are generated in software
+    /// and printed as raster bit images, for printers which do not support the corresponding commands.
+    /// Model 1 QR codes are rendered as model 2 QR codes. The codes which can not be rendered as images
+    /// (MaxiCode, 2D GS1 DataBar and micro QR codes) return an error. It is used when the code option does not set a rendering.
+    ///
+    /// ```
+    /// use escpos::printer_options::PrinterOptions;
+    /// use escpos::utils::CodeRendering;
+    ///
+    /// let mut printer_options = PrinterOptions::default();
+    /// printer_options.code_rendering(CodeRendering::Image);
+    ///
+    /// assert_eq!(printer_options.get_code_rendering(), CodeRendering::Image);
+    /// ```
+    pub fn code_rendering(&mut self, code_rendering: CodeRendering) {
+        self.code_rendering = code_rendering;
+    }
 }